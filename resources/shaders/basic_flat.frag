@@ -0,0 +1,7 @@
+#version 330 core
+flat in vec3 vertexColor;
+out vec4 FragColor;
+
+void main() {
+    FragColor = vec4(vertexColor, 1.0);
+}
\ No newline at end of file
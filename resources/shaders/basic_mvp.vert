@@ -0,0 +1,13 @@
+#version 330 core
+layout (location = 0) in vec3 aPos;
+layout (location = 1) in vec3 aColor;
+
+// projection * view * model, multiplied once per draw on the CPU
+uniform mat4 mvp;
+
+out vec3 vertexColor;
+
+void main() {
+    gl_Position = mvp * vec4(aPos, 1.0);
+    vertexColor = aColor;
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+#version 330 core
+
+void main() {
+    // Depth is written by the fixed-function pipeline
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+#version 330 core
+in vec2 texCoord;
+out vec4 FragColor;
+
+uniform sampler2D depthTexture;
+uniform float near;
+uniform float far;
+
+void main() {
+    // Undo the perspective divide so equal gray steps are equal distances
+    float depth = texture(depthTexture, texCoord).r;
+    float ndc = depth * 2.0 - 1.0;
+    float linear = (2.0 * near * far) / (far + near - ndc * (far - near));
+    FragColor = vec4(vec3((linear - near) / (far - near)), 1.0);
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+#version 330 core
+out vec4 FragColor;
+
+void main() {
+    // Unmistakable magenta marks geometry whose shader failed to compile
+    FragColor = vec4(1.0, 0.0, 1.0, 1.0);
+}
\ No newline at end of file
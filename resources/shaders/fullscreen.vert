@@ -0,0 +1,9 @@
+#version 330 core
+out vec2 texCoord;
+
+void main() {
+    // Vertices (0,0), (2,0), (0,2) form one counter-clockwise triangle covering the screen
+    vec2 position = vec2(float((gl_VertexID << 1) & 2), float(gl_VertexID & 2));
+    texCoord = position;
+    gl_Position = vec4(position * 2.0 - 1.0, 0.0, 1.0);
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+#version 330 core
+in vec2 texCoord;
+out vec4 FragColor;
+
+uniform sampler2D screenTexture;
+
+const float EDGE_THRESHOLD_MIN = 0.0312;
+const float EDGE_THRESHOLD_MAX = 0.125;
+const float SPAN_MAX = 8.0;
+
+float luma(vec3 color) {
+    return dot(color, vec3(0.299, 0.587, 0.114));
+}
+
+void main() {
+    vec2 texel = 1.0 / vec2(textureSize(screenTexture, 0));
+    
+    vec4 center = texture(screenTexture, texCoord);
+    float lumaCenter = luma(center.rgb);
+    float lumaNW = luma(texture(screenTexture, texCoord + vec2(-1.0, -1.0) * texel).rgb);
+    float lumaNE = luma(texture(screenTexture, texCoord + vec2( 1.0, -1.0) * texel).rgb);
+    float lumaSW = luma(texture(screenTexture, texCoord + vec2(-1.0,  1.0) * texel).rgb);
+    float lumaSE = luma(texture(screenTexture, texCoord + vec2( 1.0,  1.0) * texel).rgb);
+    
+    float lumaMin = min(lumaCenter, min(min(lumaNW, lumaNE), min(lumaSW, lumaSE)));
+    float lumaMax = max(lumaCenter, max(max(lumaNW, lumaNE), max(lumaSW, lumaSE)));
+    
+    // Skip pixels without enough local contrast to be an edge
+    if (lumaMax - lumaMin < max(EDGE_THRESHOLD_MIN, lumaMax * EDGE_THRESHOLD_MAX)) {
+        FragColor = center;
+        return;
+    }
+    
+    // Blur along the edge, perpendicular to the luma gradient
+    vec2 direction = vec2(
+        -((lumaNW + lumaNE) - (lumaSW + lumaSE)),
+         ((lumaNW + lumaSW) - (lumaNE + lumaSE))
+    );
+    float reduce = max((lumaNW + lumaNE + lumaSW + lumaSE) * 0.03125, 1.0 / 128.0);
+    float scale = 1.0 / (min(abs(direction.x), abs(direction.y)) + reduce);
+    direction = clamp(direction * scale, vec2(-SPAN_MAX), vec2(SPAN_MAX)) * texel;
+    
+    vec3 near = 0.5 * (
+        texture(screenTexture, texCoord + direction * (1.0 / 3.0 - 0.5)).rgb +
+        texture(screenTexture, texCoord + direction * (2.0 / 3.0 - 0.5)).rgb);
+    vec3 far = near * 0.5 + 0.25 * (
+        texture(screenTexture, texCoord - direction * 0.5).rgb +
+        texture(screenTexture, texCoord + direction * 0.5).rgb);
+    
+    float lumaFar = luma(far);
+    FragColor = vec4((lumaFar < lumaMin || lumaFar > lumaMax) ? near : far, center.a);
+}
\ No newline at end of file
@@ -0,0 +1,10 @@
+#version 330 core
+in vec2 texCoord;
+out vec4 FragColor;
+
+uniform vec4 topColor;
+uniform vec4 bottomColor;
+
+void main() {
+    FragColor = mix(bottomColor, topColor, texCoord.y);
+}
\ No newline at end of file
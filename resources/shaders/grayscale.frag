@@ -0,0 +1,11 @@
+#version 330 core
+in vec2 texCoord;
+out vec4 FragColor;
+
+uniform sampler2D screenTexture;
+
+void main() {
+    vec4 color = texture(screenTexture, texCoord);
+    float luminance = dot(color.rgb, vec3(0.2126, 0.7152, 0.0722));
+    FragColor = vec4(vec3(luminance), color.a);
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+#version 330 core
+// Written unchanged into an R32UI attachment; 0 is reserved for the background
+uniform highp uint objectId;
+out highp uint FragId;
+
+void main() {
+    FragId = objectId;
+}
\ No newline at end of file
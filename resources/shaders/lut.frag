@@ -0,0 +1,16 @@
+#version 330 core
+precision mediump sampler3D;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+uniform sampler2D screenTexture;
+uniform sampler3D lut;
+
+void main() {
+    vec4 color = texture(screenTexture, texCoord);
+    // Sample voxel centers so 0 and 1 map to the first and last LUT entries
+    float size = float(textureSize(lut, 0).x);
+    vec3 coord = clamp(color.rgb, 0.0, 1.0) * ((size - 1.0) / size) + 0.5 / size;
+    FragColor = vec4(texture(lut, coord).rgb, color.a);
+}
\ No newline at end of file
@@ -0,0 +1,20 @@
+#version 330 core
+in vec2 texCoord;
+in mat3 tbn;
+out vec4 FragColor;
+
+uniform sampler2D albedoMap;
+uniform sampler2D normalMap;
+// World-space direction the light travels in
+uniform vec3 lightDirection;
+uniform vec3 lightColor;
+uniform float ambient;
+
+void main() {
+    // Tangent-space normal stored as 0..1 color, rotated into world space
+    vec3 normal = normalize(tbn * (texture(normalMap, texCoord).rgb * 2.0 - 1.0));
+    float diffuse = max(dot(normal, -normalize(lightDirection)), 0.0);
+    
+    vec4 albedo = texture(albedoMap, texCoord);
+    FragColor = vec4(albedo.rgb * lightColor * (ambient + diffuse), albedo.a);
+}
\ No newline at end of file
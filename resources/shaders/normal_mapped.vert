@@ -0,0 +1,25 @@
+#version 330 core
+layout (location = 0) in vec3 aPos;
+layout (location = 1) in vec3 aNormal;
+layout (location = 2) in vec2 aTexCoord;
+layout (location = 3) in vec4 aTangent;
+
+uniform mat4 model;
+uniform mat4 view;
+uniform mat4 projection;
+
+out vec2 texCoord;
+out mat3 tbn;
+
+void main() {
+    // Normals use the inverse transpose so non-uniform scaling keeps them perpendicular
+    vec3 normal = normalize(mat3(transpose(inverse(model))) * aNormal);
+    vec3 tangent = normalize(mat3(model) * aTangent.xyz);
+    // Re-orthogonalize against the normal; w flips the bitangent for mirrored UVs
+    tangent = normalize(tangent - dot(tangent, normal) * normal);
+    vec3 bitangent = cross(normal, tangent) * aTangent.w;
+    
+    tbn = mat3(tangent, bitangent, normal);
+    texCoord = aTexCoord;
+    gl_Position = projection * view * model * vec4(aPos, 1.0);
+}
\ No newline at end of file
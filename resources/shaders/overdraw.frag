@@ -0,0 +1,9 @@
+#version 330 core
+out vec4 FragColor;
+
+uniform float layerStep;
+
+void main() {
+    // Blended additively, so each covering fragment adds one step
+    FragColor = vec4(vec3(layerStep), 1.0);
+}
\ No newline at end of file
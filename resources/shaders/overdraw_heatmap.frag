@@ -0,0 +1,26 @@
+#version 330 core
+in vec2 texCoord;
+out vec4 FragColor;
+
+uniform sampler2D screenTexture;
+uniform float layerStep;
+uniform float maxLayers;
+
+// Black where nothing was drawn, then blue, green, yellow and red as layers pile up
+vec3 ramp(float t) {
+    vec3 colors[5] = vec3[5](
+        vec3(0.0, 0.0, 0.0),
+        vec3(0.0, 0.2, 1.0),
+        vec3(0.0, 1.0, 0.2),
+        vec3(1.0, 1.0, 0.0),
+        vec3(1.0, 0.0, 0.0)
+    );
+    float scaled = clamp(t, 0.0, 1.0) * 4.0;
+    int index = min(int(scaled), 3);
+    return mix(colors[index], colors[index + 1], scaled - float(index));
+}
+
+void main() {
+    float layers = texture(screenTexture, texCoord).r / layerStep;
+    FragColor = vec4(ramp(layers / maxLayers), 1.0);
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+#version 330 core
+in vec2 corner;
+in vec4 particleColor;
+out vec4 FragColor;
+
+void main() {
+    float falloff = 1.0 - dot(corner, corner);
+    if (falloff <= 0.0) {
+        discard;
+    }
+    FragColor = vec4(particleColor.rgb, particleColor.a * falloff);
+}
\ No newline at end of file
@@ -0,0 +1,20 @@
+#version 330 core
+layout (location = 0) in vec2 aCorner;
+layout (location = 1) in vec3 iPosition;
+layout (location = 2) in vec4 iColor;
+layout (location = 3) in float iSize;
+
+uniform mat4 view;
+uniform mat4 projection;
+
+out vec2 corner;
+out vec4 particleColor;
+
+void main() {
+    // Billboard: offset the corner in view space so the quad always faces the camera
+    vec4 viewPosition = view * vec4(iPosition, 1.0);
+    viewPosition.xy += aCorner * iSize;
+    gl_Position = projection * viewPosition;
+    corner = aCorner;
+    particleColor = iColor;
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+#version 330 core
+in vec3 pointColor;
+out vec4 FragColor;
+
+void main() {
+    // Distance from the sprite center, 1.0 at the edge of the inscribed disc
+    float radius = length(gl_PointCoord * 2.0 - 1.0);
+    float edge = fwidth(radius);
+    float alpha = 1.0 - smoothstep(1.0 - edge, 1.0 + edge, radius);
+    if (alpha <= 0.0) {
+        discard;
+    }
+    FragColor = vec4(pointColor, alpha);
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+#version 330 core
+layout (location = 0) in vec3 aPos;
+layout (location = 1) in vec3 aColor;
+
+uniform mat4 model;
+uniform mat4 view;
+uniform mat4 projection;
+uniform float pointSize;
+uniform float minPointSize;
+uniform float maxPointSize;
+uniform float viewportHeight;
+
+out vec3 pointColor;
+
+void main() {
+    vec4 viewPosition = view * model * vec4(aPos, 1.0);
+    gl_Position = projection * viewPosition;
+    // World-space diameter projected to pixels: shrinks with distance like real geometry
+    float pixels = pointSize * projection[1][1] * viewportHeight * 0.5 / max(-viewPosition.z, 0.0001);
+    gl_PointSize = clamp(pixels, minPointSize, maxPointSize);
+    pointColor = aColor;
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+#version 330 core
+in vec3 worldPosition;
+in vec3 vertexColor;
+out vec4 FragColor;
+
+const int MAX_POINT_LIGHTS = 8;
+
+uniform vec3 pointLightPositions[MAX_POINT_LIGHTS];
+uniform vec3 pointLightColors[MAX_POINT_LIGHTS];
+// Constant, linear and quadratic attenuation factors
+uniform vec3 pointLightAttenuation[MAX_POINT_LIGHTS];
+uniform int pointLightCount;
+uniform float ambient;
+
+void main() {
+    // Position/color meshes carry no normals, so use the face normal from screen-space derivatives
+    vec3 normal = normalize(cross(dFdx(worldPosition), dFdy(worldPosition)));
+    
+    vec3 lighting = vec3(ambient);
+    for (int i = 0; i < pointLightCount; i++) {
+        vec3 toLight = pointLightPositions[i] - worldPosition;
+        float lightDistance = length(toLight);
+        vec3 k = pointLightAttenuation[i];
+        float attenuation = 1.0 / max(k.x + k.y * lightDistance + k.z * lightDistance * lightDistance, 1e-4);
+        float diffuse = max(dot(normal, toLight / lightDistance), 0.0);
+        lighting += pointLightColors[i] * diffuse * attenuation;
+    }
+    FragColor = vec4(vertexColor * lighting, 1.0);
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+#version 330 core
+layout (location = 0) in vec3 aPos;
+layout (location = 1) in vec3 aColor;
+
+uniform mat4 model;
+uniform mat4 view;
+uniform mat4 projection;
+
+out vec3 worldPosition;
+out vec3 vertexColor;
+
+void main() {
+    vec4 world = model * vec4(aPos, 1.0);
+    worldPosition = world.xyz;
+    vertexColor = aColor;
+    gl_Position = projection * view * world;
+}
\ No newline at end of file
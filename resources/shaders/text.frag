@@ -0,0 +1,13 @@
+#version 330 core
+in vec2 texCoord;
+in vec4 textColor;
+out vec4 FragColor;
+
+uniform sampler2D fontAtlas;
+
+void main() {
+    if (texture(fontAtlas, texCoord).a < 0.5) {
+        discard;
+    }
+    FragColor = textColor;
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+#version 330 core
+layout (location = 0) in vec2 aPosition;
+layout (location = 1) in vec2 aTexCoord;
+layout (location = 2) in vec4 aColor;
+
+uniform mat4 projection;
+
+out vec2 texCoord;
+out vec4 textColor;
+
+void main() {
+    gl_Position = projection * vec4(aPosition, 0.0, 1.0);
+    texCoord = aTexCoord;
+    textColor = aColor;
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+//! Antialiasing modes the demo can switch between.
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Antialiasing technique used for the main scene.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AntiAliasing {
+    /// Render straight to the window.
+    #[default]
+    Off,
+    /// Render into a multisampled framebuffer and resolve it.
+    Msaa,
+    /// Render into a texture and smooth edges with an FXAA post-process pass.
+    Fxaa,
+}
+
+impl AntiAliasing {
+    /// Get the next mode in the Off → MSAA → FXAA cycle.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Msaa,
+            Self::Msaa => Self::Fxaa,
+            Self::Fxaa => Self::Off,
+        }
+    }
+
+    /// Get a short human-readable name for logging.
+    /// 
+    /// # Arguments
+    /// * `msaa_samples` - Samples per pixel of the multisampled framebuffer, after driver clamping
+    pub fn label(self, msaa_samples: u32) -> String {
+        match self {
+            Self::Off => "off".to_string(),
+            Self::Msaa => format!("{msaa_samples}x MSAA"),
+            Self::Fxaa => "FXAA".to_string(),
+        }
+    }
+}
\ No newline at end of file
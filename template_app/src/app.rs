@@ -4,24 +4,264 @@
 // ========================= Imports ==========================
 // ============================================================
 
-use template_graphics::{Renderer, Shader, Mesh};
-use nalgebra::{Matrix4, Perspective3};
+use template_core::{TemplateResult, TemplateError, UpdateLoop};
+use template_graphics::{
+    Renderer, RenderBackend, BenchmarkReport, GlContext, Shader, Mesh, Camera, CameraProjection, SceneItem, Transform,
+    ParticleSystem, ParticleSettings, Framebuffer, MultisampleFramebuffer, PostProcessChain,
+    FxaaPass, GrayscalePass, DepthVisualizePass, ColorFormat, PointLight, set_point_lights,
+};
+use nalgebra::{Matrix4, Perspective3, UnitQuaternion, Vector3};
 use std::time::Instant;
+use winit::event_loop::ControlFlow;
 use tracing;
+use crate::antialiasing::AntiAliasing;
+use crate::input::InputMap;
+use crate::recording::{InputPlayback, InputRecorder, RecordedEvent};
+use crate::scene_file::Placement;
+use crate::spinner::Spinner;
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Action names the demo binds keys to.
+const TOGGLE_NORMALS: &str = "toggle_normals";
+const TOGGLE_GIZMOS: &str = "toggle_gizmos";
+const TOGGLE_BOUNDS: &str = "toggle_bounds";
+const TOGGLE_PARTICLES: &str = "toggle_particles";
+const TOGGLE_GRAYSCALE: &str = "toggle_grayscale";
+const TOGGLE_DEPTH_VIEW: &str = "toggle_depth_view";
+const TOGGLE_OVERDRAW: &str = "toggle_overdraw";
+const CYCLE_ANTIALIASING: &str = "cycle_antialiasing";
+const TOGGLE_INSPECTOR: &str = "toggle_inspector";
+const TOGGLE_ROTATION: &str = "toggle_rotation";
+const TOGGLE_STATS: &str = "toggle_stats";
+const SPEED_UP: &str = "speed_up";
+const SLOW_DOWN: &str = "slow_down";
+const NEXT_MODEL: &str = "next_model";
+const PREVIOUS_MODEL: &str = "previous_model";
+
+/// Length of one simulation step in seconds (60 updates per second).
+const UPDATE_STEP: f32 = 1.0 / 60.0;
+
+/// Samples per pixel requested for the MSAA antialiasing mode; `MAX_SAMPLES` may lower it.
+const MSAA_SAMPLES: u32 = 4;
+/// Index of the FXAA pass in the post-processing chain.
+const FXAA_PASS: usize = 0;
+/// Index of the grayscale pass in the post-processing chain.
+const GRAYSCALE_PASS: usize = 1;
 
 // ============================================================
 // ===================== Structs & Impls ======================
 // ============================================================
 
+/// Particle fountain, offscreen scene targets and post-processing chain of the GL backend.
+pub struct Effects {
+    pub particles: ParticleSystem,
+    pub scene_target: Framebuffer,
+    pub msaa_target: MultisampleFramebuffer,
+    pub post_process: PostProcessChain,
+    pub depth_view: PostProcessChain,
+}
+
+impl Effects {
+    /// Delete the effects' GL objects.
+    fn delete(self, gl: &GlContext) {
+        self.particles.delete(gl);
+        self.scene_target.delete(gl);
+        self.msaa_target.delete(gl);
+        self.post_process.delete(gl);
+        self.depth_view.delete(gl);
+    }
+}
+
 /// Main application state and event handler.
-pub struct TemplateApp {
-    pub renderer: Renderer,
-    pub mesh: Mesh,
-    pub shader: Shader,
+/// 
+/// Generic over the render backend so the event handling can run headless against a
+/// `NullRenderer`; the window event loop drives the default `Renderer`.
+/// 
+/// GPU objects (meshes, shaders, effects) have no `Drop` and must be deleted while their
+/// context is alive, so end the application with `shutdown`, which releases them before
+/// the renderers. Dropping the app instead leaks them.
+#[allow(clippy::struct_excessive_bools)]
+pub struct TemplateApp<B: RenderBackend = Renderer> {
+    /// Models that can be paged through; `current_model` indexes the one on screen.
+    pub meshes: Vec<Mesh>,
+    pub current_model: usize,
+    /// Objects from a scene file (`--scene <file>`) as mesh indices with model matrices and materials.
+    /// 
+    /// When set, these are drawn instead of the spinning current model.
+    pub placements: Vec<Placement>,
+    pub shader: B::Program,
+    /// Whether `shader` takes one CPU-multiplied `mvp` matrix (`--cpu-mvp`).
+    pub cpu_mvp: bool,
+    /// Lights uploaded each frame when `shader` is `point_lit.frag` (`--lit`); empty otherwise.
+    pub point_lights: Vec<PointLight>,
     pub projection: Perspective3<f32>,
-    pub view: Matrix4<f32>,
+    pub camera_projection: CameraProjection,
+    pub camera: Camera,
+    pub transform: Transform,
     pub model: Matrix4<f32>,
-    pub start_time: Instant,
+    pub spinner: Spinner,
+    pub previous_rotation: UnitQuaternion<f32>,
+    pub update_loop: UpdateLoop,
+    pub input: InputMap,
+    /// Records input to a file while set (`--record <file>`).
+    pub recorder: Option<InputRecorder>,
+    /// Replays recorded input while set (`--replay <file>`).
+    pub playback: Option<InputPlayback>,
+    pub last_frame: Instant,
+    /// Latest size from `Resized` events not yet applied, coalesced to one resize per frame.
+    pub pending_resize: Option<winit::dpi::PhysicalSize<u32>>,
+    pub frame_count: u64,
+    pub fps: f32,
+    pub show_stats: bool,
+    pub show_normals: bool,
+    pub show_gizmos: bool,
+    pub show_bounds: bool,
+    pub show_particles: bool,
+    pub post_processing: bool,
+    pub antialiasing: AntiAliasing,
+    /// GL-only effects; `None` when running on a backend without a GL context.
+    pub effects: Option<Effects>,
+    /// Shows the scene's linearized depth buffer instead of its colors.
+    pub show_depth: bool,
+    /// Shows how many fragments cover each pixel instead of the shaded scene.
+    pub show_overdraw: bool,
+    #[cfg(feature = "hot-reload")]
+    pub shader_watcher: Option<template_graphics::ShaderWatcher>,
+    pub inspector: Option<Renderer>,
+    pub renderer: B,
+}
+
+impl TemplateApp {
+    /// Create the application state with the default camera, projection and animation.
+    /// 
+    /// The first mesh is shown initially; an empty list renders only the background.
+    pub fn new(renderer: Renderer, meshes: Vec<Mesh>, shader: Shader) -> TemplateResult<Self> {
+        let (width, height) = renderer.size();
+        let effects = create_effects(&renderer, &CameraProjection::default(), width, height)?;
+        
+        let mut app = Self::with_parts(renderer, meshes, shader, Some(effects));
+        select_model(&mut app, 0);
+        sync_post_process(&mut app);
+        Ok(app)
+    }
+
+    /// Render the current scene for a fixed number of frames and report their timing.
+    /// 
+    /// Runs outside the event loop, so the window never needs to be shown; create the
+    /// renderer with `SwapMode::Off` and `with_visible(false)` for uncapped, headless runs.
+    /// 
+    /// # Arguments
+    /// * `frames` - Number of frames to render
+    pub fn benchmark(&mut self, frames: u32) -> TemplateResult<BenchmarkReport> {
+        template_graphics::run_benchmark(frames, || {
+            render_frame(self)?;
+            Ok(self.renderer.stats())
+        })
+    }
+
+    /// Release every GL object the application owns, then close the windows.
+    /// 
+    /// With the `leak-check` feature, warns about GL objects that are still alive afterwards.
+    pub fn shutdown(self) {
+        let Self { meshes, shader, effects, inspector, renderer, recorder, .. } = self;
+        drop(inspector);
+        
+        if let Some(recorder) = recorder {
+            if let Err(e) = recorder.save() {
+                tracing::error!("Failed to save input recording: {}", e);
+            }
+        }
+        
+        if let Err(e) = renderer.make_current() {
+            tracing::warn!("Could not make context current for teardown: {}", e);
+            return;
+        }
+        for mesh in meshes {
+            mesh.delete(&renderer.gl);
+        }
+        shader.delete(&renderer.gl);
+        if let Some(effects) = effects {
+            effects.delete(&renderer.gl);
+        }
+        drop(renderer);
+        
+        #[cfg(feature = "leak-check")]
+        match template_graphics::live_object_count() {
+            0 => tracing::info!("All GL objects released"),
+            count => tracing::warn!("{} GL objects still alive at exit", count),
+        }
+    }
+}
+
+impl<B: RenderBackend> TemplateApp<B> {
+    /// Assemble the application state around a backend, with the default camera, projection and animation.
+    /// 
+    /// # Arguments
+    /// * `renderer` - Backend to draw with
+    /// * `meshes` - Models to page through, or an empty list for only the background
+    /// * `shader` - Program the models are drawn with
+    /// * `effects` - GL effects, or `None` for backends without a GL context
+    fn with_parts(renderer: B, meshes: Vec<Mesh>, shader: B::Program, effects: Option<Effects>) -> Self {
+        let (width, height) = renderer.size();
+        let camera_projection = CameraProjection::default();
+        Self {
+            projection: camera_projection.perspective(width, height),
+            camera_projection,
+            camera: Camera::default(),
+            transform: Transform::new(),
+            model: Matrix4::identity(),
+            spinner: Spinner::default(),
+            previous_rotation: UnitQuaternion::identity(),
+            update_loop: UpdateLoop::new(UPDATE_STEP),
+            input: default_input_map(),
+            recorder: None,
+            playback: None,
+            last_frame: Instant::now(),
+            pending_resize: None,
+            frame_count: 0,
+            fps: 0.0,
+            show_stats: true,
+            show_normals: false,
+            show_gizmos: false,
+            show_bounds: false,
+            show_particles: false,
+            post_processing: false,
+            antialiasing: AntiAliasing::default(),
+            effects,
+            show_depth: false,
+            show_overdraw: false,
+            inspector: None,
+            #[cfg(feature = "hot-reload")]
+            shader_watcher: template_graphics::ShaderWatcher::new()
+                .map_err(|e| tracing::warn!("Shader hot-reload disabled: {}", e))
+                .ok(),
+            renderer,
+            meshes,
+            current_model: 0,
+            placements: Vec::new(),
+            cpu_mvp: false,
+            point_lights: Vec::new(),
+            shader,
+        }
+    }
+
+    /// Show the objects and camera of a scene file instead of the paged models.
+    /// 
+    /// # Arguments
+    /// * `placements` - Scene objects from `SceneDescription::build`
+    /// * `camera` - Scene camera
+    /// * `camera_projection` - Scene projection parameters
+    pub fn set_scene(&mut self, placements: Vec<Placement>, camera: Camera, camera_projection: CameraProjection) {
+        let (width, height) = self.renderer.size();
+        self.placements = placements;
+        self.camera = camera;
+        self.camera_projection = camera_projection;
+        self.projection = camera_projection.perspective(width, height);
+        self.renderer.invalidate();
+    }
 }
 
 impl winit::application::ApplicationHandler for TemplateApp {
@@ -30,30 +270,72 @@ impl winit::application::ApplicationHandler for TemplateApp {
     fn window_event(
         &mut self,
         event_loop: &winit::event_loop::ActiveEventLoop,
-        _window_id: winit::window::WindowId,
+        window_id: winit::window::WindowId,
         event: winit::event::WindowEvent,
     ) {
+        if self.inspector.as_ref().is_some_and(|inspector| inspector.id() == window_id) {
+            handle_inspector_event(self, &event);
+            return;
+        }
+        
+        if window_id != self.renderer.id() {
+            return;
+        }
+        
         match event {
             winit::event::WindowEvent::CloseRequested => {
                 event_loop.exit();
             }
-            winit::event::WindowEvent::Resized(physical_size) => {
-                handle_resize(&self.renderer, &mut self.projection, physical_size);
-            }
             winit::event::WindowEvent::RedrawRequested => {
-                let elapsed = self.start_time.elapsed().as_secs_f32();
-                let rotation_x = Matrix4::from_axis_angle(&nalgebra::Vector3::x_axis(), elapsed * 0.5);
-                let rotation_y = Matrix4::from_axis_angle(&nalgebra::Vector3::y_axis(), elapsed * 0.7);
-                self.model = rotation_y * rotation_x;
+                self.frame_count += 1;
+                let _frame_span = tracing::info_span!("frame", number = self.frame_count).entered();
+                
+                update_frame(self);
                 
-                render_frame(&self.renderer, &self.mesh, &mut self.shader, &self.projection, &self.view, &self.model);
+                if let Err(e) = self.renderer.make_current() {
+                    tracing::error!("Render error: {}", e);
+                    return;
+                }
+                
+                if let Some(physical_size) = apply_pending_resize(self) {
+                    if let Err(e) = resize_effects(self, physical_size.width, physical_size.height) {
+                        tracing::error!("Failed to resize post-processing targets: {}", e);
+                    }
+                }
+                
+                match render_frame(self) {
+                    Ok(()) => {}
+                    Err(TemplateError::ContextLost(reason)) => {
+                        tracing::warn!("OpenGL context lost ({}), recreating", reason);
+                        if let Err(e) = recover_context(self) {
+                            tracing::error!("Failed to recover from context loss: {}", e);
+                            event_loop.exit();
+                        }
+                    }
+                    Err(e) => tracing::error!("Render error: {}", e),
+                }
             }
-            _ => {}
+            event => handle_input_event(self, event),
         }
     }
     
-    fn about_to_wait(&mut self, _event_loop: &winit::event_loop::ActiveEventLoop) {
-        self.renderer.window.handle().request_redraw();
+    fn about_to_wait(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        // An idle renderer stops redrawing, so wake up on our own for the next replayed event
+        let control_flow = replay_input(self).map_or(ControlFlow::Wait, ControlFlow::WaitUntil);
+        event_loop.set_control_flow(control_flow);
+        handle_actions(self);
+        if self.input.just_pressed(TOGGLE_INSPECTOR) {
+            toggle_inspector(self, event_loop);
+        }
+        self.input.end_frame();
+        
+        #[cfg(feature = "hot-reload")]
+        reload_changed_shaders(self);
+        
+        self.renderer.request_redraw();
+        if let Some(inspector) = &self.inspector {
+            inspector.request_redraw();
+        }
     }
 }
 
@@ -61,38 +343,610 @@ impl winit::application::ApplicationHandler for TemplateApp {
 // ==================== Helper Functions ======================
 // ============================================================
 
+/// Apply a window event that changes input or the viewport, recording it if a recording is running.
+fn handle_input_event<B: RenderBackend>(app: &mut TemplateApp<B>, event: winit::event::WindowEvent) {
+    if matches!(
+        event,
+        winit::event::WindowEvent::Resized(_)
+            | winit::event::WindowEvent::KeyboardInput { .. }
+            | winit::event::WindowEvent::MouseWheel { .. }
+    ) {
+        app.renderer.invalidate();
+    }
+    
+    match event {
+        winit::event::WindowEvent::Resized(physical_size) => {
+            // Dragging a window edge fires many of these per frame; only the last one matters
+            app.pending_resize = Some(physical_size);
+            record(app, RecordedEvent::Resize(physical_size.width, physical_size.height));
+        }
+        winit::event::WindowEvent::KeyboardInput { event, .. } => {
+            app.input.process_key(&event);
+            if let Some(action) = app.input.action_for_key(&event) {
+                record(app, match event.state {
+                    winit::event::ElementState::Pressed => RecordedEvent::Press(action),
+                    winit::event::ElementState::Released => RecordedEvent::Release(action),
+                });
+            }
+        }
+        winit::event::WindowEvent::MouseWheel { delta, .. } => {
+            let steps = scroll_steps(delta);
+            handle_zoom(app, steps);
+            record(app, RecordedEvent::Zoom(steps));
+        }
+        _ => {}
+    }
+}
+
+/// Advance the FPS readout and the simulation to the current time before a frame is drawn.
+fn update_frame<B: RenderBackend>(app: &mut TemplateApp<B>) {
+    let now = Instant::now();
+    let delta_time = now.duration_since(app.last_frame).as_secs_f32();
+    app.last_frame = now;
+    
+    if delta_time > 0.0 {
+        // Smooth over roughly the last 20 frames so the readout stays legible
+        app.fps += (1.0 / delta_time - app.fps) * 0.05;
+    }
+    
+    let previous_model = app.model;
+    let steps = app.update_loop.accumulate(delta_time);
+    for _ in 0..steps.count {
+        app.previous_rotation = app.spinner.rotation();
+        fixed_update(app, app.update_loop.step());
+    }
+    // Render between the last two simulated states so motion stays smooth at any frame rate
+    app.transform.rotation = app.previous_rotation.slerp(&app.spinner.rotation(), steps.alpha);
+    app.model = app.transform.matrix();
+    
+    // Keep drawing while something moves; otherwise idle until the next input
+    if app.model != previous_model || app.show_particles {
+        app.renderer.invalidate();
+    }
+}
+
+/// Advance the simulation by one fixed step.
+fn fixed_update<B: RenderBackend>(app: &mut TemplateApp<B>, step: f32) {
+    // Holding +/- changes the speed by 50% per second
+    if app.input.is_active(SPEED_UP) {
+        app.spinner.scale_speed(1.5_f32.powf(step));
+    }
+    if app.input.is_active(SLOW_DOWN) {
+        app.spinner.scale_speed(1.5_f32.powf(-step));
+    }
+    app.spinner.update(step);
+    if let Some(effects) = app.effects.as_mut().filter(|_| app.show_particles) {
+        effects.particles.update(step);
+    }
+}
+
+/// Apply the size of the last `Resized` event since the previous frame, if any.
+/// 
+/// # Returns
+/// The applied size, so the caller can resize its offscreen targets to match
+fn apply_pending_resize<B: RenderBackend>(app: &mut TemplateApp<B>) -> Option<winit::dpi::PhysicalSize<u32>> {
+    let physical_size = app.pending_resize.take()?;
+    // A minimized window reports 0x0; keep the old projection and targets until it is restored
+    if physical_size.width == 0 || physical_size.height == 0 {
+        return None;
+    }
+    
+    handle_resize(&app.renderer, &app.camera_projection, &mut app.projection, physical_size);
+    Some(physical_size)
+}
+
 /// Handle window resize events.
 fn handle_resize(
-    renderer: &Renderer,
+    renderer: &impl RenderBackend,
+    camera_projection: &CameraProjection,
     projection: &mut Perspective3<f32>,
     physical_size: winit::dpi::PhysicalSize<u32>,
 ) {
     tracing::debug!("Window resized to {}x{}", physical_size.width, physical_size.height);
     
     renderer.resize(physical_size.width, physical_size.height);
-    let aspect = physical_size.width as f32 / physical_size.height as f32;
-    *projection = Perspective3::new(aspect, 45.0_f32.to_radians(), 0.1, 100.0);
+    *projection = camera_projection.perspective(physical_size.width, physical_size.height);
 }
 
-/// Render a single frame.
-fn render_frame(
+/// Convert mouse wheel movement to lines scrolled (positive away from the user).
+#[allow(clippy::cast_possible_truncation)]
+fn scroll_steps(delta: winit::event::MouseScrollDelta) -> f32 {
+    match delta {
+        winit::event::MouseScrollDelta::LineDelta(_, y) => y,
+        winit::event::MouseScrollDelta::PixelDelta(position) => position.y as f32 / 50.0,
+    }
+}
+
+/// Zoom by narrowing or widening the field of view with the mouse wheel.
+fn handle_zoom<B: RenderBackend>(app: &mut TemplateApp<B>, steps: f32) {
+    app.camera_projection.set_fov(app.camera_projection.fov_deg - steps * 2.0);
+    let (width, height) = app.renderer.size();
+    app.projection = app.camera_projection.perspective(width, height);
+}
+
+/// Append an event to the input recording, if one is running.
+fn record<B: RenderBackend>(app: &mut TemplateApp<B>, event: RecordedEvent) {
+    if let Some(recorder) = &mut app.recorder {
+        recorder.record(event);
+    }
+}
+
+/// Feed recorded events that are due into the app as if they had just happened.
+/// 
+/// # Returns
+/// When the next recorded event falls due, or `None` if nothing is left to replay
+fn replay_input<B: RenderBackend>(app: &mut TemplateApp<B>) -> Option<Instant> {
+    let playback = app.playback.as_mut()?;
+    let mut due = Vec::new();
+    while let Some(event) = playback.next_due() {
+        due.push(event);
+    }
+    let next_event_time = playback.next_event_time();
+    if playback.is_finished() {
+        tracing::info!("Input replay finished");
+        app.playback = None;
+    }
+    
+    for event in due {
+        match event {
+            RecordedEvent::Press(action) => app.input.press(action),
+            RecordedEvent::Release(action) => app.input.release(action),
+            RecordedEvent::Zoom(steps) => handle_zoom(app, steps),
+            RecordedEvent::Resize(width, height) => {
+                // Only the projection and targets follow; the OS window keeps its size
+                app.pending_resize = Some(winit::dpi::PhysicalSize::new(width, height));
+            }
+        }
+        app.renderer.invalidate();
+    }
+    next_event_time
+}
+
+/// Create the demo's default key bindings.
+fn default_input_map() -> InputMap {
+    use winit::keyboard::KeyCode;
+    
+    let mut input = InputMap::new();
+    input.bind(KeyCode::KeyN, TOGGLE_NORMALS);
+    input.bind(KeyCode::KeyG, TOGGLE_GIZMOS);
+    input.bind(KeyCode::KeyB, TOGGLE_BOUNDS);
+    input.bind(KeyCode::KeyP, TOGGLE_PARTICLES);
+    input.bind(KeyCode::KeyF, TOGGLE_GRAYSCALE);
+    input.bind(KeyCode::KeyZ, TOGGLE_DEPTH_VIEW);
+    input.bind(KeyCode::KeyO, TOGGLE_OVERDRAW);
+    input.bind(KeyCode::KeyA, CYCLE_ANTIALIASING);
+    input.bind(KeyCode::KeyI, TOGGLE_INSPECTOR);
+    input.bind(KeyCode::Space, TOGGLE_ROTATION);
+    input.bind(KeyCode::KeyT, TOGGLE_STATS);
+    input.bind(KeyCode::Equal, SPEED_UP);
+    input.bind(KeyCode::Minus, SLOW_DOWN);
+    input.bind(KeyCode::ArrowRight, NEXT_MODEL);
+    input.bind(KeyCode::ArrowLeft, PREVIOUS_MODEL);
+    input
+}
+
+/// Apply the actions pressed since the last event loop iteration.
+/// 
+/// Opening the inspector needs the event loop, so the window handler checks that action itself.
+fn handle_actions<B: RenderBackend>(app: &mut TemplateApp<B>) {
+    if app.input.just_pressed(TOGGLE_NORMALS) {
+        app.show_normals = !app.show_normals;
+        tracing::info!("Normal visualization {}", if app.show_normals { "enabled" } else { "disabled" });
+    }
+    if app.input.just_pressed(TOGGLE_GIZMOS) {
+        app.show_gizmos = !app.show_gizmos;
+    }
+    if app.input.just_pressed(TOGGLE_BOUNDS) {
+        app.show_bounds = !app.show_bounds;
+    }
+    if app.input.just_pressed(TOGGLE_PARTICLES) {
+        app.show_particles = !app.show_particles;
+    }
+    if app.input.just_pressed(TOGGLE_GRAYSCALE) {
+        app.post_processing = !app.post_processing;
+        sync_post_process(app);
+        tracing::info!("Grayscale post-processing {}", if app.post_processing { "enabled" } else { "disabled" });
+    }
+    if app.input.just_pressed(TOGGLE_DEPTH_VIEW) {
+        app.show_depth = !app.show_depth;
+        tracing::info!("Depth view {}", if app.show_depth { "enabled" } else { "disabled" });
+    }
+    if app.input.just_pressed(TOGGLE_OVERDRAW) {
+        app.show_overdraw = !app.show_overdraw;
+        tracing::info!("Overdraw view {}", if app.show_overdraw { "enabled" } else { "disabled" });
+    }
+    if app.input.just_pressed(CYCLE_ANTIALIASING) {
+        app.antialiasing = app.antialiasing.next();
+        sync_post_process(app);
+        let msaa_samples = app.effects.as_ref().map_or(MSAA_SAMPLES, |effects| effects.msaa_target.samples());
+        tracing::info!("Antialiasing: {}", app.antialiasing.label(msaa_samples));
+    }
+    // A loaded scene draws all of its meshes at once, so there is nothing to page through
+    if !app.meshes.is_empty() && app.placements.is_empty() {
+        let count = app.meshes.len();
+        if app.input.just_pressed(NEXT_MODEL) {
+            select_model(app, (app.current_model + 1) % count);
+        }
+        if app.input.just_pressed(PREVIOUS_MODEL) {
+            select_model(app, (app.current_model + count - 1) % count);
+        }
+    }
+    if app.input.just_pressed(TOGGLE_STATS) {
+        app.show_stats = !app.show_stats;
+    }
+    if app.input.just_pressed(TOGGLE_ROTATION) {
+        app.spinner.toggle();
+        tracing::info!("Rotation {}", if app.spinner.enabled { "resumed" } else { "paused" });
+    }
+}
+
+/// Show the model at `index` and frame the camera around it.
+fn select_model<B: RenderBackend>(app: &mut TemplateApp<B>, index: usize) {
+    let Some(mesh) = app.meshes.get(index) else {
+        return;
+    };
+    
+    let bounds = mesh.bounds();
+    app.current_model = index;
+    app.camera.frame_aabb(bounds.min, bounds.max, app.camera_projection.fov_deg);
+    // Spin around the mesh's own center even if it is not centered on the origin
+    app.transform = Transform::new().with_pivot(bounds.center());
+    app.model = app.transform.matrix();
+    app.renderer.invalidate();
+    tracing::info!("Showing model {} of {}", index + 1, app.meshes.len());
+}
+
+/// Open the inspector window, or close it if it is already open.
+fn toggle_inspector(app: &mut TemplateApp, event_loop: &winit::event_loop::ActiveEventLoop) {
+    if app.inspector.take().is_some() {
+        tracing::info!("Inspector window closed");
+    } else {
+        match Renderer::with_api(320, 240, "Inspector", app.renderer.api(), event_loop) {
+            Ok(inspector) => app.inspector = Some(inspector),
+            Err(e) => tracing::error!("Failed to open inspector window: {}", e),
+        }
+    }
+    
+    if let Err(e) = app.renderer.make_current() {
+        tracing::error!("Failed to restore main context: {}", e);
+    }
+}
+
+/// Handle events targeting the inspector window.
+fn handle_inspector_event(app: &mut TemplateApp, event: &winit::event::WindowEvent) {
+    let Some(inspector) = app.inspector.as_ref() else {
+        return;
+    };
+    
+    match event {
+        winit::event::WindowEvent::CloseRequested => {
+            app.inspector = None;
+            if let Err(e) = app.renderer.make_current() {
+                tracing::error!("Failed to restore main context: {}", e);
+            }
+        }
+        winit::event::WindowEvent::Resized(physical_size) => {
+            if let Err(e) = inspector.make_current() {
+                tracing::error!("Inspector error: {}", e);
+                return;
+            }
+            inspector.resize(physical_size.width, physical_size.height);
+        }
+        winit::event::WindowEvent::RedrawRequested => {
+            if let Err(e) = inspector.make_current() {
+                tracing::error!("Inspector error: {}", e);
+                return;
+            }
+            inspector.clear();
+            if let Err(e) = inspector.present() {
+                tracing::error!("Inspector render error: {}", e);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Recompile shaders whose source files changed on disk, keeping the old program on errors.
+#[cfg(feature = "hot-reload")]
+fn reload_changed_shaders(app: &mut TemplateApp) {
+    let Some(watcher) = &app.shader_watcher else {
+        return;
+    };
+    
+    for file in watcher.changed_files() {
+        if !app.shader.uses_file(&file) {
+            continue;
+        }
+        
+        if let Err(e) = app.renderer.make_current() {
+            tracing::error!("Shader reload error: {}", e);
+            return;
+        }
+        
+        match app.shader.reload(&app.renderer.gl) {
+            Ok(()) => tracing::info!("Reloaded shader after change to {}", file),
+            Err(e) => tracing::error!("Failed to reload shader after change to {}: {}", file, e),
+        }
+    }
+}
+
+/// Recreate the main context and every GL object the application owns.
+fn recover_context(app: &mut TemplateApp) -> TemplateResult<()> {
+    app.renderer.recreate_context()?;
+    
+    // Old meshes belong to the lost context, so they are rebuilt from their CPU-side copies
+    let gl = &app.renderer.gl;
+    app.meshes = app.meshes.iter().map(|mesh| mesh.recreate(gl)).collect::<TemplateResult<_>>()?;
+    app.shader.recreate(&app.renderer.gl)?;
+    
+    let (width, height) = app.renderer.size();
+    app.effects = Some(create_effects(&app.renderer, &app.camera_projection, width, height)?);
+    sync_post_process(app);
+    Ok(())
+}
+
+/// Create the particle fountain, the offscreen scene targets and the post-processing chain.
+fn create_effects(
     renderer: &Renderer,
-    mesh: &Mesh,
-    shader: &mut Shader,
-    projection: &Perspective3<f32>,
-    view: &Matrix4<f32>,
-    model: &Matrix4<f32>,
-) {
+    camera_projection: &CameraProjection,
+    width: u32,
+    height: u32,
+) -> TemplateResult<Effects> {
+    let particle_settings = ParticleSettings {
+        origin: Vector3::new(0.0, 0.6, 0.0),
+        ..ParticleSettings::default()
+    };
+    let particles = ParticleSystem::new(&renderer.gl, renderer.api(), 2000, particle_settings)?;
+    
+    // Depth lives in a texture so the depth view can sample it
+    let scene_target = Framebuffer::with_depth_texture(&renderer.gl, width, height, ColorFormat::Rgba8)?;
+    let msaa_target = MultisampleFramebuffer::new(&renderer.gl, width, height, MSAA_SAMPLES)?;
+    let mut post_process = PostProcessChain::new(&renderer.gl, width, height)?;
+    post_process.add_pass(FxaaPass::new(&renderer.gl, renderer.api())?);
+    post_process.add_pass(GrayscalePass::new(&renderer.gl, renderer.api())?);
+    
+    let mut depth_view = PostProcessChain::new(&renderer.gl, width, height)?;
+    depth_view.add_pass(DepthVisualizePass::new(&renderer.gl, renderer.api(), camera_projection.near, camera_projection.far)?);
+    
+    Ok(Effects { particles, scene_target, msaa_target, post_process, depth_view })
+}
+
+/// Enable the post-processing passes matching the antialiasing mode and grayscale toggle.
+fn sync_post_process<B: RenderBackend>(app: &mut TemplateApp<B>) {
+    if let Some(effects) = &mut app.effects {
+        effects.post_process.set_enabled(FXAA_PASS, app.antialiasing == AntiAliasing::Fxaa);
+        effects.post_process.set_enabled(GRAYSCALE_PASS, app.post_processing);
+    }
+}
+
+/// Resize the offscreen scene targets and post-processing chain to the window size.
+fn resize_effects(app: &mut TemplateApp, width: u32, height: u32) -> TemplateResult<()> {
+    if width == 0 || height == 0 {
+        return Ok(());
+    }
+    
+    let Some(effects) = &mut app.effects else {
+        return Ok(());
+    };
+    
+    let gl = &app.renderer.gl;
+    let scene_target = std::mem::replace(&mut effects.scene_target, Framebuffer::with_depth_texture(gl, width, height, ColorFormat::Rgba8)?);
+    scene_target.delete(gl);
+    let msaa_target = std::mem::replace(&mut effects.msaa_target, MultisampleFramebuffer::new(gl, width, height, MSAA_SAMPLES)?);
+    msaa_target.delete(gl);
+    effects.post_process.resize(gl, width, height)?;
+    effects.depth_view.resize(gl, width, height)
+}
+
+/// Render a single frame.
+#[allow(clippy::cast_precision_loss)]
+fn render_frame(app: &mut TemplateApp) -> TemplateResult<()> {
+    let renderer = &app.renderer;
+    let effects = app.effects.as_mut()
+        .ok_or_else(|| TemplateError::OpenGL("window renderer has no post-processing effects".to_string()))?;
+    // The depth view needs the single-sampled scene target's depth texture, so it bypasses MSAA and effects
+    let msaa = !app.show_depth && !app.show_overdraw && app.antialiasing == AntiAliasing::Msaa;
+    let post_process = !app.show_depth && !app.show_overdraw && effects.post_process.is_active();
+    
+    if msaa {
+        effects.msaa_target.bind(&renderer.gl);
+    } else if post_process || (app.show_depth && !app.show_overdraw) {
+        effects.scene_target.bind(&renderer.gl);
+    }
+    
     renderer.clear();
     
-    shader.bind(&renderer.gl);
-    shader.set_matrix4(&renderer.gl, "projection", projection.as_matrix());
-    shader.set_matrix4(&renderer.gl, "view", view);
-    shader.set_matrix4(&renderer.gl, "model", model);
+    if app.show_gizmos {
+        if let Err(e) = renderer.draw_grid(&app.camera, &app.projection, 5.0, 0.5) {
+            tracing::error!("Grid error: {}", e);
+        }
+        if let Err(e) = renderer.draw_axes(&app.camera, &app.projection, 1.0) {
+            tracing::error!("Axes error: {}", e);
+        }
+    }
+    
+    let mesh = app.meshes.get(app.current_model);
+    if let Some(mesh) = mesh {
+        if app.show_normals {
+            renderer.debug_draw_normals(mesh, &app.model, 0.2);
+        }
+        if app.show_bounds {
+            let bounds = mesh.bounds().transformed(&app.model);
+            renderer.debug_lines().add_aabb(bounds.min, bounds.max, [0.0, 1.0, 1.0]);
+        }
+    }
+    
+    let mut items: Vec<SceneItem> = if app.placements.is_empty() {
+        mesh.into_iter().map(|mesh| SceneItem::new(mesh, app.model)).collect()
+    } else {
+        app.placements.iter()
+            .filter_map(|placement| {
+                let item = SceneItem::new(app.meshes.get(placement.mesh)?, placement.model);
+                Some(match &placement.material {
+                    Some(material) => item.with_material(material),
+                    None => item,
+                })
+            })
+            .collect()
+    };
+    if app.cpu_mvp {
+        items = items.into_iter().map(SceneItem::with_cpu_mvp).collect();
+    }
+    if app.show_overdraw {
+        // The heatmap draws straight to the window, so any offscreen target bound above is skipped
+        Framebuffer::unbind(&renderer.gl);
+        if let Err(e) = renderer.render_overdraw(&items, &app.camera, &app.projection) {
+            tracing::error!("Overdraw error: {}", e);
+        }
+    } else {
+        if !app.point_lights.is_empty() {
+            app.shader.bind(&renderer.gl);
+            set_point_lights(&renderer.gl, &mut app.shader, &app.point_lights);
+            app.shader.set_float(&renderer.gl, "ambient", 0.15);
+        }
+        renderer.draw(&mut app.shader, &app.camera, &app.projection, &items);
+    }
+    
+    if app.show_particles {
+        effects.particles.draw(&renderer.gl, &app.camera, &app.projection);
+    }
+    
+    if msaa {
+        // Resolve straight to the window unless a post-process pass still needs the scene texture
+        effects.msaa_target.resolve(&renderer.gl, post_process.then_some(&effects.scene_target));
+    }
+    
+    if post_process {
+        Framebuffer::unbind(&renderer.gl);
+        effects.post_process.run(&renderer.gl, effects.scene_target.color_texture(), None);
+    }
+    
+    if app.show_depth && !app.show_overdraw {
+        Framebuffer::unbind(&renderer.gl);
+        if let Some(depth) = effects.scene_target.depth_texture() {
+            effects.depth_view.run(&renderer.gl, depth, None);
+        }
+    }
     
-    mesh.draw(&renderer.gl);
+    if app.show_stats {
+        Framebuffer::unbind(&renderer.gl);
+        let position = app.camera.position;
+        let stats = format!(
+            "{:.0} FPS\nGPU: {:.2} ms\nAA: {}\nCamera: {:.2}, {:.2}, {:.2}",
+            app.fps, renderer.stats().gpu_time_ns as f64 / 1e6, app.antialiasing.label(effects.msaa_target.samples()), position.x, position.y, position.z,
+        );
+        if let Err(e) = renderer.draw_text(&stats, 8.0, 8.0, 2.0, [1.0, 1.0, 1.0, 1.0]) {
+            tracing::error!("Text error: {}", e);
+        }
+    }
     
-    if let Err(e) = renderer.present() {
-        tracing::error!("Render error: {}", e);
+    // Leave no program bound between frames so other GL code starts from a clean state
+    Shader::unbind(&renderer.gl);
+    renderer.present()
+}
+
+// ============================================================
+// =========================== Tests ==========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use template_graphics::{NullRenderer, RenderCall};
+    use winit::dpi::PhysicalSize;
+    use winit::event::WindowEvent;
+
+    fn headless_app() -> TemplateApp<NullRenderer> {
+        TemplateApp::with_parts(NullRenderer::new(800, 600), Vec::new(), (), None)
+    }
+
+    #[test]
+    fn actions_toggle_once_per_press() {
+        let mut app = headless_app();
+        app.input.press(TOGGLE_NORMALS);
+        handle_actions(&mut app);
+        assert!(app.show_normals);
+        
+        // Still held on the next iteration, which must not toggle again
+        app.input.end_frame();
+        handle_actions(&mut app);
+        assert!(app.show_normals);
+        
+        app.input.release(TOGGLE_NORMALS);
+        app.input.end_frame();
+        app.input.press(TOGGLE_NORMALS);
+        handle_actions(&mut app);
+        assert!(!app.show_normals);
+    }
+
+    #[test]
+    fn resizes_are_coalesced_into_one_per_frame() {
+        let mut app = headless_app();
+        handle_input_event(&mut app, WindowEvent::Resized(PhysicalSize::new(1024, 768)));
+        handle_input_event(&mut app, WindowEvent::Resized(PhysicalSize::new(1280, 720)));
+        assert_eq!(app.renderer.calls(), vec![RenderCall::Invalidate, RenderCall::Invalidate]);
+        
+        app.renderer.clear_calls();
+        assert_eq!(apply_pending_resize(&mut app), Some(PhysicalSize::new(1280, 720)));
+        assert_eq!(apply_pending_resize(&mut app), None);
+        assert_eq!(app.renderer.calls(), vec![RenderCall::Resize(1280, 720)]);
+        assert!((app.projection.aspect() - 1280.0 / 720.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn minimized_windows_keep_their_projection() {
+        let mut app = headless_app();
+        let aspect = app.projection.aspect();
+        handle_input_event(&mut app, WindowEvent::Resized(PhysicalSize::new(0, 0)));
+        
+        assert_eq!(apply_pending_resize(&mut app), None);
+        assert_eq!(app.renderer.size(), (800, 600));
+        assert!((app.projection.aspect() - aspect).abs() < 1e-6);
+    }
+
+    #[test]
+    fn scrolling_zooms_the_projection() {
+        let mut app = headless_app();
+        let fov_deg = app.camera_projection.fov_deg;
+        handle_zoom(&mut app, 1.0);
+        
+        assert!(app.camera_projection.fov_deg < fov_deg);
+        assert!((app.projection.fovy() - app.camera_projection.fov_deg.to_radians()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn spinning_frames_invalidate() {
+        let mut app = headless_app();
+        app.last_frame = Instant::now().checked_sub(Duration::from_millis(100)).unwrap();
+        update_frame(&mut app);
+        
+        assert_ne!(app.model, Matrix4::identity());
+        assert_eq!(app.renderer.calls(), vec![RenderCall::Invalidate]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn replay_wakes_an_idle_scene_for_pending_events() {
+        let mut app = headless_app();
+        app.playback = Some(InputPlayback::new([
+            (0.0, RecordedEvent::Press(TOGGLE_NORMALS)),
+            (0.05, RecordedEvent::Release(TOGGLE_NORMALS)),
+        ]));
+        
+        let wake = replay_input(&mut app).expect("release is still pending");
+        assert!(app.input.just_pressed(TOGGLE_NORMALS));
+        assert_eq!(app.renderer.calls(), vec![RenderCall::Invalidate]);
+        
+        // Nothing invalidates the scene until the release is due, so the loop must wake itself
+        app.input.end_frame();
+        app.renderer.clear_calls();
+        assert_eq!(replay_input(&mut app), Some(wake));
+        assert!(app.renderer.calls().is_empty());
+        
+        std::thread::sleep(wake.saturating_duration_since(Instant::now()));
+        assert_eq!(replay_input(&mut app), None);
+        assert!(app.playback.is_none());
+        assert!(!app.input.is_active(TOGGLE_NORMALS));
+        assert_eq!(app.renderer.calls(), vec![RenderCall::Invalidate]);
+    }
+}
@@ -0,0 +1,136 @@
+//! Mapping from physical keys to named actions.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use std::collections::{HashMap, HashSet};
+use winit::event::{ElementState, KeyEvent};
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Binds keys to actions and tracks which actions are held or were just pressed.
+/// 
+/// Feed it every keyboard event with `process_key`, query actions during the frame, then
+/// call `end_frame` so `just_pressed` only reports each press once.
+#[derive(Default)]
+pub struct InputMap {
+    bindings: HashMap<KeyCode, &'static str>,
+    held: HashSet<&'static str>,
+    pressed: HashSet<&'static str>,
+}
+
+impl InputMap {
+    /// Create an empty input map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind a key to an action, replacing any previous binding of that key.
+    /// 
+    /// Several keys may trigger the same action.
+    pub fn bind(&mut self, key: KeyCode, action: &'static str) {
+        self.bindings.insert(key, action);
+    }
+
+    /// Update action state from a keyboard event.
+    pub fn process_key(&mut self, event: &KeyEvent) {
+        let Some(action) = self.action_for_key(event) else {
+            return;
+        };
+        
+        match event.state {
+            ElementState::Pressed => self.press(action),
+            ElementState::Released => self.release(action),
+        }
+    }
+
+    /// Get the action a keyboard event's key is bound to.
+    pub fn action_for_key(&self, event: &KeyEvent) -> Option<&'static str> {
+        let PhysicalKey::Code(code) = event.physical_key else {
+            return None;
+        };
+        self.bindings.get(&code).copied()
+    }
+
+    /// Look up a bound action by name, e.g. when reading a recording.
+    pub fn action_named(&self, name: &str) -> Option<&'static str> {
+        self.bindings.values().copied().find(|action| *action == name)
+    }
+
+    /// Mark an action as held, as if a key bound to it went down.
+    pub fn press(&mut self, action: &'static str) {
+        // Key repeat keeps the action held but is not a new press
+        if self.held.insert(action) {
+            self.pressed.insert(action);
+        }
+    }
+
+    /// Mark an action as released.
+    pub fn release(&mut self, action: &str) {
+        self.held.remove(action);
+    }
+
+    /// Check whether a key bound to the action is currently held down.
+    pub fn is_active(&self, action: &str) -> bool {
+        self.held.contains(action)
+    }
+
+    /// Check whether the action went from released to pressed since the last `end_frame`.
+    pub fn just_pressed(&self, action: &str) -> bool {
+        self.pressed.contains(action)
+    }
+
+    /// Forget this frame's presses.
+    pub fn end_frame(&mut self) {
+        self.pressed.clear();
+    }
+}
+
+// ============================================================
+// ========================== Tests ===========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JUMP: &str = "jump";
+
+    #[test]
+    fn just_pressed_reports_each_press_for_one_frame() {
+        let mut input = InputMap::new();
+        input.press(JUMP);
+        assert!(input.just_pressed(JUMP));
+        assert!(input.is_active(JUMP));
+        input.end_frame();
+        
+        // Still held, and key repeat is not a new press
+        input.press(JUMP);
+        assert!(!input.just_pressed(JUMP));
+        assert!(input.is_active(JUMP));
+        input.end_frame();
+        
+        input.release(JUMP);
+        assert!(!input.just_pressed(JUMP));
+        assert!(!input.is_active(JUMP));
+        input.end_frame();
+        
+        input.press(JUMP);
+        assert!(input.just_pressed(JUMP));
+    }
+
+    #[test]
+    fn press_and_release_within_a_frame_still_counts() {
+        let mut input = InputMap::new();
+        input.press(JUMP);
+        input.release(JUMP);
+        assert!(input.just_pressed(JUMP));
+        assert!(!input.is_active(JUMP));
+        input.end_frame();
+        assert!(!input.just_pressed(JUMP));
+    }
+}
\ No newline at end of file
@@ -4,19 +4,26 @@
 // ========================= Modules ==========================
 // ============================================================
 
+mod antialiasing;
 mod app;
+mod input;
+mod recording;
+mod scene_file;
+mod spinner;
 
 // ============================================================
 // ========================= Imports ==========================
 // ============================================================
 
 use template_core::{TemplateResult, TemplateError};
-use template_graphics::{Renderer, Shader, Mesh};
-use nalgebra::{Matrix4, Vector3, Point3, Perspective3};
+use template_graphics::{Background, GlContextBuilder, Renderer, Shader, Mesh, PointLight, SwapMode};
 use winit::event_loop::EventLoop;
 use tracing;
-use std::time::Instant;
+use std::path::Path;
+use nalgebra::Point3;
 use app::TemplateApp;
+use recording::{InputPlayback, InputRecorder};
+use scene_file::SceneDescription;
 
 // ============================================================
 // ==================== Global Functions ======================
@@ -28,20 +35,88 @@ fn main() -> TemplateResult<()> {
     tracing::info!("Starting Template Engine v0.1.0");
 
     let event_loop = EventLoop::new().unwrap();
-    let renderer = Renderer::new(800, 600, "Template Engine", &event_loop)?;
+    let bench_frames = arg_value("--bench").map(|frames| {
+        frames.parse::<u32>().map_err(|e| TemplateError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid --bench frame count '{frames}': {e}"),
+        )))
+    }).transpose()?;
+    // Benchmarks measure uncapped frame times offscreen, so swaps must not wait for the
+    // display and the window stays hidden
+    let swap_mode = if bench_frames.is_some() { SwapMode::Off } else { SwapMode::default() };
+    let builder = GlContextBuilder::new()
+        .with_swap_mode(swap_mode)
+        .with_visible(bench_frames.is_none());
+    let mut renderer = Renderer::with_builder(800, 600, "Template Engine", builder, &event_loop)?;
+    // Stop redrawing while the cube is paused and nothing else moves. Hot-reload polls for
+    // file changes between frames, so it needs the loop to keep running.
+    renderer.set_idle_when_static(!cfg!(feature = "hot-reload"));
+    renderer.set_background(Background::Gradient {
+        top: [0.25, 0.38, 0.45, 1.0],
+        bottom: [0.08, 0.1, 0.12, 1.0],
+    })?;
     
-    let cube_vertices = create_cube_vertices();
+    let flat_shading = std::env::args().any(|arg| arg == "--flat");
+    let cpu_mvp = std::env::args().any(|arg| arg == "--cpu-mvp");
+    let lit = std::env::args().any(|arg| arg == "--lit");
+    let scene = arg_value("--scene").map(|path| SceneDescription::load(Path::new(&path))).transpose()?;
     
-    let mesh = Mesh::new(&renderer.gl, &cube_vertices);
-    let shader = Shader::new(&renderer.gl, "basic.vert", "basic.frag")?;
+    let (cube_vertices, mut vertex_shader, mut fragment_shader) = if flat_shading {
+        (create_flat_cube_vertices(), "basic_flat.vert".to_string(), "basic_flat.frag".to_string())
+    } else if cpu_mvp {
+        (create_cube_vertices(), "basic_mvp.vert".to_string(), "basic.frag".to_string())
+    } else if lit {
+        (create_cube_vertices(), "point_lit.vert".to_string(), "point_lit.frag".to_string())
+    } else {
+        (create_cube_vertices(), "basic.vert".to_string(), "basic.frag".to_string())
+    };
+    
+    // Left/right arrows page through these, unless a scene file supplies its own
+    let (meshes, placements) = match &scene {
+        Some(scene) => {
+            (vertex_shader, fragment_shader) = scene.shader_files();
+            scene.build(&renderer.gl)?
+        }
+        None => (vec![
+            Mesh::new(&renderer.gl, &cube_vertices),
+            Mesh::new(&renderer.gl, &create_pyramid_vertices()),
+        ], Vec::new()),
+    };
+    // Keep running with a magenta placeholder if the shader is broken; hot-reload can fix it live
+    let (mut shader, _) = Shader::new_or_fallback(&renderer.gl, &vertex_shader, &fragment_shader, Some(renderer.api().shader_preamble()))?;
+    if let Some(scene) = &scene {
+        scene.check_materials(&renderer.gl, &mut shader)?;
+    }
     
-    let projection = Perspective3::new(800.0 / 600.0, 45.0_f32.to_radians(), 0.1, 100.0);
-    let view = create_view_matrix();
-    let model = Matrix4::identity();
-    let start_time = Instant::now();
+    let mut app = TemplateApp::new(renderer, meshes, shader)?;
+    app.cpu_mvp = cpu_mvp && !flat_shading && scene.is_none();
+    if lit && !flat_shading && !cpu_mvp && scene.is_none() {
+        app.point_lights = vec![
+            PointLight::new(Point3::new(2.0, 2.0, 2.0), [1.0, 0.9, 0.8]),
+            PointLight::new(Point3::new(-2.5, 1.0, -1.0), [0.3, 0.4, 1.0]).with_attenuation(1.0, 0.35, 0.44),
+        ];
+    }
+    if let Some(scene) = &scene {
+        let (camera, camera_projection) = scene.camera();
+        app.set_scene(placements, camera, camera_projection);
+    }
+    if let Some(frames) = bench_frames {
+        let report = app.benchmark(frames);
+        app.shutdown();
+        // The report is the benchmark's result rather than a log event, so it goes to
+        // stdout unformatted for scripts to capture
+        println!("{}", report?);
+        return Ok(());
+    }
+    if let Some(path) = arg_value("--record") {
+        app.recorder = Some(InputRecorder::new(path));
+    }
+    if let Some(path) = arg_value("--replay") {
+        app.playback = Some(InputPlayback::load(Path::new(&path), &app.input)?);
+    }
     
     tracing::info!("Entering main event loop");
-    run_event_loop(event_loop, renderer, mesh, shader, projection, view, model, start_time)
+    run_event_loop(event_loop, app)
 }
 
 /// Create cube vertices with colors (36 vertices for 12 triangles, 6 floats per vertex).
@@ -92,38 +167,62 @@ fn create_cube_vertices() -> Vec<f32> {
     ]
 }
 
-/// Create the view matrix for the camera.
-fn create_view_matrix() -> Matrix4<f32> {
-    Matrix4::look_at_rh(
-        &Point3::new(0.0, 0.0, 3.0),
-        &Point3::new(0.0, 0.0, 0.0),
-        &Vector3::new(0.0, 1.0, 0.0),
-    )
+/// Create cube vertices with one constant color per face (same positions and winding as `create_cube_vertices`).
+fn create_flat_cube_vertices() -> Vec<f32> {
+    const FACE_COLORS: [[f32; 3]; 6] = [
+        [1.0, 0.0, 0.0], // Front - red
+        [0.0, 1.0, 0.0], // Back - green
+        [0.0, 0.0, 1.0], // Left - blue
+        [1.0, 1.0, 0.0], // Right - yellow
+        [1.0, 0.0, 1.0], // Top - magenta
+        [0.0, 1.0, 1.0], // Bottom - cyan
+    ];
+    
+    let mut vertices = create_cube_vertices();
+    for (face, color) in vertices.chunks_mut(36).zip(FACE_COLORS.iter()) {
+        for vertex in face.chunks_mut(6) {
+            vertex[3..6].copy_from_slice(color);
+        }
+    }
+    vertices
 }
 
-/// Run the main event loop.
-fn run_event_loop(
-    event_loop: EventLoop<()>,
-    renderer: Renderer,
-    mesh: Mesh,
-    shader: Shader,
-    projection: Perspective3<f32>,
-    view: Matrix4<f32>,
-    model: Matrix4<f32>,
-    start_time: Instant,
-) -> TemplateResult<()> {
-    let mut app = TemplateApp {
-        renderer,
-        mesh,
-        shader,
-        projection,
-        view,
-        model,
-        start_time,
-    };
+/// Create a square pyramid with colored corners (18 vertices for 6 triangles, 6 floats per vertex).
+fn create_pyramid_vertices() -> Vec<f32> {
+    const APEX: [f32; 6] = [0.0, 0.5, 0.0, 1.0, 1.0, 1.0];
+    const BASE: [[f32; 6]; 4] = [
+        [-0.5, -0.5,  0.5, 1.0, 0.0, 0.0], // Front left - red
+        [ 0.5, -0.5,  0.5, 1.0, 1.0, 0.0], // Front right - yellow
+        [ 0.5, -0.5, -0.5, 0.0, 1.0, 0.0], // Back right - green
+        [-0.5, -0.5, -0.5, 0.0, 0.0, 1.0], // Back left - blue
+    ];
     
+    let mut vertices = Vec::with_capacity(18 * 6);
+    // Sides, counter-clockwise seen from outside
+    for i in 0..4 {
+        for vertex in [BASE[i], BASE[(i + 1) % 4], APEX] {
+            vertices.extend_from_slice(&vertex);
+        }
+    }
+    // Base, counter-clockwise seen from below
+    for index in [0, 3, 2, 2, 1, 0] {
+        vertices.extend_from_slice(&BASE[index]);
+    }
+    vertices
+}
+
+/// Get the value following a command-line flag, as in `--record demo.txt`.
+fn arg_value(flag: &str) -> Option<String> {
+    let mut args = std::env::args();
+    args.by_ref().find(|arg| arg == flag)?;
+    args.next()
+}
+
+/// Run the main event loop.
+fn run_event_loop(event_loop: EventLoop<()>, mut app: TemplateApp) -> TemplateResult<()> {
     event_loop.run_app(&mut app)
         .map_err(|e| TemplateError::WindowCreation(e.to_string()))?;
     
+    app.shutdown();
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,170 @@
+//! Recording and replay of input for reproducible demos and bug reports.
+//! 
+//! Recordings are plain text, one event per line: seconds since the start, then the event.
+//! 
+//! ```text
+//! 0.512 press toggle_normals
+//! 0.640 release toggle_normals
+//! 1.250 zoom -1
+//! 2.000 resize 1024 768
+//! ```
+//! 
+//! Keys are stored as the actions they trigger, so recordings survive key rebinding.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use crate::input::InputMap;
+
+// ============================================================
+// ====================== Types & Enums ======================
+// ============================================================
+
+/// An input event in the form the app reacts to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecordedEvent {
+    /// A key bound to the action went down.
+    Press(&'static str),
+    /// A key bound to the action was released.
+    Release(&'static str),
+    /// Mouse wheel movement in lines (positive zooms in).
+    Zoom(f32),
+    /// The window was resized to this physical size.
+    Resize(u32, u32),
+}
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Collects timestamped input events and writes them to a file.
+pub struct InputRecorder {
+    path: PathBuf,
+    start: Instant,
+    events: Vec<(f32, RecordedEvent)>,
+}
+
+impl InputRecorder {
+    /// Start recording; timestamps are measured from now.
+    /// 
+    /// # Arguments
+    /// * `path` - File `save` writes to
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), start: Instant::now(), events: Vec::new() }
+    }
+
+    /// Append an event at the current time.
+    pub fn record(&mut self, event: RecordedEvent) {
+        self.events.push((self.start.elapsed().as_secs_f32(), event));
+    }
+
+    /// Write the recording to its file, replacing it if it exists.
+    pub fn save(&self) -> TemplateResult<()> {
+        let mut file = std::io::BufWriter::new(std::fs::File::create(&self.path)?);
+        for (time, event) in &self.events {
+            match event {
+                RecordedEvent::Press(action) => writeln!(file, "{time:.3} press {action}")?,
+                RecordedEvent::Release(action) => writeln!(file, "{time:.3} release {action}")?,
+                RecordedEvent::Zoom(steps) => writeln!(file, "{time:.3} zoom {steps}")?,
+                RecordedEvent::Resize(width, height) => writeln!(file, "{time:.3} resize {width} {height}")?,
+            }
+        }
+        file.flush()?;
+        tracing::info!("Saved {} input events to {}", self.events.len(), self.path.display());
+        Ok(())
+    }
+}
+
+/// Feeds recorded events back at the times they were recorded.
+pub struct InputPlayback {
+    start: Instant,
+    events: VecDeque<(f32, RecordedEvent)>,
+}
+
+impl InputPlayback {
+    /// Read a recording; playback time starts now.
+    /// 
+    /// # Arguments
+    /// * `path` - File written by `InputRecorder::save`
+    /// * `input` - Input map whose action names the recording refers to
+    /// 
+    /// # Returns
+    /// `TemplateError::Io` if the file cannot be read, a line is malformed or names an unbound action
+    pub fn load(path: &Path, input: &InputMap) -> TemplateResult<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let events = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(number, line)| {
+                parse_line(line, input).ok_or_else(|| {
+                    TemplateError::Io(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("{}:{}: invalid input event '{}'", path.display(), number + 1, line),
+                    ))
+                })
+            })
+            .collect::<TemplateResult<VecDeque<_>>>()?;
+        
+        tracing::info!("Replaying {} input events from {}", events.len(), path.display());
+        Ok(Self::new(events))
+    }
+
+    /// Replay already parsed events; playback time starts now.
+    /// 
+    /// # Arguments
+    /// * `events` - `(seconds, event)` pairs in time order
+    pub fn new(events: impl IntoIterator<Item = (f32, RecordedEvent)>) -> Self {
+        Self { start: Instant::now(), events: events.into_iter().collect() }
+    }
+
+    /// Remove and return the next event whose time has come.
+    pub fn next_due(&mut self) -> Option<RecordedEvent> {
+        let elapsed = self.start.elapsed().as_secs_f32();
+        if self.events.front().is_some_and(|(time, _)| *time <= elapsed) {
+            self.events.pop_front().map(|(_, event)| event)
+        } else {
+            None
+        }
+    }
+
+    /// Get the instant the next pending event falls due.
+    /// 
+    /// # Returns
+    /// `None` once every event has been replayed, or if the next time is out of range
+    #[must_use]
+    pub fn next_event_time(&self) -> Option<Instant> {
+        let (time, _) = self.events.front()?;
+        let offset = Duration::try_from_secs_f32(time.max(0.0)).ok()?;
+        self.start.checked_add(offset)
+    }
+
+    /// Check whether every event has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+// ============================================================
+// ==================== Helper Functions ======================
+// ============================================================
+
+/// Parse one `<seconds> <event> [arguments]` line.
+fn parse_line(line: &str, input: &InputMap) -> Option<(f32, RecordedEvent)> {
+    let mut parts = line.split_whitespace();
+    let time = parts.next()?.parse().ok()?;
+    let event = match parts.next()? {
+        "press" => RecordedEvent::Press(input.action_named(parts.next()?)?),
+        "release" => RecordedEvent::Release(input.action_named(parts.next()?)?),
+        "zoom" => RecordedEvent::Zoom(parts.next()?.parse().ok()?),
+        "resize" => RecordedEvent::Resize(parts.next()?.parse().ok()?, parts.next()?.parse().ok()?),
+        _ => return None,
+    };
+    parts.next().is_none().then_some((time, event))
+}
\ No newline at end of file
@@ -0,0 +1,619 @@
+//! Scene descriptions loaded from RON files.
+//! 
+//! A scene file lists objects built from the demo's primitive meshes or from Wavefront OBJ
+//! and glTF models, their transforms and material uniforms, the shader they are drawn with and the camera:
+//! 
+//! ```ron
+//! (
+//!     shader: "point_lit",
+//!     camera: (position: (0.0, 1.5, 4.0), target: (0.0, 0.0, 0.0), fov_deg: 50.0),
+//!     objects: [
+//!         (mesh: Cube, position: (-0.8, 0.0, 0.0), material: {"ambient": Float(0.6)}),
+//!         (mesh: Model("models/wedge.obj"), position: (0.8, 0.0, 0.0), rotation_deg: (0.0, 45.0, 0.0)),
+//!         (mesh: Model("models/wedge.obj", (center: true, normalize_scale: true)), position: (0.0, 0.5, -1.0)),
+//!         (mesh: Gltf("models/statue.glb", (normalize_scale: true)), position: (0.0, 0.0, 1.0)),
+//!     ],
+//! )
+//! ```
+//! 
+//! Model paths are relative to the resource root; the optional second value of `Model` and
+//! `Gltf` centers the model and scales it to a unit box as it is loaded. Material entries name
+//! uniforms of the scene's shader and are applied before the object is drawn.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use template_graphics::{Camera, CameraProjection, GlContext, InvalidTriangles, Material, Mesh, MeshData, MeshLoadOptions, Shader, Transform, UniformValue, VertexColors};
+use gltf::mesh::util::ReadColors;
+use nalgebra::{Matrix4, Point3, UnitQuaternion, Vector3};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// ============================================================
+// ====================== Types & Enums =======================
+// ============================================================
+
+/// Mesh a scene object is made of.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum MeshSource {
+    /// Cube with colored corners.
+    Cube,
+    /// Cube with one solid color per face.
+    FlatCube,
+    /// Square pyramid with colored corners.
+    Pyramid,
+    /// Wavefront OBJ file, relative to the resource root, and how to reposition it.
+    Model(String, #[serde(default)] LoadOptions),
+    /// glTF 2.0 file (`.gltf` or `.glb`), relative to the resource root, and how to reposition it.
+    Gltf(String, #[serde(default)] LoadOptions),
+}
+
+impl MeshSource {
+    /// Upload the mesh.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// 
+    /// # Returns
+    /// The mesh, or `TemplateError::SceneParse` if a model file is malformed
+    fn create(&self, gl: &GlContext) -> TemplateResult<Mesh> {
+        match self {
+            Self::Cube => Ok(Mesh::new(gl, &crate::create_cube_vertices())),
+            Self::FlatCube => Ok(Mesh::new(gl, &crate::create_flat_cube_vertices())),
+            Self::Pyramid => Ok(Mesh::new(gl, &crate::create_pyramid_vertices())),
+            Self::Model(path, options) => {
+                let path = template_graphics::resource_path(path);
+                let text = std::fs::read_to_string(&path)?;
+                // Objects are placed in the repositioned model's coordinates, so the undo transform is not needed
+                let (data, _undo) = parse_obj(&text, (*options).into())
+                    .map_err(|reason| TemplateError::SceneParse(format!("{}: {}", path.display(), reason)))?;
+                Mesh::from_data(gl, &data)
+            }
+            Self::Gltf(path, options) => {
+                let path = template_graphics::resource_path(path);
+                let bytes = std::fs::read(&path)?;
+                let (data, _undo) = parse_gltf(&bytes, path.parent(), (*options).into())
+                    .map_err(|reason| TemplateError::SceneParse(format!("{}: {}", path.display(), reason)))?;
+                Mesh::from_data(gl, &data)
+            }
+        }
+    }
+}
+
+/// How a model is repositioned and checked as it is loaded; mirrors `MeshLoadOptions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(default)]
+pub struct LoadOptions {
+    pub center: bool,
+    pub normalize_scale: bool,
+    /// Remove invalid triangles instead of only reporting them.
+    pub drop_invalid_triangles: bool,
+}
+
+impl From<LoadOptions> for MeshLoadOptions {
+    fn from(options: LoadOptions) -> Self {
+        let invalid_triangles = if options.drop_invalid_triangles { InvalidTriangles::Drop } else { InvalidTriangles::Report };
+        MeshLoadOptions { center: options.center, normalize_scale: options.normalize_scale, invalid_triangles }
+    }
+}
+
+/// Uniform value of a material entry in a scene file.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum MaterialValue {
+    Float(f32),
+    Int(i32),
+    Vec3([f32; 3]),
+    Vec4([f32; 4]),
+}
+
+impl From<MaterialValue> for UniformValue {
+    fn from(value: MaterialValue) -> Self {
+        match value {
+            MaterialValue::Float(value) => UniformValue::Float(value),
+            MaterialValue::Int(value) => UniformValue::Int(value),
+            MaterialValue::Vec3(vector) => UniformValue::Vec3(vector),
+            MaterialValue::Vec4(vector) => UniformValue::Vec4(vector),
+        }
+    }
+}
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Camera placement and projection of a scene file; every field is optional.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct CameraDescription {
+    pub position: [f32; 3],
+    pub target: [f32; 3],
+    pub fov_deg: f32,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl Default for CameraDescription {
+    /// The default `Camera` and `CameraProjection`.
+    fn default() -> Self {
+        let camera = Camera::default();
+        let projection = CameraProjection::default();
+        Self {
+            position: camera.position.coords.into(),
+            target: camera.target.coords.into(),
+            fov_deg: projection.fov_deg,
+            near: projection.near,
+            far: projection.far,
+        }
+    }
+}
+
+/// One object placed in a scene file.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ObjectDescription {
+    pub mesh: MeshSource,
+    #[serde(default)]
+    pub position: [f32; 3],
+    /// Rotation as XYZ Euler angles in degrees.
+    #[serde(default)]
+    pub rotation_deg: [f32; 3],
+    #[serde(default = "unit_scale")]
+    pub scale: [f32; 3],
+    /// Uniform values set before the object is drawn, by uniform name.
+    #[serde(default)]
+    pub material: BTreeMap<String, MaterialValue>,
+}
+
+impl ObjectDescription {
+    /// Build the object's model matrix.
+    pub fn model_matrix(&self) -> Matrix4<f32> {
+        let [roll, pitch, yaw] = self.rotation_deg.map(f32::to_radians);
+        let transform = Transform {
+            translation: Vector3::from(self.position),
+            rotation: UnitQuaternion::from_euler_angles(roll, pitch, yaw),
+            scale: Vector3::from(self.scale),
+            ..Transform::new()
+        };
+        transform.matrix()
+    }
+
+    /// Build the object's material, or `None` if it has no material entries.
+    pub fn material(&self) -> Option<Material<'static>> {
+        if self.material.is_empty() {
+            return None;
+        }
+        
+        let mut material = Material::new();
+        for (name, value) in &self.material {
+            material.set(name, (*value).into());
+        }
+        Some(material)
+    }
+}
+
+/// Scene object ready to draw: a mesh index, its model matrix and material.
+pub struct Placement {
+    /// Index into the meshes returned alongside it by `SceneDescription::build`.
+    pub mesh: usize,
+    pub model: Matrix4<f32>,
+    pub material: Option<Material<'static>>,
+}
+
+/// Contents of a scene file.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SceneDescription {
+    /// Shader file name without extension; `basic` loads `basic.vert` and `basic.frag`.
+    #[serde(default = "default_shader")]
+    pub shader: String,
+    #[serde(default)]
+    pub camera: CameraDescription,
+    pub objects: Vec<ObjectDescription>,
+}
+
+impl SceneDescription {
+    /// Read and parse a scene file.
+    /// 
+    /// # Arguments
+    /// * `path` - Path to the RON file
+    /// 
+    /// # Returns
+    /// The scene, or `TemplateError::SceneParse` describing where the file is malformed
+    pub fn load(path: &Path) -> TemplateResult<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let scene = Self::parse(&text).map_err(|e| match e {
+            TemplateError::SceneParse(reason) => TemplateError::SceneParse(format!("{}: {}", path.display(), reason)),
+            other => other,
+        })?;
+        tracing::info!("Loaded scene {} with {} objects", path.display(), scene.objects.len());
+        Ok(scene)
+    }
+
+    /// Parse a scene from RON text.
+    pub fn parse(text: &str) -> TemplateResult<Self> {
+        ron::from_str(text).map_err(|e| TemplateError::SceneParse(e.to_string()))
+    }
+
+    /// Get the vertex and fragment shader files to draw the scene with.
+    pub fn shader_files(&self) -> (String, String) {
+        (format!("{}.vert", self.shader), format!("{}.frag", self.shader))
+    }
+
+    /// Get the scene's camera and projection parameters.
+    pub fn camera(&self) -> (Camera, CameraProjection) {
+        let description = &self.camera;
+        let camera = Camera::new(Point3::from(description.position), Point3::from(description.target));
+        let projection = CameraProjection::new(description.fov_deg, description.near, description.far);
+        (camera, projection)
+    }
+
+    /// Check that every material entry names a uniform of the scene's shader.
+    /// 
+    /// The magenta fallback shader is skipped, since it only declares the transform uniforms.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `shader` - Shader loaded from `shader_files`
+    /// 
+    /// # Returns
+    /// `TemplateError::SceneParse` naming the first unknown uniform
+    pub fn check_materials(&self, gl: &GlContext, shader: &mut Shader) -> TemplateResult<()> {
+        if shader.is_fallback() {
+            return Ok(());
+        }
+        
+        for name in self.objects.iter().flat_map(|object| object.material.keys()) {
+            if !shader.has_uniform(gl, name) {
+                return Err(TemplateError::SceneParse(format!("shader '{}' has no uniform '{}'", self.shader, name)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Create the meshes the scene needs and place its objects.
+    /// 
+    /// Each mesh source is uploaded once and shared by every object using it.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// 
+    /// # Returns
+    /// The meshes and one placement per object, or the error of the first model that
+    /// fails to load
+    pub fn build(&self, gl: &GlContext) -> TemplateResult<(Vec<Mesh>, Vec<Placement>)> {
+        let mut sources: Vec<&MeshSource> = Vec::new();
+        let mut meshes: Vec<Mesh> = Vec::new();
+        let mut placements = Vec::with_capacity(self.objects.len());
+        
+        for object in &self.objects {
+            let index = if let Some(index) = sources.iter().position(|source| **source == object.mesh) {
+                index
+            } else {
+                match object.mesh.create(gl) {
+                    Ok(mesh) => meshes.push(mesh),
+                    Err(e) => {
+                        for mesh in meshes {
+                            mesh.delete(gl);
+                        }
+                        return Err(e);
+                    }
+                }
+                sources.push(&object.mesh);
+                meshes.len() - 1
+            };
+            placements.push(Placement {
+                mesh: index,
+                model: object.model_matrix(),
+                material: object.material(),
+            });
+        }
+        Ok((meshes, placements))
+    }
+}
+
+// ============================================================
+// ===================== Helper Functions =====================
+// ============================================================
+
+/// Default object scale for serde.
+fn unit_scale() -> [f32; 3] {
+    [1.0; 3]
+}
+
+/// Default scene shader for serde.
+fn default_shader() -> String {
+    "basic".to_string()
+}
+
+/// Parse the vertices and faces of a Wavefront OBJ model.
+/// 
+/// `v` lines give a position with an optional RGB color; `f` lines give polygons, which are
+/// triangulated as fans. Texture coordinates, normals, groups and materials are ignored.
+/// 
+/// # Arguments
+/// * `text` - Contents of the OBJ file
+/// * `options` - How to reposition the positions once they are read
+/// 
+/// # Returns
+/// The mesh data and the transform undoing `options`, or a description of the first
+/// malformed line
+fn parse_obj(text: &str, options: MeshLoadOptions) -> Result<(MeshData, Matrix4<f32>), String> {
+    let mut positions = Vec::new();
+    let mut colors = Vec::new();
+    let mut indices = Vec::new();
+    
+    for (number, line) in text.lines().enumerate() {
+        let malformed = |reason: &str| format!("line {}: {}", number + 1, reason);
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("v") => {
+                let values = fields
+                    .map(str::parse::<f32>)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| malformed(&e.to_string()))?;
+                match values[..] {
+                    // The optional fourth value is a homogeneous weight, not a color
+                    [x, y, z] | [x, y, z, _] => {
+                        positions.push([x, y, z]);
+                        colors.push([1.0; 3]);
+                    }
+                    [x, y, z, red, green, blue] => {
+                        positions.push([x, y, z]);
+                        colors.push([red, green, blue]);
+                    }
+                    _ => return Err(malformed("expected 3, 4 or 6 vertex values")),
+                }
+            }
+            Some("f") => {
+                let corners = fields
+                    .map(|corner| obj_index(corner, positions.len()))
+                    .collect::<Option<Vec<u32>>>()
+                    .ok_or_else(|| malformed("invalid face index"))?;
+                if corners.len() < 3 {
+                    return Err(malformed("faces need at least 3 corners"));
+                }
+                for pair in corners[1..].windows(2) {
+                    indices.extend([corners[0], pair[0], pair[1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+    if indices.is_empty() {
+        return Err("model has no faces".to_string());
+    }
+    let undo = options.apply(positions.as_flattened_mut(), 3);
+    let data = MeshData::build(&positions, &colors, Some(&indices), options.invalid_triangles).map_err(|e| e.to_string())?;
+    Ok((data, undo))
+}
+
+/// Read the triangle primitives of every mesh in a glTF 2.0 file into one mesh.
+/// 
+/// Positions, `COLOR_0` and indices are read; node transforms, normals, texture coordinates
+/// and materials are ignored, and primitives that are not triangle lists are skipped.
+/// 
+/// # Arguments
+/// * `bytes` - Contents of a `.gltf` or `.glb` file
+/// * `base` - Directory external buffer URIs are relative to, or `None` to only allow embedded data
+/// * `options` - How to reposition the positions once they are read
+/// 
+/// # Returns
+/// The mesh data and the transform undoing `options`, or a description of the problem
+fn parse_gltf(bytes: &[u8], base: Option<&Path>, options: MeshLoadOptions) -> Result<(MeshData, Matrix4<f32>), String> {
+    let gltf = gltf::Gltf::from_slice(bytes).map_err(|e| e.to_string())?;
+    let buffers = gltf::import_buffers(&gltf.document, base, gltf.blob.clone()).map_err(|e| e.to_string())?;
+    
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut colors = Vec::new();
+    let mut indices = Vec::new();
+    
+    for mesh in gltf.document.meshes() {
+        for primitive in mesh.primitives() {
+            let name = format!("mesh {} primitive {}", mesh.index(), primitive.index());
+            if primitive.mode() != gltf::mesh::Mode::Triangles {
+                tracing::warn!("Skipping {} with mode {:?}", name, primitive.mode());
+                continue;
+            }
+            if let Some(accessor) = primitive.get(&gltf::Semantic::Colors(0)) {
+                // COLOR_0 integers are only meaningful as normalized values
+                if accessor.data_type() != gltf::accessor::DataType::F32 && !accessor.normalized() {
+                    return Err(format!("{name}: integer COLOR_0 must be normalized"));
+                }
+            }
+            
+            let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|data| &data.0[..]));
+            let first = u32::try_from(positions.len()).map_err(|_| "model has too many vertices".to_string())?;
+            let primitive_positions: Vec<[f32; 3]> = reader
+                .read_positions()
+                .ok_or_else(|| format!("{name}: missing POSITION"))?
+                .collect();
+            colors.extend(gltf_colors(reader.read_colors(0), primitive_positions.len()));
+            if let Some(primitive_indices) = reader.read_indices() {
+                indices.extend(primitive_indices.into_u32().map(|index| first.saturating_add(index)));
+            } else {
+                let count = u32::try_from(primitive_positions.len()).map_err(|_| "model has too many vertices".to_string())?;
+                indices.extend((0..count).map(|index| first.saturating_add(index)));
+            }
+            positions.extend(primitive_positions);
+        }
+    }
+    if indices.is_empty() {
+        return Err("model has no triangles".to_string());
+    }
+    let undo = options.apply(positions.as_flattened_mut(), 3);
+    let data = MeshData::build(&positions, &colors, Some(&indices), options.invalid_triangles).map_err(|e| e.to_string())?;
+    Ok((data, undo))
+}
+
+/// Convert a glTF `COLOR_0` attribute to float RGB through `VertexColors`.
+/// 
+/// # Arguments
+/// * `colors` - The primitive's colors, or `None` if it has no `COLOR_0`
+/// * `vertex_count` - Number of vertices in the primitive, for the white default
+fn gltf_colors(colors: Option<ReadColors<'_>>, vertex_count: usize) -> Vec<[f32; 3]> {
+    match colors {
+        None => VertexColors::None.to_rgb(vertex_count),
+        Some(ReadColors::RgbF32(colors)) => VertexColors::RgbF32(&colors.collect::<Vec<_>>()).to_rgb(vertex_count),
+        Some(ReadColors::RgbaF32(colors)) => VertexColors::RgbaF32(&colors.collect::<Vec<_>>()).to_rgb(vertex_count),
+        Some(ReadColors::RgbU8(colors)) => VertexColors::RgbU8(&colors.collect::<Vec<_>>()).to_rgb(vertex_count),
+        Some(ReadColors::RgbaU8(colors)) => VertexColors::RgbaU8(&colors.collect::<Vec<_>>()).to_rgb(vertex_count),
+        Some(ReadColors::RgbU16(colors)) => VertexColors::RgbU16(&colors.collect::<Vec<_>>()).to_rgb(vertex_count),
+        Some(ReadColors::RgbaU16(colors)) => VertexColors::RgbaU16(&colors.collect::<Vec<_>>()).to_rgb(vertex_count),
+    }
+}
+
+/// Resolve the position index of an OBJ face corner such as `3`, `3/1` or `-1//2`.
+/// 
+/// # Arguments
+/// * `corner` - Face corner as written in the file
+/// * `vertex_count` - Number of vertices read so far, for negative (relative) indices
+/// 
+/// # Returns
+/// The zero-based index, or `None` if it is zero or refers before the first vertex
+fn obj_index(corner: &str, vertex_count: usize) -> Option<u32> {
+    let index: i64 = corner.split('/').next()?.parse().ok()?;
+    let resolved = match index {
+        0 => return None,
+        index if index > 0 => index - 1,
+        index => i64::try_from(vertex_count).ok()? + index,
+    };
+    u32::try_from(resolved).ok()
+}
+
+// ============================================================
+// =========================== Tests ==========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn objects_accept_model_paths_and_materials() {
+        let scene = SceneDescription::parse(r#"(
+            objects: [
+                (mesh: Cube),
+                (mesh: Model("models/wedge.obj"), material: {"ambient": Float(0.5), "tint": Vec3((1.0, 0.5, 0.0))}),
+            ],
+        )"#).unwrap();
+        
+        assert_eq!(scene.objects[0].mesh, MeshSource::Cube);
+        assert!(scene.objects[0].material().is_none());
+        assert_eq!(scene.objects[1].mesh, MeshSource::Model("models/wedge.obj".to_string(), LoadOptions::default()));
+        let material = scene.objects[1].material().unwrap();
+        assert_eq!(material.get("ambient"), Some(UniformValue::Float(0.5)));
+        assert_eq!(material.get("tint"), Some(UniformValue::Vec3([1.0, 0.5, 0.0])));
+    }
+
+    #[test]
+    fn obj_faces_are_triangulated_with_relative_indices() {
+        let (data, undo) = parse_obj("\
+            # quad with one colored corner\n\
+            v 0 0 0\n\
+            v 1 0 0 1 0 0\n\
+            v 1 1 0\n\
+            v 0 1 0\n\
+            vn 0 0 1\n\
+            f 1//1 2//1 -2//1 -1//1\n", MeshLoadOptions::default()).unwrap();
+        
+        assert_eq!(undo, Matrix4::identity());
+        assert_eq!(data.vertex_count(), 4);
+        assert_eq!(data.indices(), Some(&[0, 1, 2, 0, 2, 3][..]));
+        assert_eq!(&data.vertices()[6..12], &[1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn malformed_obj_lines_are_reported() {
+        assert_eq!(parse_obj("v 0 0 0\nv 1 0\n", MeshLoadOptions::default()).unwrap_err(), "line 2: expected 3, 4 or 6 vertex values");
+        assert_eq!(parse_obj("v 0 0 0\nf 1 0 1\n", MeshLoadOptions::default()).unwrap_err(), "line 2: invalid face index");
+        assert_eq!(parse_obj("v 0 0 0\n", MeshLoadOptions::default()).unwrap_err(), "model has no faces");
+    }
+
+    #[test]
+    fn model_load_options_reposition_obj_positions() {
+        let scene = SceneDescription::parse(r#"(
+            objects: [(mesh: Model("models/wedge.obj", (center: true, normalize_scale: true)))],
+        )"#).unwrap();
+        let MeshSource::Model(_, options) = scene.objects[0].mesh.clone() else {
+            panic!("expected a model");
+        };
+        assert_eq!(MeshLoadOptions::from(options), MeshLoadOptions::fit_unit_box());
+        
+        let (data, undo) = parse_obj("v 2 2 2\nv 6 2 2\nv 2 4 2\nf 1 2 3\n", options.into()).unwrap();
+        assert_eq!(&data.vertices()[0..3], &[-0.5, -0.25, 0.0]);
+        assert_eq!(&data.vertices()[6..9], &[0.5, -0.25, 0.0]);
+        assert_eq!(undo.transform_point(&Point3::new(-0.5, -0.25, 0.0)), Point3::new(2.0, 2.0, 2.0));
+    }
+
+    #[test]
+    fn degenerate_obj_faces_are_only_dropped_on_request() {
+        let obj = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\nf 1 1 2\n";
+        let (kept, _undo) = parse_obj(obj, MeshLoadOptions::default()).unwrap();
+        assert_eq!(kept.indices(), Some(&[0, 1, 2, 0, 0, 1][..]));
+        
+        let options = LoadOptions { drop_invalid_triangles: true, ..LoadOptions::default() };
+        let (repaired, _undo) = parse_obj(obj, options.into()).unwrap();
+        assert_eq!(repaired.indices(), Some(&[0, 1, 2][..]));
+    }
+
+    /// Pack a JSON document and binary buffer into a GLB container.
+    fn glb(json: &str, bin: &[u8]) -> Vec<u8> {
+        let mut json = json.as_bytes().to_vec();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        let mut bin = bin.to_vec();
+        bin.resize(bin.len().next_multiple_of(4), 0);
+        
+        let length = u32::try_from(12 + 8 + json.len() + 8 + bin.len()).unwrap();
+        let mut bytes = b"glTF".to_vec();
+        bytes.extend(2u32.to_le_bytes());
+        bytes.extend(length.to_le_bytes());
+        bytes.extend(u32::try_from(json.len()).unwrap().to_le_bytes());
+        bytes.extend(b"JSON");
+        bytes.extend(json);
+        bytes.extend(u32::try_from(bin.len()).unwrap().to_le_bytes());
+        bytes.extend(b"BIN\0");
+        bytes.extend(bin);
+        bytes
+    }
+
+    /// One triangle with normalized `u8` RGBA `COLOR_0`, optionally marked as not normalized.
+    fn colored_triangle(normalized: bool) -> Vec<u8> {
+        let mut bin = Vec::new();
+        for value in [0.0f32, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0] {
+            bin.extend(value.to_le_bytes());
+        }
+        bin.extend([255, 0, 0, 255, 0, 51, 0, 128, 0, 0, 255, 0]);
+        let json = format!(r#"{{
+            "asset": {{"version": "2.0"}},
+            "buffers": [{{"byteLength": 48}}],
+            "bufferViews": [
+                {{"buffer": 0, "byteOffset": 0, "byteLength": 36}},
+                {{"buffer": 0, "byteOffset": 36, "byteLength": 12}}
+            ],
+            "accessors": [
+                {{"bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3", "min": [0, 0, 0], "max": [1, 1, 0]}},
+                {{"bufferView": 1, "componentType": 5121, "normalized": {normalized}, "count": 3, "type": "VEC4"}}
+            ],
+            "meshes": [{{"primitives": [{{"attributes": {{"POSITION": 0, "COLOR_0": 1}}}}]}}]
+        }}"#);
+        glb(&json, &bin)
+    }
+
+    #[test]
+    fn gltf_color_0_is_normalized_to_rgb() {
+        let (data, _undo) = parse_gltf(&colored_triangle(true), None, MeshLoadOptions::default()).unwrap();
+        
+        assert_eq!(data.vertex_count(), 3);
+        assert_eq!(data.indices(), Some(&[0, 1, 2][..]));
+        assert_eq!(&data.vertices()[3..6], &[1.0, 0.0, 0.0]);
+        assert_eq!(&data.vertices()[9..12], &[0.0, 0.2, 0.0]);
+        assert_eq!(&data.vertices()[15..18], &[0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn gltf_integer_colors_must_be_normalized() {
+        let error = parse_gltf(&colored_triangle(false), None, MeshLoadOptions::default()).unwrap_err();
+        assert_eq!(error, "mesh 0 primitive 0: integer COLOR_0 must be normalized");
+    }
+}
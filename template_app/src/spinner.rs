@@ -0,0 +1,66 @@
+//! Rotation animation controller for the demo model.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use nalgebra::{UnitQuaternion, Vector3};
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Spins the model around the X, Y and Z axes at independent speeds.
+pub struct Spinner {
+    /// Angular speed around each axis in radians per second.
+    pub axis_speeds: Vector3<f32>,
+    /// Whether the rotation advances on `update`.
+    pub enabled: bool,
+    angles: Vector3<f32>,
+}
+
+impl Spinner {
+    /// Create a new spinner with the given per-axis speeds (radians per second).
+    pub fn new(axis_speeds: Vector3<f32>) -> Self {
+        Self {
+            axis_speeds,
+            enabled: true,
+            angles: Vector3::zeros(),
+        }
+    }
+
+    /// Advance the rotation by the elapsed time.
+    /// 
+    /// # Arguments
+    /// * `delta_time` - Seconds since the previous update
+    pub fn update(&mut self, delta_time: f32) {
+        if self.enabled {
+            self.angles += self.axis_speeds * delta_time;
+        }
+    }
+
+    /// Pause or resume the rotation.
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    /// Multiply all axis speeds by a factor.
+    pub fn scale_speed(&mut self, factor: f32) {
+        self.axis_speeds *= factor;
+    }
+
+    /// Get the current rotation (applied X, then Y, then Z).
+    pub fn rotation(&self) -> UnitQuaternion<f32> {
+        let rotation_x = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), self.angles.x);
+        let rotation_y = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), self.angles.y);
+        let rotation_z = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), self.angles.z);
+        rotation_z * rotation_y * rotation_x
+    }
+}
+
+impl Default for Spinner {
+    /// The original demo spin: 0.5 rad/s around X and 0.7 rad/s around Y.
+    fn default() -> Self {
+        Self::new(Vector3::new(0.5, 0.7, 0.0))
+    }
+}
\ No newline at end of file
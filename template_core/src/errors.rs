@@ -21,4 +21,14 @@ pub enum TemplateError {
     ShaderCompilation(String),
     #[error("Window creation error: {0}")]
     WindowCreation(String),
+    #[error("Texture error: {0}")]
+    Texture(String),
+    #[error("OpenGL context lost: {0}")]
+    ContextLost(String),
+    #[error("Frame mismatch: {0}")]
+    FrameMismatch(String),
+    #[error("Invalid mesh: {0}")]
+    InvalidMesh(String),
+    #[error("Scene parse error: {0}")]
+    SceneParse(String),
 }
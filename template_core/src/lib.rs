@@ -5,12 +5,14 @@
 // ============================================================
 
 pub mod errors;
+pub mod update_loop;
 
 // ============================================================
 // ========================= Imports ==========================
 // ============================================================
 
 pub use errors::*;
+pub use update_loop::*;
 
 // ============================================================
 // ========================== Types ===========================
@@ -0,0 +1,149 @@
+//! Fixed-timestep accumulator decoupling simulation rate from render rate.
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Result of feeding one frame's elapsed time into an `UpdateLoop`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedSteps {
+    /// Number of fixed steps to simulate this frame (may be 0).
+    pub count: u32,
+    /// How far the leftover time reaches into the next step, in `0.0..1.0`.
+    /// 
+    /// Render by interpolating between the state before and after the last step.
+    pub alpha: f32,
+}
+
+/// Turns variable frame times into a whole number of fixed-length update steps.
+/// 
+/// Simulating in fixed steps makes motion independent of the frame rate and reproducible
+/// from run to run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UpdateLoop {
+    step: f32,
+    max_steps: u32,
+    time_scale: f32,
+    accumulator: f32,
+}
+
+impl UpdateLoop {
+    /// Default cap on steps per frame, so a long stall does not trigger a burst of catch-up work.
+    pub const DEFAULT_MAX_STEPS: u32 = 8;
+
+    /// Create an update loop running at a fixed rate.
+    /// 
+    /// # Arguments
+    /// * `step` - Length of one update step in seconds (e.g. `1.0 / 60.0`)
+    /// 
+    /// # Panics
+    /// If `step` is not a positive, finite number of seconds
+    #[must_use]
+    pub fn new(step: f32) -> Self {
+        assert!(step > 0.0 && step.is_finite(), "update step must be positive and finite, got {step}");
+        Self {
+            step,
+            max_steps: Self::DEFAULT_MAX_STEPS,
+            time_scale: 1.0,
+            accumulator: 0.0,
+        }
+    }
+
+    /// Set the maximum number of steps a single frame may produce.
+    /// 
+    /// Time beyond the cap is dropped, so the simulation slows down instead of falling
+    /// further and further behind.
+    #[must_use]
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// Set how fast simulated time passes relative to real time (1.0 is real time, 0.0 pauses).
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_scale = time_scale.max(0.0);
+    }
+
+    /// Get the time scale.
+    #[must_use]
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// Get the length of one update step in seconds.
+    #[must_use]
+    pub fn step(&self) -> f32 {
+        self.step
+    }
+
+    /// Add a frame's elapsed time and take out the whole steps it completes.
+    /// 
+    /// # Arguments
+    /// * `delta_time` - Real seconds since the previous frame
+    /// 
+    /// # Returns
+    /// The number of steps to simulate and the interpolation alpha for rendering
+    pub fn accumulate(&mut self, delta_time: f32) -> FixedSteps {
+        self.accumulator += delta_time.max(0.0) * self.time_scale;
+        
+        let mut count = 0;
+        while self.accumulator >= self.step && count < self.max_steps {
+            self.accumulator -= self.step;
+            count += 1;
+        }
+        
+        if count == self.max_steps && self.accumulator >= self.step {
+            tracing::debug!("Update loop fell behind, dropping {:.3}s", self.accumulator - self.step);
+            self.accumulator %= self.step;
+        }
+        
+        FixedSteps {
+            count,
+            // Rounding can leave the remainder a hair short of a full step
+            alpha: (self.accumulator / self.step).min(1.0 - f32::EPSILON),
+        }
+    }
+}
+
+// ============================================================
+// ========================== Tests ===========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_produce_whole_steps_and_carry_the_rest() {
+        let mut update_loop = UpdateLoop::new(0.25);
+        
+        assert_eq!(update_loop.accumulate(0.125), FixedSteps { count: 0, alpha: 0.5 });
+        assert_eq!(update_loop.accumulate(0.5), FixedSteps { count: 2, alpha: 0.5 });
+        assert_eq!(update_loop.accumulate(0.125), FixedSteps { count: 1, alpha: 0.0 });
+    }
+
+    #[test]
+    fn long_frames_are_capped_at_max_steps() {
+        let mut update_loop = UpdateLoop::new(0.25).with_max_steps(3);
+        
+        // 10 steps worth of time, but only 3 run and the backlog is dropped
+        let steps = update_loop.accumulate(2.5 + 0.125);
+        assert_eq!(steps, FixedSteps { count: 3, alpha: 0.5 });
+        assert_eq!(update_loop.accumulate(0.0), FixedSteps { count: 0, alpha: 0.5 });
+    }
+
+    #[test]
+    fn alpha_stays_below_one() {
+        let mut update_loop = UpdateLoop::new(1.0 / 60.0);
+        for frame in 0..1000u16 {
+            let alpha = update_loop.accumulate(f32::from(frame % 7) * 0.004 + 0.001).alpha;
+            assert!((0.0..1.0).contains(&alpha), "alpha {alpha} at frame {frame}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "update step must be positive")]
+    fn zero_step_is_rejected() {
+        let _ = UpdateLoop::new(0.0);
+    }
+}
@@ -0,0 +1,196 @@
+//! Backend abstraction so application logic can run without a GPU.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::TemplateResult;
+use crate::{Camera, Renderer, SceneItem, Shader};
+use nalgebra::Perspective3;
+use std::cell::{Cell, RefCell};
+
+// ============================================================
+// ====================== Types & Enums ======================
+// ============================================================
+
+/// A call recorded by `NullRenderer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderCall {
+    Clear,
+    Present,
+    Resize(u32, u32),
+    RequestRedraw,
+    Invalidate,
+    /// A `draw` call with this many scene items.
+    Draw(usize),
+}
+
+// ============================================================
+// ========================== Traits ==========================
+// ============================================================
+
+/// Frame-level operations the application drives, independent of the graphics API.
+pub trait RenderBackend {
+    /// Shader program `draw` renders with; `()` for backends that compile nothing.
+    type Program;
+
+    /// Clear the color and depth buffers.
+    fn clear(&self);
+    /// Present the rendered frame.
+    /// 
+    /// # Errors
+    /// `TemplateError::ContextLost` if the context was lost, or another error if presenting fails
+    fn present(&self) -> TemplateResult<()>;
+    /// Update the viewport for a new window size.
+    fn resize(&self, width: u32, height: u32);
+    /// Get the current viewport size in pixels.
+    fn size(&self) -> (u32, u32);
+    /// Schedule another frame.
+    fn request_redraw(&self);
+    /// Mark the frame as out of date so the next `request_redraw` schedules a redraw.
+    fn invalidate(&self);
+    /// Draw scene items as seen from a camera.
+    /// 
+    /// # Arguments
+    /// * `program` - Program with `projection`, `view` and `model` matrix uniforms
+    /// * `camera` - Camera the scene is viewed from
+    /// * `projection` - Projection matrix
+    /// * `items` - Items to draw
+    fn draw(&self, program: &mut Self::Program, camera: &Camera, projection: &Perspective3<f32>, items: &[SceneItem]);
+}
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+impl RenderBackend for Renderer {
+    type Program = Shader;
+
+    fn clear(&self) {
+        Renderer::clear(self);
+    }
+
+    fn present(&self) -> TemplateResult<()> {
+        Renderer::present(self)
+    }
+
+    fn resize(&self, width: u32, height: u32) {
+        Renderer::resize(self, width, height);
+    }
+
+    fn size(&self) -> (u32, u32) {
+        Renderer::size(self)
+    }
+
+    fn request_redraw(&self) {
+        Renderer::request_redraw(self);
+    }
+
+    fn invalidate(&self) {
+        Renderer::invalidate(self);
+    }
+
+    fn draw(&self, program: &mut Shader, camera: &Camera, projection: &Perspective3<f32>, items: &[SceneItem]) {
+        self.render_scene(program, camera, projection, items);
+    }
+}
+
+/// Backend that performs no rendering and records every call, for headless tests.
+pub struct NullRenderer {
+    size: Cell<(u32, u32)>,
+    calls: RefCell<Vec<RenderCall>>,
+}
+
+impl NullRenderer {
+    /// Create a null renderer reporting the given viewport size.
+    #[must_use]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            size: Cell::new((width, height)),
+            calls: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Get a copy of the calls recorded so far.
+    pub fn calls(&self) -> Vec<RenderCall> {
+        self.calls.borrow().clone()
+    }
+
+    /// Forget all recorded calls.
+    pub fn clear_calls(&self) {
+        self.calls.borrow_mut().clear();
+    }
+}
+
+impl RenderBackend for NullRenderer {
+    type Program = ();
+
+    fn clear(&self) {
+        self.calls.borrow_mut().push(RenderCall::Clear);
+    }
+
+    fn present(&self) -> TemplateResult<()> {
+        self.calls.borrow_mut().push(RenderCall::Present);
+        Ok(())
+    }
+
+    fn resize(&self, width: u32, height: u32) {
+        self.size.set((width, height));
+        self.calls.borrow_mut().push(RenderCall::Resize(width, height));
+    }
+
+    fn size(&self) -> (u32, u32) {
+        self.size.get()
+    }
+
+    fn request_redraw(&self) {
+        self.calls.borrow_mut().push(RenderCall::RequestRedraw);
+    }
+
+    fn invalidate(&self) {
+        self.calls.borrow_mut().push(RenderCall::Invalidate);
+    }
+
+    fn draw(&self, _program: &mut (), _camera: &Camera, _projection: &Perspective3<f32>, items: &[SceneItem]) {
+        self.calls.borrow_mut().push(RenderCall::Draw(items.len()));
+    }
+}
+
+// ============================================================
+// =========================== Tests ==========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn null_renderer_logs_calls_in_order() {
+        let renderer = NullRenderer::new(800, 600);
+        renderer.clear();
+        renderer.draw(&mut (), &Camera::default(), &Perspective3::new(1.0, 1.0, 0.1, 100.0), &[]);
+        renderer.present().unwrap();
+        renderer.invalidate();
+        renderer.request_redraw();
+        
+        assert_eq!(renderer.calls(), vec![
+            RenderCall::Clear,
+            RenderCall::Draw(0),
+            RenderCall::Present,
+            RenderCall::Invalidate,
+            RenderCall::RequestRedraw,
+        ]);
+        renderer.clear_calls();
+        assert!(renderer.calls().is_empty());
+    }
+
+    #[test]
+    fn null_renderer_reports_the_resized_size() {
+        let renderer = NullRenderer::new(800, 600);
+        assert_eq!(renderer.size(), (800, 600));
+        
+        renderer.resize(1024, 768);
+        assert_eq!(renderer.size(), (1024, 768));
+        assert_eq!(renderer.calls(), vec![RenderCall::Resize(1024, 768)]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+//! Scene backgrounds drawn when the frame is cleared.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::TemplateResult;
+use crate::{FullscreenQuad, GlApi, GlContext, Shader};
+use glow::HasContext;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// What fills the frame before the scene is drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Background {
+    /// A single RGBA clear color.
+    Solid([f32; 4]),
+    /// A vertical blend from `bottom` at the bottom edge to `top` at the top edge.
+    Gradient { top: [f32; 4], bottom: [f32; 4] },
+}
+
+/// Shader and fullscreen triangle used to draw gradient backgrounds.
+pub struct GradientBackground {
+    shader: Shader,
+    quad: FullscreenQuad,
+}
+
+impl GradientBackground {
+    /// Compile the gradient shader.
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the shader cannot be loaded, or
+    /// `TemplateError::OpenGL` if the fullscreen triangle cannot be created
+    pub fn new(gl: &GlContext, api: GlApi) -> TemplateResult<Self> {
+        let shader = Shader::with_preamble(gl, "fullscreen.vert", "gradient.frag", Some(api.shader_preamble()))?;
+        let quad = FullscreenQuad::new(gl)?;
+        Ok(Self { shader, quad })
+    }
+
+    /// Fill the current viewport with the gradient without touching the depth buffer.
+    /// 
+    /// The depth test and depth write mask are restored to whatever they were before.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `top` - RGBA color at the top edge
+    /// * `bottom` - RGBA color at the bottom edge
+    pub fn draw(&mut self, gl: &GlContext, top: [f32; 4], bottom: [f32; 4]) {
+        let (depth_test, depth_write) = unsafe {
+            (gl.is_enabled(glow::DEPTH_TEST), gl.get_parameter_bool(glow::DEPTH_WRITEMASK))
+        };
+        unsafe {
+            gl.disable(glow::DEPTH_TEST);
+            gl.depth_mask(false);
+        }
+        
+        self.shader.bind(gl);
+        self.shader.set_vec4(gl, "topColor", top);
+        self.shader.set_vec4(gl, "bottomColor", bottom);
+        self.quad.draw(gl);
+        
+        unsafe {
+            gl.depth_mask(depth_write);
+            if depth_test {
+                gl.enable(glow::DEPTH_TEST);
+            }
+        }
+    }
+
+    /// Release the shader and vertex array.
+    pub fn delete(self, gl: &GlContext) {
+        self.shader.delete(gl);
+        self.quad.delete(gl);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,136 @@
+//! Frame time benchmarks reporting CPU and GPU timing percentiles.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::TemplateResult;
+use crate::RenderStats;
+use std::fmt;
+use std::time::Instant;
+use tracing;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Distribution of a set of frame times, in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FrameTimeSummary {
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub max_ms: f64,
+    /// 99th percentile, the time only the slowest 1% of frames exceed.
+    pub p99_ms: f64,
+}
+
+impl FrameTimeSummary {
+    /// Summarize frame time samples.
+    /// 
+    /// # Arguments
+    /// * `samples` - Frame times in milliseconds, in any order
+    /// 
+    /// # Returns
+    /// The summary, or `None` if there are no samples
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let (&min_ms, &max_ms) = (sorted.first()?, sorted.last()?);
+        
+        Some(Self {
+            min_ms,
+            median_ms: percentile(&sorted, 0.5),
+            max_ms,
+            p99_ms: percentile(&sorted, 0.99),
+        })
+    }
+}
+
+impl fmt::Display for FrameTimeSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:.3} ms, median {:.3} ms, max {:.3} ms, p99 {:.3} ms",
+            self.min_ms, self.median_ms, self.max_ms, self.p99_ms,
+        )
+    }
+}
+
+/// Timing results of `run_benchmark`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchmarkReport {
+    /// Number of frames rendered.
+    pub frames: u32,
+    /// Wall-clock time of each frame as seen by the CPU, including the buffer swap.
+    pub cpu: FrameTimeSummary,
+    /// GPU time of each frame, or `None` without timer query support.
+    pub gpu: Option<FrameTimeSummary>,
+}
+
+impl fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Benchmark: {} frames", self.frames)?;
+        write!(f, "  CPU: {}", self.cpu)?;
+        match &self.gpu {
+            Some(gpu) => write!(f, "\n  GPU: {gpu}"),
+            None => write!(f, "\n  GPU: not available"),
+        }
+    }
+}
+
+// ============================================================
+// ===================== Helper Functions =====================
+// ============================================================
+
+/// Get the nearest-rank percentile of sorted, non-empty samples.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    let rank = (fraction * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+// ============================================================
+// ==================== Global Functions ======================
+// ============================================================
+
+/// Render a fixed number of frames as fast as possible and report their timing.
+/// 
+/// `frame` renders and presents one frame and returns the renderer's `stats()`. Create
+/// the renderer with `SwapMode::Off` so swaps do not wait for the display; with vsync
+/// every frame reads as the refresh interval. The loop runs outside the winit event loop,
+/// so it works from a criterion benchmark as well as from the app's `--bench` flag.
+/// 
+/// GPU times lag a frame behind and read 0 until the first timer query completes, so zero
+/// readings are left out of the GPU summary.
+/// 
+/// # Arguments
+/// * `frames` - Number of frames to render
+/// * `frame` - Renders one frame and returns the renderer's stats
+/// 
+/// # Returns
+/// The timing report
+/// 
+/// # Errors
+/// The first error returned by `frame`
+#[allow(clippy::cast_precision_loss)]
+pub fn run_benchmark(frames: u32, mut frame: impl FnMut() -> TemplateResult<RenderStats>) -> TemplateResult<BenchmarkReport> {
+    let frames = frames.max(1);
+    tracing::info!("Benchmarking {} frames", frames);
+    
+    let mut cpu_samples = Vec::with_capacity(frames as usize);
+    let mut gpu_samples = Vec::with_capacity(frames as usize);
+    for _ in 0..frames {
+        let start = Instant::now();
+        let stats = frame()?;
+        cpu_samples.push(start.elapsed().as_secs_f64() * 1e3);
+        if stats.gpu_time_ns > 0 {
+            gpu_samples.push(stats.gpu_time_ns as f64 / 1e6);
+        }
+    }
+    
+    Ok(BenchmarkReport {
+        frames,
+        cpu: FrameTimeSummary::from_samples(&cpu_samples).unwrap_or_default(),
+        gpu: FrameTimeSummary::from_samples(&gpu_samples),
+    })
+}
\ No newline at end of file
@@ -0,0 +1,68 @@
+//! Axis-aligned bounding boxes for meshes and scene items.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use nalgebra::{Matrix4, Point3};
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Point3<f32>,
+    pub max: Point3<f32>,
+}
+
+impl Aabb {
+    /// Create a bounding box from its corners.
+    #[must_use]
+    pub fn new(min: Point3<f32>, max: Point3<f32>) -> Self {
+        Self { min, max }
+    }
+
+    /// Compute the bounding box of a set of points.
+    /// 
+    /// # Returns
+    /// The enclosing box, or `None` if there are no points
+    pub fn from_points(points: impl IntoIterator<Item = Point3<f32>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::new(first, first), |aabb, point| Self {
+            min: aabb.min.inf(&point),
+            max: aabb.max.sup(&point),
+        }))
+    }
+
+    /// Get the center of the box.
+    #[must_use]
+    pub fn center(&self) -> Point3<f32> {
+        nalgebra::center(&self.min, &self.max)
+    }
+
+    /// Get the eight corners of the box.
+    #[must_use]
+    pub fn corners(&self) -> [Point3<f32>; 8] {
+        let (min, max) = (self.min, self.max);
+        [
+            Point3::new(min.x, min.y, min.z),
+            Point3::new(max.x, min.y, min.z),
+            Point3::new(min.x, max.y, min.z),
+            Point3::new(max.x, max.y, min.z),
+            Point3::new(min.x, min.y, max.z),
+            Point3::new(max.x, min.y, max.z),
+            Point3::new(min.x, max.y, max.z),
+            Point3::new(max.x, max.y, max.z),
+        ]
+    }
+
+    /// Get the box enclosing this box after transforming it by a matrix.
+    #[must_use]
+    pub fn transformed(&self, matrix: &Matrix4<f32>) -> Self {
+        let corners = self.corners().map(|corner| matrix.transform_point(&corner));
+        Self::from_points(corners).unwrap_or(*self)
+    }
+}
\ No newline at end of file
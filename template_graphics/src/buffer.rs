@@ -0,0 +1,177 @@
+//! Vertex buffers that several meshes can share.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::{BufferPool, GlContext};
+use glow::HasContext;
+use std::sync::Arc;
+use tracing;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// A GPU array buffer holding interleaved vertex data.
+/// 
+/// Meshes hold it through an `Arc`, so one buffer can back several VAOs (for example the
+/// main pass and a shadow pass with different attribute setups). GL objects cannot be
+/// deleted from `Drop`, so owners call `release` instead; the buffer is deleted when the
+/// last owner releases it.
+#[derive(Debug)]
+pub struct VertexBuffer {
+    handle: glow::Buffer,
+    size: usize,
+    /// Taken from a `BufferPool`, so it was allocated with `DYNAMIC_DRAW`.
+    pooled: bool,
+}
+
+impl VertexBuffer {
+    /// Upload vertex data into a new static buffer.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `data` - Interleaved vertex bytes
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the buffer cannot be created
+    pub fn new(gl: &GlContext, data: &[u8]) -> TemplateResult<Arc<Self>> {
+        unsafe {
+            let handle = gl.create_buffer().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(handle));
+            gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, data, glow::STATIC_DRAW);
+            gl.bind_buffer(glow::ARRAY_BUFFER, None);
+            Ok(Arc::new(Self { handle, size: data.len(), pooled: false }))
+        }
+    }
+
+    /// Upload vertex data into a buffer taken from a pool.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `pool` - Pool to reuse a buffer of the same size from
+    /// * `data` - Interleaved vertex bytes
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if no pooled buffer fits and a new one cannot be created
+    pub fn from_pool(gl: &GlContext, pool: &mut BufferPool, data: &[u8]) -> TemplateResult<Arc<Self>> {
+        let handle = pool.acquire_buffer(gl, data)?;
+        Ok(Arc::new(Self { handle, size: data.len(), pooled: true }))
+    }
+
+    /// Get the underlying OpenGL buffer handle.
+    #[must_use]
+    pub fn handle(&self) -> glow::Buffer {
+        self.handle
+    }
+
+    /// Get the size of the buffer in bytes.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Copy bytes of the buffer back to the CPU.
+    /// 
+    /// Stalls until the GPU has finished writing the buffer, so use it for debugging only.
+    /// OpenGL ES has no `glGetBufferSubData`, so the range is mapped for reading there.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `offset` - First byte to read
+    /// * `len` - Number of bytes to read
+    /// 
+    /// # Returns
+    /// The bytes
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the range is out of bounds or cannot be mapped
+    pub fn read(&self, gl: &GlContext, offset: usize, len: usize) -> TemplateResult<Vec<u8>> {
+        let out_of_range = || TemplateError::OpenGL(format!(
+            "cannot read {} bytes at offset {} of a {}-byte vertex buffer",
+            len, offset, self.size
+        ));
+        if offset.checked_add(len).as_ref().is_none_or(|&end| end > self.size) {
+            return Err(out_of_range());
+        }
+        let gl_offset = i32::try_from(offset).map_err(|_| out_of_range())?;
+        let gl_len = i32::try_from(len).map_err(|_| out_of_range())?;
+        
+        let mut data = vec![0u8; len];
+        unsafe {
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.handle));
+            let result = if gl.version().is_embedded {
+                let mapped = gl.map_buffer_range(glow::ARRAY_BUFFER, gl_offset, gl_len, glow::MAP_READ_BIT);
+                if mapped.is_null() {
+                    Err(TemplateError::OpenGL("failed to map the vertex buffer for reading".to_string()))
+                } else {
+                    std::ptr::copy_nonoverlapping(mapped, data.as_mut_ptr(), len);
+                    gl.unmap_buffer(glow::ARRAY_BUFFER);
+                    Ok(())
+                }
+            } else {
+                gl.get_buffer_sub_data(glow::ARRAY_BUFFER, gl_offset, &mut data);
+                Ok(())
+            };
+            gl.bind_buffer(glow::ARRAY_BUFFER, None);
+            result?;
+        }
+        Ok(data)
+    }
+
+    /// Give up one reference, returning the buffer to a pool if it was the last.
+    /// 
+    /// Only buffers taken with `from_pool` go back to the pool, so every pooled buffer
+    /// carries the `DYNAMIC_DRAW` hint; a static buffer is deleted instead.
+    /// 
+    /// # Arguments
+    /// * `buffer` - Reference to release
+    /// * `gl` - OpenGL context the buffer was created with
+    /// * `pool` - Pool that takes the buffer
+    /// 
+    /// # Returns
+    /// Whether the buffer went back to the pool
+    pub fn recycle(buffer: Arc<Self>, gl: &GlContext, pool: &mut BufferPool) -> bool {
+        if !buffer.pooled {
+            Self::release(buffer, gl);
+            return false;
+        }
+        match Arc::try_unwrap(buffer) {
+            Ok(buffer) => {
+                pool.release_buffer(buffer.handle, buffer.size);
+                true
+            }
+            Err(shared) => {
+                tracing::trace!("Vertex buffer still used by {} other owners", Arc::strong_count(&shared) - 1);
+                false
+            }
+        }
+    }
+
+    /// Give up one reference, deleting the buffer if it was the last.
+    /// 
+    /// # Arguments
+    /// * `buffer` - Reference to release
+    /// * `gl` - OpenGL context the buffer was created with
+    /// 
+    /// # Returns
+    /// Whether the buffer was deleted
+    pub fn release(buffer: Arc<Self>, gl: &GlContext) -> bool {
+        match Arc::try_unwrap(buffer) {
+            Ok(buffer) => {
+                unsafe {
+                    gl.delete_buffer(buffer.handle);
+                }
+                crate::leaks::track_deleted();
+                true
+            }
+            Err(shared) => {
+                tracing::trace!("Vertex buffer still used by {} other owners", Arc::strong_count(&shared) - 1);
+                false
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,217 @@
+//! Camera placement and view matrix generation.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use nalgebra::{Matrix4, Perspective3, Point3, Vector3};
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Largest pitch `set_orientation` allows, in degrees; looking straight up or down would
+/// make the view direction parallel to `up`.
+pub const MAX_PITCH_DEG: f32 = 89.0;
+
+/// Smallest bounding-sphere radius `frame_aabb` fits, the default near plane distance; a
+/// point-sized box would otherwise put the eye on the target.
+const MIN_FRAME_RADIUS: f32 = 0.1;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// A look-at camera described by an eye position, a target and an up vector.
+#[derive(Debug, Clone, Copy)]
+pub struct Camera {
+    pub position: Point3<f32>,
+    pub target: Point3<f32>,
+    pub up: Vector3<f32>,
+}
+
+impl Camera {
+    /// Create a new camera looking from `position` towards `target` with +Y up.
+    #[must_use]
+    pub fn new(position: Point3<f32>, target: Point3<f32>) -> Self {
+        Self {
+            position,
+            target,
+            up: Vector3::y(),
+        }
+    }
+
+    /// Get the right-handed view matrix for the current placement.
+    #[must_use]
+    pub fn view_matrix(&self) -> Matrix4<f32> {
+        Matrix4::look_at_rh(&self.position, &self.target, &self.up)
+    }
+
+    /// Get the normalized direction the camera is looking in.
+    #[must_use]
+    pub fn forward(&self) -> Vector3<f32> {
+        (self.target - self.position).normalize()
+    }
+
+    /// Aim the camera from yaw and pitch angles, keeping its position.
+    /// 
+    /// Yaw 0 and pitch 0 look down -Z; positive yaw turns right (towards +X) and positive
+    /// pitch looks up. The target is placed one unit in front of the camera.
+    /// 
+    /// # Arguments
+    /// * `yaw_deg` - Rotation around +Y in degrees
+    /// * `pitch_deg` - Elevation in degrees, clamped to `±MAX_PITCH_DEG`
+    pub fn set_orientation(&mut self, yaw_deg: f32, pitch_deg: f32) {
+        let yaw = yaw_deg.to_radians();
+        let pitch = pitch_deg.clamp(-MAX_PITCH_DEG, MAX_PITCH_DEG).to_radians();
+        let forward = Vector3::new(yaw.sin() * pitch.cos(), pitch.sin(), -yaw.cos() * pitch.cos());
+        self.target = self.position + forward;
+    }
+
+    /// Get the yaw and pitch the camera is looking with, as used by `set_orientation`.
+    /// 
+    /// # Returns
+    /// `(yaw_deg, pitch_deg)`
+    #[must_use]
+    pub fn orientation(&self) -> (f32, f32) {
+        let forward = self.forward();
+        let yaw = forward.x.atan2(-forward.z);
+        let pitch = forward.y.clamp(-1.0, 1.0).asin();
+        (yaw.to_degrees(), pitch.to_degrees())
+    }
+
+    /// Move the camera back along its view direction until a bounding box fits in view,
+    /// and aim it at the box center.
+    /// 
+    /// Fits the box's bounding sphere into the vertical field of view, so the box also fits
+    /// horizontally for viewports wider than they are tall. Degenerate boxes are framed as a
+    /// sphere of radius 0.1.
+    /// 
+    /// # Arguments
+    /// * `min` - Minimum corner of the box
+    /// * `max` - Maximum corner of the box
+    /// * `fov_deg` - Vertical field of view in degrees, usually `CameraProjection::fov_deg`
+    pub fn frame_aabb(&mut self, min: Point3<f32>, max: Point3<f32>, fov_deg: f32) {
+        let center = nalgebra::center(&min, &max);
+        let radius = ((max - min).norm() * 0.5).max(MIN_FRAME_RADIUS);
+        let distance = radius / (fov_deg.to_radians() * 0.5).sin();
+        
+        // Keep looking the same way; fall back to -Z if the camera sits on its target
+        let forward = (self.target - self.position)
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(|| -Vector3::z());
+        
+        self.target = center;
+        self.position = center - forward * distance;
+    }
+}
+
+impl Default for Camera {
+    /// Camera at (0, 0, 3) looking at the origin.
+    fn default() -> Self {
+        Self::new(Point3::new(0.0, 0.0, 3.0), Point3::origin())
+    }
+}
+
+/// Perspective projection parameters shared by the initial projection and resize handling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraProjection {
+    /// Vertical field of view in degrees.
+    pub fov_deg: f32,
+    /// Distance to the near clipping plane.
+    pub near: f32,
+    /// Distance to the far clipping plane.
+    pub far: f32,
+}
+
+impl CameraProjection {
+    /// Create projection parameters from a vertical FOV in degrees and clip distances.
+    #[must_use]
+    pub fn new(fov_deg: f32, near: f32, far: f32) -> Self {
+        Self { fov_deg, near, far }
+    }
+
+    /// Set the vertical field of view in degrees, clamped to a usable range.
+    pub fn set_fov(&mut self, fov_deg: f32) {
+        self.fov_deg = fov_deg.clamp(1.0, 179.0);
+    }
+
+    /// Set the near and far clipping distances.
+    pub fn set_clip_planes(&mut self, near: f32, far: f32) {
+        self.near = near;
+        self.far = far;
+    }
+
+    /// Build the perspective projection for a viewport of the given size.
+    /// 
+    /// # Arguments
+    /// * `width` - Viewport width in pixels
+    /// * `height` - Viewport height in pixels
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn perspective(&self, width: u32, height: u32) -> Perspective3<f32> {
+        let aspect = width as f32 / height.max(1) as f32;
+        Perspective3::new(aspect, self.fov_deg.to_radians(), self.near, self.far)
+    }
+}
+
+impl Default for CameraProjection {
+    /// 45 degree FOV with clip planes at 0.1 and 100.
+    fn default() -> Self {
+        Self::new(45.0, 0.1, 100.0)
+    }
+}
+
+// ============================================================
+// ========================== Tests ===========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-4;
+
+    fn camera() -> Camera {
+        Camera::new(Point3::new(1.0, 2.0, 3.0), Point3::origin())
+    }
+
+    #[test]
+    fn zero_angles_look_down_negative_z() {
+        let mut camera = camera();
+        camera.set_orientation(0.0, 0.0);
+        assert!((camera.forward() - -Vector3::z()).norm() < EPSILON);
+    }
+
+    #[test]
+    fn pitch_is_clamped_short_of_vertical() {
+        let mut camera = camera();
+        camera.set_orientation(30.0, 120.0);
+        assert!((camera.orientation().1 - MAX_PITCH_DEG).abs() < EPSILON);
+        camera.set_orientation(30.0, -90.0);
+        assert!((camera.orientation().1 + MAX_PITCH_DEG).abs() < EPSILON);
+        assert!(camera.forward().cross(&camera.up).norm() > EPSILON);
+    }
+
+    #[test]
+    fn angles_round_trip_through_orientation() {
+        let mut camera = camera();
+        for (yaw, pitch) in [(0.0, 0.0), (45.0, 10.0), (-120.0, -60.0), (170.0, 85.0)] {
+            camera.set_orientation(yaw, pitch);
+            let (yaw_out, pitch_out) = camera.orientation();
+            assert!((yaw_out - yaw).abs() < 1e-3, "yaw {yaw} came back as {yaw_out}");
+            assert!((pitch_out - pitch).abs() < 1e-3, "pitch {pitch} came back as {pitch_out}");
+        }
+    }
+
+    #[test]
+    fn degenerate_box_keeps_the_eye_off_the_target() {
+        let mut camera = camera();
+        let point = Point3::new(1.0, 1.0, 1.0);
+        camera.frame_aabb(point, point, 45.0);
+        
+        assert_eq!(camera.target, point);
+        assert!((camera.position - point).norm() >= MIN_FRAME_RADIUS);
+        assert!(camera.view_matrix().iter().all(|value| value.is_finite()));
+    }
+}
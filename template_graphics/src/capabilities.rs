@@ -0,0 +1,109 @@
+//! Driver limits queried from the OpenGL context.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use crate::GlContext;
+use glow::HasContext;
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Capability enabled by `GL_NV_conservative_raster` (not in glow's core constants).
+pub const CONSERVATIVE_RASTERIZATION_NV: u32 = 0x9346;
+
+/// Capability enabled by `GL_INTEL_conservative_rasterization`.
+pub const CONSERVATIVE_RASTERIZATION_INTEL: u32 = 0x83FE;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Implementation limits reported by the driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlCapabilities {
+    /// Largest width or height of a 2D texture.
+    pub max_texture_size: u32,
+    /// Largest supported MSAA sample count.
+    pub max_samples: u32,
+    /// Number of vertex attribute locations.
+    pub max_vertex_attribs: u32,
+    /// Number of texture units available to the fragment shader.
+    pub max_texture_image_units: u32,
+    /// Largest uniform block in bytes.
+    pub max_uniform_block_size: u32,
+    /// Whether conservative rasterization is available (see `conservative_raster_capability`).
+    pub conservative_raster: bool,
+    /// Number of viewports a geometry shader can select with `gl_ViewportIndex` (1 without viewport arrays).
+    pub max_viewports: u32,
+}
+
+impl GlCapabilities {
+    /// Query the limits of the current context.
+    pub fn query(gl: &GlContext) -> Self {
+        let get = |parameter| unsafe { gl.get_parameter_i32(parameter) }.max(0).unsigned_abs();
+        
+        Self {
+            max_texture_size: get(glow::MAX_TEXTURE_SIZE),
+            max_samples: get(glow::MAX_SAMPLES),
+            max_vertex_attribs: get(glow::MAX_VERTEX_ATTRIBS),
+            max_texture_image_units: get(glow::MAX_TEXTURE_IMAGE_UNITS),
+            max_uniform_block_size: get(glow::MAX_UNIFORM_BLOCK_SIZE),
+            conservative_raster: conservative_raster_capability(gl).is_some(),
+            // Querying MAX_VIEWPORTS is itself an error without viewport arrays
+            max_viewports: if supports_viewport_array(gl) { get(glow::MAX_VIEWPORTS).max(1) } else { 1 },
+        }
+    }
+}
+
+// ============================================================
+// ==================== Global Functions ======================
+// ============================================================
+
+/// Find the `glEnable` capability that turns on conservative rasterization.
+/// 
+/// Conservative rasterization covers every pixel a triangle touches, not only those whose
+/// center it contains. Core OpenGL has no such state, so this checks the NVIDIA and Intel
+/// extensions.
+/// 
+/// # Returns
+/// The capability to enable, or `None` if the driver offers neither extension
+pub fn conservative_raster_capability(gl: &GlContext) -> Option<u32> {
+    let extensions = gl.supported_extensions();
+    if extensions.contains("GL_NV_conservative_raster") {
+        Some(CONSERVATIVE_RASTERIZATION_NV)
+    } else if extensions.contains("GL_INTEL_conservative_rasterization") {
+        Some(CONSERVATIVE_RASTERIZATION_INTEL)
+    } else {
+        None
+    }
+}
+
+/// Check whether the context supports viewport arrays (desktop GL 4.1 or `GL_ARB_viewport_array`).
+pub fn supports_viewport_array(gl: &GlContext) -> bool {
+    let version = gl.version();
+    if version.is_embedded {
+        gl.supported_extensions().contains("GL_OES_viewport_array")
+    } else {
+        (version.major, version.minor) >= (4, 1) || gl.supported_extensions().contains("GL_ARB_viewport_array")
+    }
+}
+
+/// Check whether the context supports `glDrawElementsBaseVertex` (desktop GL 3.2, OpenGL ES 3.2
+/// or `GL_ARB_draw_elements_base_vertex`).
+pub fn supports_base_vertex(gl: &GlContext) -> bool {
+    let version = gl.version();
+    (version.major, version.minor) >= (3, 2)
+        || (!version.is_embedded && gl.supported_extensions().contains("GL_ARB_draw_elements_base_vertex"))
+}
+
+/// Check whether the context supports tessellation shaders (desktop GL 4.0 or `GL_ARB_tessellation_shader`).
+/// 
+/// OpenGL ES contexts always report false; the template's shaders target desktop tessellation only.
+pub fn supports_tessellation(gl: &GlContext) -> bool {
+    let version = gl.version();
+    !version.is_embedded
+        && ((version.major, version.minor) >= (4, 0) || gl.supported_extensions().contains("GL_ARB_tessellation_shader"))
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+//! Rectangular clip regions for 2D overlays.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use crate::context::gl_size;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// A rectangle in window pixels with the origin at the top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClipRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ClipRect {
+    /// Create a new clip rectangle.
+    #[must_use]
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// Get the overlapping area of two rectangles (zero-sized if they don't overlap).
+    #[must_use]
+    pub fn intersect(&self, other: &ClipRect) -> ClipRect {
+        let left = self.x.max(other.x);
+        let top = self.y.max(other.y);
+        let right = (self.x + gl_size(self.width)).min(other.x + gl_size(other.width));
+        let bottom = (self.y + gl_size(self.height)).min(other.y + gl_size(other.height));
+        ClipRect {
+            x: left,
+            y: top,
+            width: (right - left).max(0).unsigned_abs(),
+            height: (bottom - top).max(0).unsigned_abs(),
+        }
+    }
+}
\ No newline at end of file
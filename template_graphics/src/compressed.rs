@@ -0,0 +1,492 @@
+//! GPU block-compressed images loaded from DDS and KTX2 files.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::GlContext;
+use glow::HasContext;
+use std::path::Path;
+use tracing;
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// File signature of a DDS file.
+const DDS_MAGIC: &[u8; 4] = b"DDS ";
+
+/// Size of the DDS magic plus header; pixel data (or the DX10 header) follows.
+const DDS_HEADER_SIZE: usize = 128;
+
+/// Size of the extended header present when the four-character code is `DX10`.
+const DDS_DX10_HEADER_SIZE: usize = 20;
+
+/// DDS header flag marking the mip map count field as valid.
+const DDSD_MIPMAPCOUNT: u32 = 0x2_0000;
+
+/// File signature of a KTX2 file.
+const KTX2_MAGIC: [u8; 12] = [0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Offset of the KTX2 level index, after the fixed header and data format index.
+const KTX2_LEVEL_INDEX: usize = 80;
+
+/// Size of one KTX2 level index entry (byte offset, byte length, uncompressed length).
+const KTX2_LEVEL_ENTRY_SIZE: usize = 24;
+
+// S3TC and BPTC formats are extensions and not among glow's core constants
+const COMPRESSED_RGB_S3TC_DXT1: u32 = 0x83F0;
+const COMPRESSED_RGBA_S3TC_DXT1: u32 = 0x83F1;
+const COMPRESSED_RGBA_S3TC_DXT3: u32 = 0x83F2;
+const COMPRESSED_RGBA_S3TC_DXT5: u32 = 0x83F3;
+const COMPRESSED_RED_RGTC1: u32 = 0x8DBB;
+const COMPRESSED_RG_RGTC2: u32 = 0x8DBD;
+const COMPRESSED_RGBA_BPTC_UNORM: u32 = 0x8E8C;
+const COMPRESSED_RGB8_ETC2: u32 = 0x9274;
+const COMPRESSED_RGBA8_ETC2_EAC: u32 = 0x9278;
+
+// ============================================================
+// ====================== Types & Enums =======================
+// ============================================================
+
+/// Block compression format of a `CompressedImage`.
+/// 
+/// Every format encodes 4x4 pixel blocks into 8 or 16 bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressedFormat {
+    /// BC1 / DXT1 without alpha.
+    Bc1Rgb,
+    /// BC1 / DXT1 with 1-bit alpha.
+    Bc1Rgba,
+    /// BC2 / DXT3, explicit 4-bit alpha.
+    Bc2,
+    /// BC3 / DXT5, interpolated alpha.
+    Bc3,
+    /// BC4, single red channel.
+    Bc4,
+    /// BC5, red and green channels (typically normal maps).
+    Bc5,
+    /// BC7, high quality RGBA.
+    Bc7,
+    /// ETC2 RGB.
+    Etc2Rgb,
+    /// ETC2 RGBA with EAC alpha.
+    Etc2Rgba,
+}
+
+impl CompressedFormat {
+    /// Get the OpenGL internal format passed to `compressed_tex_image_2d`.
+    #[must_use]
+    pub fn gl_internal_format(self) -> u32 {
+        match self {
+            Self::Bc1Rgb => COMPRESSED_RGB_S3TC_DXT1,
+            Self::Bc1Rgba => COMPRESSED_RGBA_S3TC_DXT1,
+            Self::Bc2 => COMPRESSED_RGBA_S3TC_DXT3,
+            Self::Bc3 => COMPRESSED_RGBA_S3TC_DXT5,
+            Self::Bc4 => COMPRESSED_RED_RGTC1,
+            Self::Bc5 => COMPRESSED_RG_RGTC2,
+            Self::Bc7 => COMPRESSED_RGBA_BPTC_UNORM,
+            Self::Etc2Rgb => COMPRESSED_RGB8_ETC2,
+            Self::Etc2Rgba => COMPRESSED_RGBA8_ETC2_EAC,
+        }
+    }
+
+    /// Get the size of one 4x4 block in bytes.
+    #[must_use]
+    pub fn block_bytes(self) -> usize {
+        match self {
+            Self::Bc1Rgb | Self::Bc1Rgba | Self::Bc4 | Self::Etc2Rgb => 8,
+            Self::Bc2 | Self::Bc3 | Self::Bc5 | Self::Bc7 | Self::Etc2Rgba => 16,
+        }
+    }
+
+    /// Get the number of bytes a mip level of the given size occupies.
+    /// 
+    /// # Arguments
+    /// * `width` - Level width in pixels
+    /// * `height` - Level height in pixels
+    #[must_use]
+    pub fn level_size(self, width: u32, height: u32) -> usize {
+        width.div_ceil(4) as usize * height.div_ceil(4) as usize * self.block_bytes()
+    }
+
+    /// Check whether the context can sample textures in this format.
+    /// 
+    /// S3TC always needs an extension. RGTC is core since OpenGL 3.0, BPTC since 4.2 and
+    /// ETC2 since 4.3 and OpenGL ES 3.0.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    pub fn is_supported(self, gl: &GlContext) -> bool {
+        let version = gl.version();
+        let extensions = gl.supported_extensions();
+        let desktop = |major, minor| !version.is_embedded && (version.major, version.minor) >= (major, minor);
+        
+        match self {
+            Self::Bc1Rgb | Self::Bc1Rgba | Self::Bc2 | Self::Bc3 => extensions.contains("GL_EXT_texture_compression_s3tc"),
+            Self::Bc4 | Self::Bc5 => desktop(3, 0) || extensions.contains("GL_EXT_texture_compression_rgtc"),
+            Self::Bc7 => {
+                desktop(4, 2)
+                    || extensions.contains("GL_ARB_texture_compression_bptc")
+                    || extensions.contains("GL_EXT_texture_compression_bptc")
+            }
+            Self::Etc2Rgb | Self::Etc2Rgba => {
+                version.is_embedded || desktop(4, 3) || extensions.contains("GL_ARB_ES3_compatibility")
+            }
+        }
+    }
+
+    /// Map a Vulkan format number from a KTX2 header.
+    fn from_vk_format(vk_format: u32) -> Option<Self> {
+        match vk_format {
+            131 => Some(Self::Bc1Rgb),
+            133 => Some(Self::Bc1Rgba),
+            135 => Some(Self::Bc2),
+            137 => Some(Self::Bc3),
+            139 => Some(Self::Bc4),
+            141 => Some(Self::Bc5),
+            145 => Some(Self::Bc7),
+            147 => Some(Self::Etc2Rgb),
+            151 => Some(Self::Etc2Rgba),
+            _ => None,
+        }
+    }
+
+    /// Map a DXGI format number from a DDS DX10 header.
+    fn from_dxgi_format(dxgi_format: u32) -> Option<Self> {
+        match dxgi_format {
+            71 => Some(Self::Bc1Rgba),
+            74 => Some(Self::Bc2),
+            77 => Some(Self::Bc3),
+            80 => Some(Self::Bc4),
+            83 => Some(Self::Bc5),
+            98 => Some(Self::Bc7),
+            _ => None,
+        }
+    }
+
+    /// Map a legacy DDS four-character code.
+    fn from_four_cc(four_cc: &[u8]) -> Option<Self> {
+        match four_cc {
+            b"DXT1" => Some(Self::Bc1Rgba),
+            b"DXT3" => Some(Self::Bc2),
+            b"DXT5" => Some(Self::Bc3),
+            b"ATI1" | b"BC4U" => Some(Self::Bc4),
+            b"ATI2" | b"BC5U" => Some(Self::Bc5),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Block-compressed pixels with their mip chain, ready for `Texture::upload_compressed`.
+/// 
+/// Unlike `DecodedImage`, the rows are kept in file order (top to bottom), since compressed
+/// blocks cannot be flipped cheaply. Flip the V coordinate when sampling if that matters.
+pub struct CompressedImage {
+    pub format: CompressedFormat,
+    pub width: u32,
+    pub height: u32,
+    /// Compressed data of each mip level, largest first.
+    pub levels: Vec<Vec<u8>>,
+}
+
+impl CompressedImage {
+    /// Read a DDS or KTX2 file, detected from its signature.
+    /// 
+    /// # Arguments
+    /// * `path` - Path to the file, relative to the resource root unless absolute
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` if the file cannot be read, or `TemplateError::Texture` if it is not a
+    /// supported DDS or KTX2 file
+    pub fn load(path: &Path) -> TemplateResult<Self> {
+        let path = &crate::resource_path(path);
+        let _span = tracing::debug_span!("load_compressed", path = %path.display()).entered();
+        
+        let bytes = std::fs::read(path)?;
+        let image = if bytes.starts_with(DDS_MAGIC) {
+            Self::from_dds(&bytes)
+        } else if bytes.starts_with(&KTX2_MAGIC) {
+            Self::from_ktx2(&bytes)
+        } else {
+            Err(TemplateError::Texture("not a DDS or KTX2 file".to_string()))
+        };
+        image.map_err(|e| match e {
+            TemplateError::Texture(reason) => TemplateError::Texture(format!("failed to load {}: {}", path.display(), reason)),
+            other => other,
+        })
+    }
+
+    /// Parse the contents of a DDS file.
+    /// 
+    /// Supports DXT1/3/5 and ATI1/2 four-character codes and BC1-BC5 and BC7 in DX10
+    /// headers. Cube maps and arrays are not supported.
+    /// 
+    /// # Arguments
+    /// * `bytes` - Whole file contents
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if the header is truncated or malformed, the format is unsupported,
+    /// or the data is shorter than the mip chain it describes
+    pub fn from_dds(bytes: &[u8]) -> TemplateResult<Self> {
+        if !bytes.starts_with(DDS_MAGIC) {
+            return Err(TemplateError::Texture("missing DDS signature".to_string()));
+        }
+        
+        let flags = read_u32(bytes, 8)?;
+        let height = read_u32(bytes, 12)?;
+        let width = read_u32(bytes, 16)?;
+        // Writers may leave garbage in the mip count when the flag is not set
+        let level_count = if flags & DDSD_MIPMAPCOUNT == 0 { 1 } else { read_u32(bytes, 28)?.max(1) };
+        let four_cc = bytes.get(84..88)
+            .ok_or_else(|| TemplateError::Texture("truncated DDS header".to_string()))?;
+        
+        let (format, data_start) = if &four_cc == b"DX10" {
+            let dxgi_format = read_u32(bytes, DDS_HEADER_SIZE)?;
+            let format = CompressedFormat::from_dxgi_format(dxgi_format)
+                .ok_or_else(|| TemplateError::Texture(format!("unsupported DXGI format {dxgi_format}")))?;
+            (format, DDS_HEADER_SIZE + DDS_DX10_HEADER_SIZE)
+        } else {
+            let format = CompressedFormat::from_four_cc(four_cc)
+                .ok_or_else(|| TemplateError::Texture(format!("unsupported DDS format {}", String::from_utf8_lossy(four_cc))))?;
+            (format, DDS_HEADER_SIZE)
+        };
+        
+        check_level_count(width, height, level_count)?;
+        // DDS stores the levels back to back, largest first
+        let mut levels = Vec::with_capacity(level_count as usize);
+        let mut offset = data_start;
+        for level in 0..level_count {
+            let size = format.level_size(mip_extent(width, level), mip_extent(height, level));
+            levels.push(slice(bytes, offset, size)?.to_vec());
+            offset += size;
+        }
+        
+        Ok(Self { format, width, height, levels })
+    }
+
+    /// Parse the contents of a KTX2 file.
+    /// 
+    /// Supports single-layer 2D textures in `BCn` or ETC2 formats without supercompression
+    /// (Basis Universal and Zstandard payloads need transcoding first).
+    /// 
+    /// # Arguments
+    /// * `bytes` - Whole file contents
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if the header is truncated or malformed, the texture is not a
+    /// single-layer 2D one, the payload is supercompressed, the format is unsupported or a
+    /// level's length does not match its size
+    pub fn from_ktx2(bytes: &[u8]) -> TemplateResult<Self> {
+        if !bytes.starts_with(&KTX2_MAGIC) {
+            return Err(TemplateError::Texture("missing KTX2 signature".to_string()));
+        }
+        
+        let vk_format = read_u32(bytes, 12)?;
+        let width = read_u32(bytes, 20)?;
+        let height = read_u32(bytes, 24)?;
+        let depth = read_u32(bytes, 28)?;
+        let layers = read_u32(bytes, 32)?;
+        let faces = read_u32(bytes, 36)?;
+        let level_count = read_u32(bytes, 40)?.max(1);
+        let supercompression = read_u32(bytes, 44)?;
+        
+        if depth > 1 || layers > 1 || faces > 1 {
+            return Err(TemplateError::Texture("only single-layer 2D KTX2 textures are supported".to_string()));
+        }
+        if supercompression != 0 {
+            return Err(TemplateError::Texture(format!("unsupported KTX2 supercompression scheme {supercompression}")));
+        }
+        let format = CompressedFormat::from_vk_format(vk_format)
+            .ok_or_else(|| TemplateError::Texture(format!("unsupported KTX2 format {vk_format}")))?;
+        check_level_count(width, height, level_count)?;
+        
+        let mut levels = Vec::with_capacity(level_count as usize);
+        for level in 0..level_count {
+            let entry = KTX2_LEVEL_INDEX + level as usize * KTX2_LEVEL_ENTRY_SIZE;
+            let offset = read_u64(bytes, entry)?;
+            let length = read_u64(bytes, entry + 8)?;
+            let expected = format.level_size(mip_extent(width, level), mip_extent(height, level));
+            if length != expected {
+                return Err(TemplateError::Texture(format!(
+                    "KTX2 level {level} holds {length} bytes, expected {expected}"
+                )));
+            }
+            levels.push(slice(bytes, offset, length)?.to_vec());
+        }
+        
+        Ok(Self { format, width, height, levels })
+    }
+
+    /// Check whether the context can sample this image's format.
+    pub fn is_supported(&self, gl: &GlContext) -> bool {
+        self.format.is_supported(gl)
+    }
+}
+
+// ============================================================
+// ===================== Helper Functions =====================
+// ============================================================
+
+/// Get the size of a mip level along one axis.
+pub(crate) fn mip_extent(base: u32, level: u32) -> u32 {
+    base.checked_shr(level).unwrap_or(0).max(1)
+}
+
+/// Reject a header's mip level count if it exceeds the full chain for the base size.
+/// 
+/// Keeps a malformed file from requesting billions of levels before any data is read.
+fn check_level_count(width: u32, height: u32, level_count: u32) -> TemplateResult<()> {
+    let max_levels = u32::BITS - width.max(height).max(1).leading_zeros();
+    if level_count > max_levels {
+        return Err(TemplateError::Texture(format!(
+            "{level_count} mip levels given for a {width}x{height} texture, which has at most {max_levels}"
+        )));
+    }
+    Ok(())
+}
+
+/// Read a little-endian `u32` at a byte offset.
+fn read_u32(bytes: &[u8], offset: usize) -> TemplateResult<u32> {
+    let field = slice(bytes, offset, 4)?;
+    Ok(u32::from_le_bytes([field[0], field[1], field[2], field[3]]))
+}
+
+/// Read a little-endian `u64` at a byte offset, as a size or offset.
+fn read_u64(bytes: &[u8], offset: usize) -> TemplateResult<usize> {
+    let field = slice(bytes, offset, 8)?;
+    let mut value = [0; 8];
+    value.copy_from_slice(field);
+    usize::try_from(u64::from_le_bytes(value))
+        .map_err(|_| TemplateError::Texture("KTX2 offset out of range".to_string()))
+}
+
+/// Borrow `length` bytes at `offset`, failing on truncated files.
+fn slice(bytes: &[u8], offset: usize, length: usize) -> TemplateResult<&[u8]> {
+    offset.checked_add(length)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or_else(|| TemplateError::Texture("file is truncated".to_string()))
+}
+
+// ============================================================
+// ========================== Tests ===========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a DDS file with a legacy four-character code, or a DX10 header for `DX10`.
+    fn dds(width: u32, height: u32, mip_count: Option<u32>, four_cc: [u8; 4], dxgi_format: u32, data_len: usize) -> Vec<u8> {
+        let mut bytes = vec![0; DDS_HEADER_SIZE];
+        bytes[..4].copy_from_slice(DDS_MAGIC);
+        bytes[4..8].copy_from_slice(&124u32.to_le_bytes());
+        let flags = if mip_count.is_some() { DDSD_MIPMAPCOUNT } else { 0 };
+        bytes[8..12].copy_from_slice(&flags.to_le_bytes());
+        bytes[12..16].copy_from_slice(&height.to_le_bytes());
+        bytes[16..20].copy_from_slice(&width.to_le_bytes());
+        // A count that is only valid with the flag set
+        bytes[28..32].copy_from_slice(&mip_count.unwrap_or(7).to_le_bytes());
+        bytes[84..88].copy_from_slice(&four_cc);
+        if &four_cc == b"DX10" {
+            let mut dx10 = [0; DDS_DX10_HEADER_SIZE];
+            dx10[..4].copy_from_slice(&dxgi_format.to_le_bytes());
+            bytes.extend(dx10);
+        }
+        bytes.resize(bytes.len() + data_len, 0xAA);
+        bytes
+    }
+
+    /// Build a KTX2 file with the given level lengths stored back to back.
+    fn ktx2(vk_format: u32, width: u32, height: u32, level_lengths: &[usize]) -> Vec<u8> {
+        let mut bytes = vec![0; KTX2_LEVEL_INDEX];
+        bytes[..12].copy_from_slice(&KTX2_MAGIC);
+        bytes[12..16].copy_from_slice(&vk_format.to_le_bytes());
+        bytes[16..20].copy_from_slice(&1u32.to_le_bytes());
+        bytes[20..24].copy_from_slice(&width.to_le_bytes());
+        bytes[24..28].copy_from_slice(&height.to_le_bytes());
+        bytes[36..40].copy_from_slice(&1u32.to_le_bytes());
+        let level_count = u32::try_from(level_lengths.len()).unwrap();
+        bytes[40..44].copy_from_slice(&level_count.to_le_bytes());
+        
+        let mut offset = KTX2_LEVEL_INDEX + level_lengths.len() * KTX2_LEVEL_ENTRY_SIZE;
+        for &length in level_lengths {
+            bytes.extend((offset as u64).to_le_bytes());
+            bytes.extend((length as u64).to_le_bytes());
+            bytes.extend((length as u64).to_le_bytes());
+            offset += length;
+        }
+        bytes.resize(offset, 0xAA);
+        bytes
+    }
+
+    #[test]
+    fn dds_bc1_and_bc3_read_the_mip_chain() {
+        // 8x8 BC1: 4 blocks, then 1 block each for 4x4, 2x2 and 1x1
+        let image = CompressedImage::from_dds(&dds(8, 8, Some(4), *b"DXT1", 0, 8 * 4 + 8 * 3)).unwrap();
+        assert_eq!(image.format, CompressedFormat::Bc1Rgba);
+        assert_eq!((image.width, image.height), (8, 8));
+        assert_eq!(image.levels.iter().map(Vec::len).collect::<Vec<_>>(), [32, 8, 8, 8]);
+        
+        let image = CompressedImage::from_dds(&dds(8, 4, Some(1), *b"DXT5", 0, 32)).unwrap();
+        assert_eq!(image.format, CompressedFormat::Bc3);
+        assert_eq!(image.levels.len(), 1);
+        assert_eq!(image.levels[0].len(), 32);
+    }
+
+    #[test]
+    fn dds_bc7_uses_the_dx10_header() {
+        let image = CompressedImage::from_dds(&dds(4, 4, Some(1), *b"DX10", 98, 16)).unwrap();
+        assert_eq!(image.format, CompressedFormat::Bc7);
+        assert_eq!(image.levels, [vec![0xAA; 16]]);
+    }
+
+    #[test]
+    fn dds_mip_count_needs_its_flag() {
+        let image = CompressedImage::from_dds(&dds(8, 8, None, *b"DXT1", 0, 32)).unwrap();
+        assert_eq!(image.levels.len(), 1);
+    }
+
+    #[test]
+    fn malformed_dds_files_are_rejected() {
+        let truncated = CompressedImage::from_dds(&dds(8, 8, Some(2), *b"DXT5", 0, 64));
+        assert!(matches!(truncated, Err(TemplateError::Texture(reason)) if reason == "file is truncated"));
+        
+        let mut bad_magic = dds(4, 4, Some(1), *b"DXT1", 0, 8);
+        bad_magic[0] = b'X';
+        assert!(matches!(CompressedImage::from_dds(&bad_magic), Err(TemplateError::Texture(_))));
+        
+        let too_many_levels = CompressedImage::from_dds(&dds(4, 4, Some(4), *b"DXT1", 0, 8 * 4));
+        assert!(matches!(too_many_levels, Err(TemplateError::Texture(reason)) if reason.contains("at most 3")));
+    }
+
+    #[test]
+    fn ktx2_bc7_reads_each_level() {
+        let image = CompressedImage::from_ktx2(&ktx2(145, 8, 4, &[32, 16, 16, 16])).unwrap();
+        assert_eq!(image.format, CompressedFormat::Bc7);
+        assert_eq!((image.width, image.height), (8, 4));
+        assert_eq!(image.levels.iter().map(Vec::len).collect::<Vec<_>>(), [32, 16, 16, 16]);
+    }
+
+    #[test]
+    fn malformed_ktx2_files_are_rejected() {
+        let wrong_length = CompressedImage::from_ktx2(&ktx2(131, 8, 8, &[24]));
+        assert!(matches!(wrong_length, Err(TemplateError::Texture(reason)) if reason == "KTX2 level 0 holds 24 bytes, expected 32"));
+        
+        let mut truncated = ktx2(131, 8, 8, &[32]);
+        truncated.truncate(truncated.len() - 1);
+        assert!(matches!(CompressedImage::from_ktx2(&truncated), Err(TemplateError::Texture(reason)) if reason == "file is truncated"));
+        
+        let mut bad_magic = ktx2(131, 4, 4, &[8]);
+        bad_magic[1] = 0;
+        assert!(matches!(CompressedImage::from_ktx2(&bad_magic), Err(TemplateError::Texture(_))));
+        
+        let too_many_levels = CompressedImage::from_ktx2(&ktx2(131, 4, 4, &[8, 8, 8, 8]));
+        assert!(matches!(too_many_levels, Err(TemplateError::Texture(reason)) if reason.contains("at most 3")));
+    }
+}
@@ -4,42 +4,314 @@
 // ========================= Imports ==========================
 // ============================================================
 
-use template_core::TemplateResult;
-use std::{ffi::CString, sync::Arc};
+use template_core::{TemplateResult, TemplateError};
+use glow::HasContext;
+use glutin::{context::{NotCurrentContext, PossiblyCurrentContext}, display::{GetGlDisplay, GlDisplay}};
+use std::{ffi::{c_void, CStr}, ops::Deref, sync::{Arc, OnceLock}, thread::ThreadId};
 use tracing::info;
 use crate::Window;
+use crate::mesh::PrimitiveRestartIndex;
+use crate::pipeline::PipelineFunctions;
 
 // ============================================================
 // ========================== Types ===========================
 // ============================================================
 
 /// Shared OpenGL context type.
-pub type GlContext = Arc<glow::Context>;
+/// 
+/// The `Arc` only shares the function table; it does not make GL thread-safe. Every GL
+/// call must happen on the thread that created the context (see `assert_gl_thread`).
+/// Do CPU-heavy preparation elsewhere with `WorkerTask` and upload on the GL thread.
+pub type GlContext = Arc<Gl>;
+
+/// Thread the first OpenGL context was created on.
+static GL_THREAD: OnceLock<ThreadId> = OnceLock::new();
+
+/// Graphics API flavour the context is created for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlApi {
+    /// Desktop OpenGL (3.3 core shaders).
+    OpenGl,
+    /// OpenGL ES 3.0 (mobile and embedded targets such as Android or Raspberry Pi).
+    Gles,
+}
+
+impl GlApi {
+    /// Shader header that replaces the `#version` line of shader sources for this API.
+    #[must_use]
+    pub fn shader_preamble(self) -> &'static str {
+        match self {
+            GlApi::OpenGl => "#version 330 core\n",
+            GlApi::Gles => "#version 300 es\nprecision highp float;\n",
+        }
+    }
+}
+
+impl Default for GlApi {
+    /// OpenGL ES on mobile targets, desktop OpenGL everywhere else.
+    fn default() -> Self {
+        if cfg!(any(target_os = "android", target_os = "ios")) {
+            GlApi::Gles
+        } else {
+            GlApi::OpenGl
+        }
+    }
+}
+
+/// How buffer swaps are synchronized with the display refresh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SwapMode {
+    /// Wait for vertical blank on every swap (no tearing, may stutter on missed frames).
+    #[default]
+    Vsync,
+    /// Sync when on time, tear instead of waiting when a frame is late.
+    /// 
+    /// Uses a swap interval of -1 via `GLX_EXT_swap_control_tear` or `WGL_EXT_swap_control_tear`.
+    /// Falls back to `Vsync` with a warning where neither is available, including all EGL displays.
+    Adaptive,
+    /// Swap immediately (uncapped frame rate, may tear).
+    Off,
+}
 
 // ============================================================
 // ===================== Structs & Impls ======================
 // ============================================================
 
+/// Function table of one OpenGL context: glow's, plus the entry points glow does not wrap.
+/// 
+/// The extra entry points are loaded for each context, since a driver may return different
+/// addresses per context (WGL does). Dereferences to `glow::Context`, so `glow::HasContext`
+/// methods are called on it directly.
+pub struct Gl {
+    context: glow::Context,
+    /// Separate shader object entry points, or `None` if the driver lacks them.
+    pub(crate) pipeline: Option<PipelineFunctions>,
+    /// `glPrimitiveRestartIndex`, or `None` if the driver lacks it.
+    pub(crate) primitive_restart_index: Option<PrimitiveRestartIndex>,
+}
+
+impl Gl {
+    /// Load a context's functions.
+    /// 
+    /// # Arguments
+    /// * `load` - Returns the address of a GL entry point in the current context
+    fn load(load: impl Fn(&CStr) -> *const c_void) -> Self {
+        let context = unsafe { glow::Context::from_loader_function_cstr(&load) };
+        Self {
+            context,
+            pipeline: crate::pipeline::load_pipeline_functions(&load),
+            primitive_restart_index: crate::mesh::load_primitive_restart_function(&load),
+        }
+    }
+}
+
+impl Deref for Gl {
+    type Target = glow::Context;
+
+    fn deref(&self) -> &glow::Context {
+        &self.context
+    }
+}
+
 /// Builder for creating OpenGL contexts.
-pub struct GlContextBuilder;
+#[derive(Debug, Clone, Copy)]
+pub struct GlContextBuilder {
+    api: GlApi,
+    swap_mode: SwapMode,
+    visible: bool,
+}
 
 impl GlContextBuilder {
     /// Create a new context builder.
     pub fn new() -> Self {
-        Self
+        Self { api: GlApi::default(), swap_mode: SwapMode::default(), visible: true }
+    }
+
+    /// Select the graphics API the context is created for.
+    #[must_use]
+    pub fn with_api(mut self, api: GlApi) -> Self {
+        self.api = api;
+        self
+    }
+
+    /// Select how buffer swaps are synchronized with the display.
+    #[must_use]
+    pub fn with_swap_mode(mut self, swap_mode: SwapMode) -> Self {
+        self.swap_mode = swap_mode;
+        self
+    }
+
+    /// Show the window (the default), or keep it hidden for offscreen work such as
+    /// benchmarks and tests.
+    #[must_use]
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Get the selected graphics API.
+    #[must_use]
+    pub fn api(&self) -> GlApi {
+        self.api
+    }
+
+    /// Get the selected swap mode.
+    #[must_use]
+    pub fn swap_mode(&self) -> SwapMode {
+        self.swap_mode
+    }
+
+    /// Check whether the window is shown.
+    #[must_use]
+    pub fn visible(&self) -> bool {
+        self.visible
     }
 
     /// Build an OpenGL context for the given window.
     #[allow(clippy::arc_with_non_send_sync)]
     pub fn build(self, window: &Window) -> TemplateResult<GlContext> {
-        info!("Creating OpenGL Context...");
-        let gl = unsafe {
-            glow::Context::from_loader_function(|s| {
-                let s = CString::new(s).expect("failed to construct C string");
-                window.get_proc_address(&s)
-            })
-        };
+        info!("Creating OpenGL Context ({:?})...", self.api);
+        GL_THREAD.get_or_init(|| std::thread::current().id());
+        let gl = Gl::load(|s| window.get_proc_address(s));
         info!("OpenGL Context created successfully");
         Ok(Arc::new(gl))
     }
+
+    /// Create a second context sharing objects with an existing one, for a loading thread.
+    /// 
+    /// Buffers, textures, renderbuffers and linked programs created in either context are
+    /// usable from both. Container objects (VAOs, framebuffers, program pipelines) are
+    /// never shared, so build those on the render thread.
+    /// 
+    /// Platform caveats: the worker side relies on surfaceless contexts
+    /// (`EGL_KHR_surfaceless_context`, or GLX/WGL with `ARB_create_context`); macOS and some
+    /// GLX drivers refuse them, and `SharedContext::make_current` then fails. Treat an error
+    /// as "upload on the render thread instead", not as fatal.
+    /// 
+    /// # Arguments
+    /// * `window` - Window whose context the objects are shared with
+    /// * `existing` - That window's GL context; the shared context requests the same version
+    /// 
+    /// # Returns
+    /// A context to move to the worker thread
+    /// 
+    /// # Errors
+    /// `TemplateError::WindowCreation` if the driver cannot create a shared context
+    pub fn build_shared(self, window: &Window, existing: &GlContext) -> TemplateResult<SharedContext> {
+        let version = existing.version();
+        info!("Creating shared OpenGL context ({:?} {}.{})...", self.api, version.major, version.minor);
+        let context = window.create_shared_context((u8::try_from(version.major).unwrap_or(u8::MAX), u8::try_from(version.minor).unwrap_or(u8::MAX)))?;
+        Ok(SharedContext { context })
+    }
+}
+
+/// An OpenGL context sharing objects with a window's context, not yet current on any thread.
+/// 
+/// It is `Send`: create it on the render thread with `GlContextBuilder::build_shared`, move
+/// it to a worker and call `make_current` there. It is not `Sync`, and neither is the
+/// resulting `WorkerContext`, so each context stays on one thread.
+pub struct SharedContext {
+    context: NotCurrentContext,
+}
+
+impl SharedContext {
+    /// Make the context current on the calling thread without a surface and load GL functions.
+    /// 
+    /// # Returns
+    /// The worker's context
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the platform lacks surfaceless contexts
+    #[allow(clippy::arc_with_non_send_sync)]
+    pub fn make_current(self) -> TemplateResult<WorkerContext> {
+        let context = make_current_surfaceless(self.context)
+            .map_err(|e| TemplateError::OpenGL(format!("shared context cannot be made current: {e}")))?;
+        let display = context.display();
+        let gl = Gl::load(|s| display.get_proc_address(s));
+        info!("Shared OpenGL context current on {:?}", std::thread::current().id());
+        Ok(WorkerContext { gl: Arc::new(gl), _context: context })
+    }
+}
+
+/// A shared context current on a worker thread, for creating resources off the render thread.
+/// 
+/// Only create and fill objects here (`VertexBuffer::new`, `Texture::from_rgba`, ...);
+/// binding and drawing assert that they run on the render thread. Call `finish` before
+/// handing the objects over so the render thread never sees a half-finished upload, and
+/// delete objects on whichever thread owns them last.
+pub struct WorkerContext {
+    gl: GlContext,
+    _context: PossiblyCurrentContext,
+}
+
+impl WorkerContext {
+    /// Get the GL function table for this thread's context.
+    #[must_use]
+    pub fn gl(&self) -> &GlContext {
+        &self.gl
+    }
+
+    /// Wait until every command issued on this context has completed.
+    pub fn finish(&self) {
+        unsafe {
+            self.gl.finish();
+        }
+    }
+}
+
+// ============================================================
+// ===================== Helper Functions =====================
+// ============================================================
+
+/// Make a context current without a surface, which glutin only exposes per backend.
+fn make_current_surfaceless(context: NotCurrentContext) -> glutin::error::Result<PossiblyCurrentContext> {
+    match context {
+        #[cfg(all(any(windows, unix), not(target_vendor = "apple"), not(target_family = "wasm")))]
+        NotCurrentContext::Egl(context) => context.make_current_surfaceless().map(PossiblyCurrentContext::Egl),
+        #[cfg(all(unix, not(target_vendor = "apple"), not(target_os = "android"), not(target_env = "ohos"), not(target_family = "wasm")))]
+        NotCurrentContext::Glx(context) => context.make_current_surfaceless().map(PossiblyCurrentContext::Glx),
+        #[cfg(windows)]
+        NotCurrentContext::Wgl(context) => context.make_current_surfaceless().map(PossiblyCurrentContext::Wgl),
+        #[cfg(target_os = "macos")]
+        NotCurrentContext::Cgl(context) => context.make_current_surfaceless().map(PossiblyCurrentContext::Cgl),
+    }
+}
+
+// ============================================================
+// ==================== Global Functions ======================
+// ============================================================
+
+/// Panic in debug builds if called off the thread that created the OpenGL context.
+/// 
+/// Called from the bind and draw paths to catch rendering from worker threads early.
+/// Compiles to nothing in release builds.
+/// 
+/// # Panics
+/// In debug builds, if the calling thread is not the one the context was created on
+#[inline]
+pub fn assert_gl_thread() {
+    #[cfg(debug_assertions)]
+    if let Some(gl_thread) = GL_THREAD.get() {
+        assert_eq!(
+            *gl_thread,
+            std::thread::current().id(),
+            "OpenGL called from a thread other than the one that created the context",
+        );
+    }
+}
+
+/// Convert a size in pixels to the `i32` that OpenGL size and offset parameters take.
+/// 
+/// Sizes beyond `i32::MAX` saturate; no driver accepts them, so GL reports the error instead.
+#[inline]
+pub(crate) fn gl_size(size: u32) -> i32 {
+    i32::try_from(size).unwrap_or(i32::MAX)
+}
+
+/// Convert an element count, stride or byte length to the `i32` that OpenGL takes.
+/// 
+/// Like `gl_size`, counts beyond `i32::MAX` saturate and are left for GL to reject.
+#[inline]
+pub(crate) fn gl_count(count: usize) -> i32 {
+    i32::try_from(count).unwrap_or(i32::MAX)
 }
\ No newline at end of file
@@ -0,0 +1,197 @@
+//! Per-frame batch of colored debug lines drawn with a single draw call.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::{Aabb, Camera, GlApi, GlContext, Shader};
+use crate::context::gl_count;
+use glow::HasContext;
+use nalgebra::{Matrix4, Perspective3, Point3};
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Floats per line vertex: world position (3) and color (3).
+const VERTEX_FLOATS: usize = 6;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Collects line segments during a frame and draws them all at once.
+/// 
+/// Lines are given in world space. The GPU buffer and line shader are created on the
+/// first draw and reused afterwards.
+#[derive(Default)]
+pub struct DebugLines {
+    vertices: Vec<f32>,
+    uploaded: bool,
+    batch: Option<LineBatch>,
+}
+
+/// GPU side of the batch: line shader and a growable dynamic vertex buffer.
+struct LineBatch {
+    shader: Shader,
+    vao: glow::VertexArray,
+    vbo: glow::Buffer,
+    buffer_size: usize,
+}
+
+impl DebugLines {
+    /// Create an empty batch. No GL objects are created until the first draw.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a line segment.
+    /// 
+    /// # Arguments
+    /// * `a` - Start point in world space
+    /// * `b` - End point in world space
+    /// * `color` - RGB color
+    pub fn add_line(&mut self, a: Point3<f32>, b: Point3<f32>, color: [f32; 3]) {
+        let [red, green, blue] = color;
+        self.vertices.extend_from_slice(&[a.x, a.y, a.z, red, green, blue, b.x, b.y, b.z, red, green, blue]);
+        self.uploaded = false;
+    }
+
+    /// Queue the 12 edges of an axis-aligned box.
+    /// 
+    /// # Arguments
+    /// * `min` - Minimum corner in world space
+    /// * `max` - Maximum corner in world space
+    /// * `color` - RGB color
+    pub fn add_aabb(&mut self, min: Point3<f32>, max: Point3<f32>, color: [f32; 3]) {
+        let corners = Aabb::new(min, max).corners();
+        // Corner index bits select max x (1), max y (2) and max z (4); edges join corners one bit apart
+        for (start, corner) in corners.iter().enumerate() {
+            for axis_bit in [1, 2, 4] {
+                if start & axis_bit == 0 {
+                    self.add_line(*corner, corners[start | axis_bit], color);
+                }
+            }
+        }
+    }
+
+    /// Get the number of queued line segments.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.vertices.len() / (VERTEX_FLOATS * 2)
+    }
+
+    /// Check whether no lines are queued.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Drop every queued line.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.uploaded = false;
+    }
+
+    /// Draw the queued lines without clearing them.
+    /// 
+    /// The vertex data is only uploaded when lines were added since the last draw, so
+    /// drawing the same batch for several views (e.g. stereo) costs one upload.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `api` - Graphics API, used to pick the shader preamble
+    /// * `camera` - Camera the lines are viewed from
+    /// * `projection` - Projection matrix
+    /// 
+    /// # Errors
+    /// `TemplateError::Io`, `TemplateError::ShaderCompilation` or `TemplateError::OpenGL` if the
+    /// line shader or buffers cannot be created on first use
+    pub fn draw(&mut self, gl: &GlContext, api: GlApi, camera: &Camera, projection: &Perspective3<f32>) -> TemplateResult<()> {
+        if self.vertices.is_empty() {
+            return Ok(());
+        }
+        
+        if self.batch.is_none() {
+            self.batch = Some(LineBatch::new(gl, api)?);
+        }
+        let Some(batch) = self.batch.as_mut() else {
+            return Ok(());
+        };
+        
+        if !self.uploaded {
+            batch.upload(gl, &self.vertices);
+            self.uploaded = true;
+        }
+        
+        batch.shader.bind(gl);
+        batch.shader.set_matrix4(gl, "projection", projection.as_matrix());
+        batch.shader.set_matrix4(gl, "view", &camera.view_matrix());
+        batch.shader.set_matrix4(gl, "model", &Matrix4::identity());
+        
+        unsafe {
+            gl.bind_vertex_array(Some(batch.vao));
+            gl.draw_arrays(glow::LINES, 0, gl_count(self.vertices.len() / VERTEX_FLOATS));
+            gl.bind_vertex_array(None);
+        }
+        Ok(())
+    }
+
+    /// Release the line shader and buffer, if they were created.
+    pub fn delete(self, gl: &GlContext) {
+        if let Some(batch) = self.batch {
+            batch.delete(gl);
+        }
+    }
+}
+
+impl LineBatch {
+    fn new(gl: &GlContext, api: GlApi) -> TemplateResult<Self> {
+        let shader = Shader::with_preamble(gl, "line.vert", "line.frag", Some(api.shader_preamble()))?;
+        
+        unsafe {
+            let vao = gl.create_vertex_array().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            let vbo = gl.create_buffer().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            
+            gl.bind_vertex_array(Some(vao));
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(vbo));
+            
+            let stride = gl_count(VERTEX_FLOATS * std::mem::size_of::<f32>());
+            gl.enable_vertex_attrib_array(0);
+            gl.vertex_attrib_pointer_f32(0, 3, glow::FLOAT, false, stride, 0);
+            gl.enable_vertex_attrib_array(1);
+            gl.vertex_attrib_pointer_f32(1, 3, glow::FLOAT, false, stride, 12);
+            
+            gl.bind_vertex_array(None);
+            
+            Ok(Self { shader, vao, vbo, buffer_size: 0 })
+        }
+    }
+
+    fn upload(&mut self, gl: &GlContext, vertices: &[f32]) {
+        let data: &[u8] = bytemuck::cast_slice(vertices);
+        unsafe {
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo));
+            if data.len() > self.buffer_size {
+                gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, data, glow::DYNAMIC_DRAW);
+                self.buffer_size = data.len();
+            } else {
+                gl.buffer_sub_data_u8_slice(glow::ARRAY_BUFFER, 0, data);
+            }
+        }
+    }
+
+    fn delete(self, gl: &GlContext) {
+        unsafe {
+            gl.delete_vertex_array(self.vao);
+            crate::leaks::track_deleted();
+            gl.delete_buffer(self.vbo);
+            crate::leaks::track_deleted();
+        }
+        self.shader.delete(gl);
+    }
+}
\ No newline at end of file
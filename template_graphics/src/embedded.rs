@@ -0,0 +1,60 @@
+//! Shader sources compiled into the binary for single-file distribution.
+
+// ============================================================
+// ===================== Helper Functions =====================
+// ============================================================
+
+macro_rules! embedded_shaders {
+    ($($name:literal),* $(,)?) => {
+        &[$(($name, include_str!(concat!("../../resources/shaders/", $name)))),*]
+    };
+}
+
+/// Every file in resources/shaders/, keyed by its path relative to that folder.
+const SHADERS: &[(&str, &str)] = embedded_shaders![
+    "basic.vert",
+    "basic.frag",
+    "basic_mvp.vert",
+    "basic_flat.vert",
+    "basic_flat.frag",
+    "basic_rgba.vert",
+    "basic_rgba.frag",
+    "line.vert",
+    "line.frag",
+    "particle.vert",
+    "particle.frag",
+    "fullscreen.vert",
+    "grayscale.frag",
+    "fxaa.frag",
+    "depth_visualize.frag",
+    "depth.vert",
+    "depth.frag",
+    "lut.frag",
+    "fallback.vert",
+    "fallback.frag",
+    "gradient.frag",
+    "text.vert",
+    "text.frag",
+    "point.vert",
+    "point.frag",
+    "normal_mapped.vert",
+    "normal_mapped.frag",
+    "point_lit.vert",
+    "point_lit.frag",
+    "id.vert",
+    "id.frag",
+    "overdraw.frag",
+    "overdraw_heatmap.frag",
+];
+
+/// Look up an embedded shader source.
+/// 
+/// # Arguments
+/// * `path` - Path relative to resources/shaders/
+/// 
+/// # Returns
+/// The GLSL source, or `None` if the file was not embedded
+#[must_use]
+pub fn shader_source(path: &str) -> Option<&'static str> {
+    SHADERS.iter().find(|(name, _)| *name == path).map(|(_, source)| *source)
+}
\ No newline at end of file
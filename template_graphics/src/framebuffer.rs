@@ -0,0 +1,409 @@
+//! Offscreen render targets.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::{GlCapabilities, GlContext, Texture};
+use crate::context::gl_size;
+use glow::HasContext;
+use tracing;
+
+// ============================================================
+// ====================== Types & Enums ======================
+// ============================================================
+
+/// Storage format of a framebuffer's color texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorFormat {
+    /// 8 bits per channel, stored as written.
+    #[default]
+    Rgba8,
+    /// 8 bits per channel, sRGB-encoded on write and decoded to linear on sampling.
+    /// 
+    /// Spends the 8 bits perceptually, so dark gradients in linear color do not band.
+    Srgb8Alpha8,
+}
+
+impl ColorFormat {
+    /// Get the matching OpenGL internal format.
+    #[must_use]
+    pub fn internal_format(self) -> u32 {
+        match self {
+            ColorFormat::Rgba8 => glow::RGBA8,
+            ColorFormat::Srgb8Alpha8 => glow::SRGB8_ALPHA8,
+        }
+    }
+}
+
+/// Depth storage of a `Framebuffer`.
+enum DepthAttachment {
+    /// Depth and stencil renderbuffer; cheapest when depth is never sampled.
+    Renderbuffer(glow::Renderbuffer),
+    /// Depth-only texture that later passes can sample.
+    Texture(Texture),
+}
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Framebuffer with an RGBA color texture and a depth renderbuffer or texture.
+pub struct Framebuffer {
+    fbo: glow::Framebuffer,
+    format: ColorFormat,
+    color: Texture,
+    depth: DepthAttachment,
+    width: u32,
+    height: u32,
+}
+
+impl Framebuffer {
+    /// Create a framebuffer of the given size.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `width` - Width in pixels
+    /// * `height` - Height in pixels
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the size exceeds `MAX_TEXTURE_SIZE` or the framebuffer is
+    /// incomplete, or `TemplateError::Texture` if its color texture cannot be created
+    pub fn new(gl: &GlContext, width: u32, height: u32) -> TemplateResult<Self> {
+        Self::with_format(gl, width, height, ColorFormat::Rgba8)
+    }
+
+    /// Create a framebuffer whose color texture uses the given format.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `width` - Width in pixels
+    /// * `height` - Height in pixels
+    /// * `format` - Color texture format
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the size exceeds `MAX_TEXTURE_SIZE` or the framebuffer is
+    /// incomplete, or `TemplateError::Texture` if its color texture cannot be created
+    pub fn with_format(gl: &GlContext, width: u32, height: u32, format: ColorFormat) -> TemplateResult<Self> {
+        Self::create(gl, width, height, format, false)
+    }
+
+    /// Create a framebuffer whose depth is stored in a texture, for passes that read depth.
+    /// 
+    /// The depth texture has no stencil bits.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `width` - Width in pixels
+    /// * `height` - Height in pixels
+    /// * `format` - Color texture format
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the size exceeds `MAX_TEXTURE_SIZE` or the framebuffer is
+    /// incomplete, or `TemplateError::Texture` if its color texture cannot be created
+    pub fn with_depth_texture(gl: &GlContext, width: u32, height: u32, format: ColorFormat) -> TemplateResult<Self> {
+        Self::create(gl, width, height, format, true)
+    }
+
+    fn create(gl: &GlContext, width: u32, height: u32, format: ColorFormat, depth_texture: bool) -> TemplateResult<Self> {
+        tracing::debug!("Creating {}x{} {:?} framebuffer", width, height, format);
+        
+        let max_size = GlCapabilities::query(gl).max_texture_size;
+        if width > max_size || height > max_size {
+            return Err(TemplateError::OpenGL(format!(
+                "framebuffer size {width}x{height} exceeds MAX_TEXTURE_SIZE ({max_size})"
+            )));
+        }
+        
+        let color = Texture::render_target_with_format(gl, width, height, format.internal_format())?;
+        let depth = if depth_texture {
+            DepthAttachment::Texture(Texture::depth_target(gl, width, height)?)
+        } else {
+            let renderbuffer = unsafe { gl.create_renderbuffer() }.map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            unsafe {
+                gl.bind_renderbuffer(glow::RENDERBUFFER, Some(renderbuffer));
+                gl.renderbuffer_storage(glow::RENDERBUFFER, glow::DEPTH24_STENCIL8, gl_size(width), gl_size(height));
+                gl.bind_renderbuffer(glow::RENDERBUFFER, None);
+            }
+            DepthAttachment::Renderbuffer(renderbuffer)
+        };
+        
+        unsafe {
+            let fbo = gl.create_framebuffer().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+            gl.framebuffer_texture_2d(glow::FRAMEBUFFER, glow::COLOR_ATTACHMENT0, glow::TEXTURE_2D, Some(color.handle()), 0);
+            match &depth {
+                DepthAttachment::Renderbuffer(renderbuffer) => {
+                    gl.framebuffer_renderbuffer(glow::FRAMEBUFFER, glow::DEPTH_STENCIL_ATTACHMENT, glow::RENDERBUFFER, Some(*renderbuffer));
+                }
+                DepthAttachment::Texture(texture) => {
+                    gl.framebuffer_texture_2d(glow::FRAMEBUFFER, glow::DEPTH_ATTACHMENT, glow::TEXTURE_2D, Some(texture.handle()), 0);
+                }
+            }
+            
+            let status = gl.check_framebuffer_status(glow::FRAMEBUFFER);
+            gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+            
+            if status != glow::FRAMEBUFFER_COMPLETE {
+                gl.delete_framebuffer(fbo);
+                crate::leaks::track_deleted();
+                delete_depth(gl, depth);
+                color.delete(gl);
+                return Err(TemplateError::OpenGL(format!("framebuffer incomplete (status 0x{status:X})")));
+            }
+            
+            Ok(Self { fbo, format, color, depth, width, height })
+        }
+    }
+
+    /// Bind the framebuffer for drawing and set the viewport to cover it.
+    /// 
+    /// sRGB encoding on write is switched on for `ColorFormat::Srgb8Alpha8` targets and off
+    /// for all others.
+    pub fn bind(&self, gl: &GlContext) {
+        crate::assert_gl_thread();
+        set_srgb_write(gl, self.format == ColorFormat::Srgb8Alpha8);
+        unsafe {
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.fbo));
+            gl.viewport(0, 0, gl_size(self.width), gl_size(self.height));
+        }
+    }
+
+    /// Clear a single color attachment to its own color, leaving other attachments untouched.
+    /// 
+    /// Leaves the framebuffer bound.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `index` - Draw buffer index of the attachment (0 for `COLOR_ATTACHMENT0`)
+    /// * `color` - RGBA clear color
+    pub fn clear_attachment(&self, gl: &GlContext, index: u32, color: [f32; 4]) {
+        unsafe {
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.fbo));
+            gl.clear_buffer_f32_slice(glow::COLOR, index, &color);
+        }
+    }
+
+    /// Bind the default (window) framebuffer. The caller restores the window viewport.
+    /// 
+    /// sRGB encoding on write is switched off, so the final pass writes its shader output
+    /// unchanged. Sampling an sRGB target returns linear values; if the scene was shaded in
+    /// linear space, that final pass must apply the gamma curve itself.
+    pub fn unbind(gl: &GlContext) {
+        set_srgb_write(gl, false);
+        unsafe {
+            gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+        }
+    }
+
+    /// Get the color texture format.
+    #[must_use]
+    pub fn format(&self) -> ColorFormat {
+        self.format
+    }
+
+    /// Get the color attachment.
+    #[must_use]
+    pub fn color_texture(&self) -> &Texture {
+        &self.color
+    }
+
+    /// Get the depth texture, if the framebuffer was created with `with_depth_texture`.
+    #[must_use]
+    pub fn depth_texture(&self) -> Option<&Texture> {
+        match &self.depth {
+            DepthAttachment::Texture(texture) => Some(texture),
+            DepthAttachment::Renderbuffer(_) => None,
+        }
+    }
+
+    /// Get the size in pixels.
+    #[must_use]
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Get the underlying OpenGL framebuffer handle.
+    #[must_use]
+    pub fn handle(&self) -> glow::Framebuffer {
+        self.fbo
+    }
+
+    /// Release the framebuffer and its attachments.
+    pub fn delete(self, gl: &GlContext) {
+        unsafe {
+            gl.delete_framebuffer(self.fbo);
+            crate::leaks::track_deleted();
+        }
+        delete_depth(gl, self.depth);
+        self.color.delete(gl);
+    }
+}
+
+/// Multisampled framebuffer with color and depth renderbuffers, resolved by blitting.
+/// 
+/// Render into it like a `Framebuffer`, then call `resolve` to average the samples into a
+/// single-sampled target (or the window) before reading or presenting the result.
+pub struct MultisampleFramebuffer {
+    fbo: glow::Framebuffer,
+    color: glow::Renderbuffer,
+    depth: glow::Renderbuffer,
+    width: u32,
+    height: u32,
+    samples: u32,
+}
+
+impl MultisampleFramebuffer {
+    /// Create a multisampled framebuffer, clamping the sample count to `MAX_SAMPLES`.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `width` - Width in pixels
+    /// * `height` - Height in pixels
+    /// * `samples` - Requested samples per pixel
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the renderbuffers cannot be created or the framebuffer is incomplete
+    pub fn new(gl: &GlContext, width: u32, height: u32, samples: u32) -> TemplateResult<Self> {
+        let max_samples = GlCapabilities::query(gl).max_samples;
+        let samples = samples.clamp(1, max_samples.max(1));
+        tracing::debug!("Creating {}x{} framebuffer with {}x MSAA", width, height, samples);
+        
+        unsafe {
+            let fbo = gl.create_framebuffer().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            let color = match gl.create_renderbuffer() {
+                Ok(color) => color,
+                Err(error) => {
+                    gl.delete_framebuffer(fbo);
+                    crate::leaks::track_deleted();
+                    return Err(TemplateError::OpenGL(error));
+                }
+            };
+            crate::leaks::track_created();
+            let depth = match gl.create_renderbuffer() {
+                Ok(depth) => depth,
+                Err(error) => {
+                    gl.delete_framebuffer(fbo);
+                    crate::leaks::track_deleted();
+                    gl.delete_renderbuffer(color);
+                    crate::leaks::track_deleted();
+                    return Err(TemplateError::OpenGL(error));
+                }
+            };
+            crate::leaks::track_created();
+            
+            gl.bind_renderbuffer(glow::RENDERBUFFER, Some(color));
+            gl.renderbuffer_storage_multisample(glow::RENDERBUFFER, gl_size(samples), glow::RGBA8, gl_size(width), gl_size(height));
+            gl.bind_renderbuffer(glow::RENDERBUFFER, Some(depth));
+            gl.renderbuffer_storage_multisample(glow::RENDERBUFFER, gl_size(samples), glow::DEPTH24_STENCIL8, gl_size(width), gl_size(height));
+            gl.bind_renderbuffer(glow::RENDERBUFFER, None);
+            
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+            gl.framebuffer_renderbuffer(glow::FRAMEBUFFER, glow::COLOR_ATTACHMENT0, glow::RENDERBUFFER, Some(color));
+            gl.framebuffer_renderbuffer(glow::FRAMEBUFFER, glow::DEPTH_STENCIL_ATTACHMENT, glow::RENDERBUFFER, Some(depth));
+            
+            let status = gl.check_framebuffer_status(glow::FRAMEBUFFER);
+            gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+            
+            if status != glow::FRAMEBUFFER_COMPLETE {
+                gl.delete_framebuffer(fbo);
+                crate::leaks::track_deleted();
+                gl.delete_renderbuffer(color);
+                crate::leaks::track_deleted();
+                gl.delete_renderbuffer(depth);
+                crate::leaks::track_deleted();
+                return Err(TemplateError::OpenGL(format!("multisample framebuffer incomplete (status 0x{status:X})")));
+            }
+            
+            Ok(Self { fbo, color, depth, width, height, samples })
+        }
+    }
+
+    /// Bind the framebuffer for drawing and set the viewport to cover it.
+    pub fn bind(&self, gl: &GlContext) {
+        unsafe {
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.fbo));
+            gl.viewport(0, 0, gl_size(self.width), gl_size(self.height));
+        }
+    }
+
+    /// Resolve the samples into a single-sampled target of the same size.
+    /// 
+    /// Leaves the target bound for drawing.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `target` - Framebuffer to resolve into, or `None` for the window
+    pub fn resolve(&self, gl: &GlContext, target: Option<&Framebuffer>) {
+        let (width, height) = (gl_size(self.width), gl_size(self.height));
+        unsafe {
+            gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(self.fbo));
+            gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, target.map(Framebuffer::handle));
+            gl.blit_framebuffer(0, 0, width, height, 0, 0, width, height, glow::COLOR_BUFFER_BIT, glow::NEAREST);
+            gl.bind_framebuffer(glow::FRAMEBUFFER, target.map(Framebuffer::handle));
+        }
+    }
+
+    /// Get the size in pixels.
+    #[must_use]
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Get the number of samples per pixel actually allocated.
+    #[must_use]
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// Release the framebuffer and its renderbuffers.
+    pub fn delete(self, gl: &GlContext) {
+        unsafe {
+            gl.delete_framebuffer(self.fbo);
+            crate::leaks::track_deleted();
+            gl.delete_renderbuffer(self.color);
+            crate::leaks::track_deleted();
+            gl.delete_renderbuffer(self.depth);
+            crate::leaks::track_deleted();
+        }
+    }
+}
+
+// ============================================================
+// ==================== Helper Functions ======================
+// ============================================================
+
+/// Release a framebuffer's depth attachment.
+fn delete_depth(gl: &GlContext, depth: DepthAttachment) {
+    match depth {
+        DepthAttachment::Renderbuffer(renderbuffer) => {
+            unsafe {
+                gl.delete_renderbuffer(renderbuffer);
+            }
+            crate::leaks::track_deleted();
+        }
+        DepthAttachment::Texture(texture) => texture.delete(gl),
+    }
+}
+
+/// Enable or disable linear-to-sRGB conversion when writing to sRGB attachments.
+/// 
+/// OpenGL ES always converts for sRGB attachments and has no switch, so this only
+/// affects desktop contexts.
+fn set_srgb_write(gl: &GlContext, enabled: bool) {
+    if gl.version().is_embedded {
+        return;
+    }
+    unsafe {
+        if enabled {
+            gl.enable(glow::FRAMEBUFFER_SRGB);
+        } else {
+            gl.disable(glow::FRAMEBUFFER_SRGB);
+        }
+    }
+}
\ No newline at end of file
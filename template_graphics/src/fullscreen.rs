@@ -0,0 +1,50 @@
+//! Fullscreen triangle used by post-processing passes.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::GlContext;
+use glow::HasContext;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Draws a single triangle covering the viewport.
+/// 
+/// Vertex positions and texture coordinates are generated from `gl_VertexID` in
+/// `fullscreen.vert`, so only an empty vertex array is needed.
+pub struct FullscreenQuad {
+    vao: glow::VertexArray,
+}
+
+impl FullscreenQuad {
+    /// Create the empty vertex array used for drawing.
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the vertex array cannot be created
+    pub fn new(gl: &GlContext) -> TemplateResult<Self> {
+        let vao = unsafe { gl.create_vertex_array() }.map_err(TemplateError::OpenGL)?;
+        crate::leaks::track_created();
+        Ok(Self { vao })
+    }
+
+    /// Draw the fullscreen triangle with the currently bound shader.
+    pub fn draw(&self, gl: &GlContext) {
+        unsafe {
+            gl.bind_vertex_array(Some(self.vao));
+            gl.draw_arrays(glow::TRIANGLES, 0, 3);
+            gl.bind_vertex_array(None);
+        }
+    }
+
+    /// Release the vertex array.
+    pub fn delete(self, gl: &GlContext) {
+        unsafe {
+            gl.delete_vertex_array(self.vao);
+            crate::leaks::track_deleted();
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,124 @@
+//! Reference grid and axis gizmos drawn with cached line meshes.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::TemplateResult;
+use crate::{Camera, GlApi, GlContext, Mesh, Shader};
+use nalgebra::{Matrix4, Perspective3};
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Cached line meshes and shader used to draw the ground grid and XYZ axes.
+pub struct Gizmos {
+    shader: Shader,
+    grid: Option<((f32, f32), Mesh)>,
+    axes: Option<(f32, Mesh)>,
+}
+
+impl Gizmos {
+    /// Compile the colored-line shader used by all gizmos.
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the line shader cannot be loaded
+    pub fn new(gl: &GlContext, api: GlApi) -> TemplateResult<Self> {
+        let shader = Shader::with_preamble(gl, "line.vert", "line.frag", Some(api.shader_preamble()))?;
+        Ok(Self { shader, grid: None, axes: None })
+    }
+
+    /// Release the shader and any cached meshes.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context the gizmos were created with
+    pub fn delete(self, gl: &GlContext) {
+        self.shader.delete(gl);
+        if let Some((_, mesh)) = self.grid {
+            mesh.delete(gl);
+        }
+        if let Some((_, mesh)) = self.axes {
+            mesh.delete(gl);
+        }
+    }
+
+    /// Draw the ground grid, rebuilding the cached mesh if the extent or spacing changed.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `camera` - Camera the grid is viewed from
+    /// * `projection` - Projection matrix
+    /// * `size` - Half extent of the grid on the XZ plane
+    /// * `spacing` - Distance between neighbouring grid lines
+    pub fn draw_grid(&mut self, gl: &GlContext, camera: &Camera, projection: &Perspective3<f32>, size: f32, spacing: f32) {
+        if self.grid.as_ref().is_some_and(|(key, _)| *key != (size, spacing)) {
+            if let Some((_, mesh)) = self.grid.take() {
+                mesh.delete(gl);
+            }
+        }
+        
+        let (_, mesh) = self.grid
+            .get_or_insert_with(|| ((size, spacing), Mesh::new_lines(gl, &Self::grid_vertices(size, spacing))));
+        Self::draw_lines(&mut self.shader, gl, mesh, camera, projection);
+    }
+
+    /// Draw the XYZ axes, rebuilding the cached mesh if the length changed.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `camera` - Camera the axes are viewed from
+    /// * `projection` - Projection matrix
+    /// * `length` - Length of each axis line
+    pub fn draw_axes(&mut self, gl: &GlContext, camera: &Camera, projection: &Perspective3<f32>, length: f32) {
+        if self.axes.as_ref().is_some_and(|(key, _)| key.to_bits() != length.to_bits()) {
+            if let Some((_, mesh)) = self.axes.take() {
+                mesh.delete(gl);
+            }
+        }
+        
+        let (_, mesh) = self.axes
+            .get_or_insert_with(|| (length, Mesh::new_lines(gl, &Self::axes_vertices(length))));
+        Self::draw_lines(&mut self.shader, gl, mesh, camera, projection);
+    }
+
+    fn draw_lines(shader: &mut Shader, gl: &GlContext, mesh: &Mesh, camera: &Camera, projection: &Perspective3<f32>) {
+        shader.bind(gl);
+        shader.set_matrix4(gl, "projection", projection.as_matrix());
+        shader.set_matrix4(gl, "view", &camera.view_matrix());
+        shader.set_matrix4(gl, "model", &Matrix4::identity());
+        mesh.draw(gl);
+    }
+
+    /// Build gray grid lines on the XZ plane (6 floats per vertex: x, y, z, r, g, b).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn grid_vertices(size: f32, spacing: f32) -> Vec<f32> {
+        let spacing = spacing.max(f32::EPSILON);
+        let steps = (size / spacing).floor() as i32;
+        let color = [0.5, 0.5, 0.5];
+        
+        let mut vertices = Vec::new();
+        for step in -steps..=steps {
+            let offset = step as f32 * spacing;
+            vertices.extend_from_slice(&[offset, 0.0, -size, color[0], color[1], color[2]]);
+            vertices.extend_from_slice(&[offset, 0.0, size, color[0], color[1], color[2]]);
+            vertices.extend_from_slice(&[-size, 0.0, offset, color[0], color[1], color[2]]);
+            vertices.extend_from_slice(&[size, 0.0, offset, color[0], color[1], color[2]]);
+        }
+        vertices
+    }
+
+    /// Build red/green/blue lines along the X/Y/Z axes (6 floats per vertex: x, y, z, r, g, b).
+    #[must_use]
+    pub fn axes_vertices(length: f32) -> Vec<f32> {
+        vec![
+            0.0, 0.0, 0.0,     1.0, 0.0, 0.0,
+            length, 0.0, 0.0,  1.0, 0.0, 0.0,
+            0.0, 0.0, 0.0,     0.0, 1.0, 0.0,
+            0.0, length, 0.0,  0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0,     0.0, 0.0, 1.0,
+            0.0, 0.0, length,  0.0, 0.0, 1.0,
+        ]
+    }
+}
\ No newline at end of file
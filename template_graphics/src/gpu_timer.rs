@@ -0,0 +1,164 @@
+//! GPU frame timing with asynchronous timer queries.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::GlContext;
+use glow::HasContext;
+use tracing;
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Queries in flight. Each result is read this many frames after it was issued.
+const QUERY_COUNT: usize = 2;
+
+/// `GL_GPU_DISJOINT_EXT`, set when a timing result on OpenGL ES is unreliable.
+const GPU_DISJOINT_EXT: u32 = 0x8FBB;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Measures GPU time spent between `begin` and `end` with `TIME_ELAPSED` queries.
+/// 
+/// Two queries are used in turn and each result is read one frame late, once the GPU has
+/// finished it, so reading never stalls the pipeline. Without timer query support every
+/// call is a no-op and the elapsed time stays 0, which is also what `default` creates.
+#[derive(Default)]
+pub struct GpuTimer {
+    queries: Option<[glow::Query; QUERY_COUNT]>,
+    pending: [bool; QUERY_COUNT],
+    current: usize,
+    active: bool,
+    embedded: bool,
+    elapsed_ns: u64,
+}
+
+impl GpuTimer {
+    /// Create a timer, or an inactive one if the context has no timer queries.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if timer queries are supported but cannot be created
+    pub fn new(gl: &GlContext) -> TemplateResult<Self> {
+        let embedded = gl.version().is_embedded;
+        let queries = if Self::is_supported(gl) {
+            let mut queries = [None; QUERY_COUNT];
+            for query in &mut queries {
+                *query = Some(unsafe { gl.create_query() }.map_err(TemplateError::OpenGL)?);
+                crate::leaks::track_created();
+            }
+            Some(queries.map(Option::unwrap))
+        } else {
+            tracing::info!("Timer queries not supported, GPU time will read as 0");
+            None
+        };
+        
+        Ok(Self {
+            queries,
+            pending: [false; QUERY_COUNT],
+            current: 0,
+            active: false,
+            embedded,
+            elapsed_ns: 0,
+        })
+    }
+
+    /// Check whether the context supports `TIME_ELAPSED` queries.
+    pub fn is_supported(gl: &GlContext) -> bool {
+        let version = gl.version();
+        let extensions = gl.supported_extensions();
+        if version.is_embedded {
+            extensions.contains("GL_EXT_disjoint_timer_query")
+        } else {
+            (version.major, version.minor) >= (3, 3) || extensions.contains("GL_ARB_timer_query")
+        }
+    }
+
+    /// Collect the oldest finished result and start timing.
+    /// 
+    /// Does nothing if a measurement is already running, since timer queries cannot nest.
+    pub fn begin(&mut self, gl: &GlContext) {
+        let Some(queries) = self.queries else {
+            return;
+        };
+        if self.active {
+            return;
+        }
+        
+        let query = queries[self.current];
+        if self.pending[self.current] {
+            self.collect(gl, query);
+        }
+        
+        unsafe {
+            gl.begin_query(glow::TIME_ELAPSED, query);
+        }
+        self.active = true;
+    }
+
+    /// Stop timing. The result becomes available through `elapsed_ns` a frame later.
+    pub fn end(&mut self, gl: &GlContext) {
+        if !self.active {
+            return;
+        }
+        
+        unsafe {
+            gl.end_query(glow::TIME_ELAPSED);
+        }
+        self.pending[self.current] = true;
+        self.current = (self.current + 1) % QUERY_COUNT;
+        self.active = false;
+    }
+
+    /// Get the GPU time of the most recently collected measurement in nanoseconds.
+    #[must_use]
+    pub fn elapsed_ns(&self) -> u64 {
+        self.elapsed_ns
+    }
+
+    /// Check whether this timer is measuring anything.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.queries.is_some()
+    }
+
+    /// Release the timer queries.
+    pub fn delete(self, gl: &GlContext) {
+        let Some(queries) = self.queries else {
+            return;
+        };
+        for query in queries {
+            unsafe {
+                gl.delete_query(query);
+            }
+            crate::leaks::track_deleted();
+        }
+    }
+
+    /// Read a finished query, keeping the previous value if it is not ready or unreliable.
+    fn collect(&mut self, gl: &GlContext, query: glow::Query) {
+        unsafe {
+            if gl.get_query_parameter_u32(query, glow::QUERY_RESULT_AVAILABLE) == 0 {
+                return;
+            }
+            // Read all 64 bits; a 32-bit result wraps after about 4.29 seconds. Every context with
+            // timer queries has glGetQueryObjectui64v (or its EXT variant on OpenGL ES). With no
+            // QUERY_BUFFER bound, the "offset" is the client address the result is written to.
+            let mut elapsed: u64 = 0;
+            gl.get_query_parameter_u64_with_offset(query, glow::QUERY_RESULT, std::ptr::from_mut(&mut elapsed) as usize);
+            // A disjoint event (e.g. a clock change) invalidates every query in flight
+            if self.embedded && gl.get_parameter_i32(GPU_DISJOINT_EXT) != 0 {
+                return;
+            }
+            self.elapsed_ns = elapsed;
+        }
+        self.pending[self.current] = false;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,182 @@
+//! Integer ID render target for occlusion-correct object picking.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::{Camera, GlApi, GlContext, SceneItem, Shader};
+use crate::context::gl_size;
+use glow::HasContext;
+use nalgebra::Perspective3;
+use tracing;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Framebuffer with an `R32UI` color attachment holding the ID of the item drawn at each pixel.
+/// 
+/// Item `i` of the rendered slice is written as `i + 1`; 0 means nothing was drawn there.
+/// IDs are plain integers, so they are never blended, filtered or multisampled.
+pub struct IdBuffer {
+    fbo: glow::Framebuffer,
+    ids: glow::Texture,
+    depth: glow::Renderbuffer,
+    shader: Shader,
+    width: u32,
+    height: u32,
+}
+
+impl IdBuffer {
+    /// Create an ID buffer and compile the `id` shader.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `api` - Graphics API the shader preamble is chosen for
+    /// * `width` - Width in pixels
+    /// * `height` - Height in pixels
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the `id` shader cannot be loaded,
+    /// `TemplateError::Texture` or `TemplateError::OpenGL` if the targets cannot be created or the
+    /// framebuffer is incomplete
+    pub fn new(gl: &GlContext, api: GlApi, width: u32, height: u32) -> TemplateResult<Self> {
+        tracing::debug!("Creating {}x{} ID buffer", width, height);
+        let shader = Shader::with_preamble(gl, "id.vert", "id.frag", Some(api.shader_preamble()))?;
+        
+        unsafe {
+            let ids = gl.create_texture().map_err(TemplateError::Texture)?;
+            crate::leaks::track_created();
+            gl.bind_texture(glow::TEXTURE_2D, Some(ids));
+            gl.tex_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                glow::R32UI.cast_signed(),
+                gl_size(width),
+                gl_size(height),
+                0,
+                glow::RED_INTEGER,
+                glow::UNSIGNED_INT,
+                glow::PixelUnpackData::Slice(None),
+            );
+            // Integer textures are incomplete with linear filtering
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, glow::NEAREST.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, glow::NEAREST.cast_signed());
+            gl.bind_texture(glow::TEXTURE_2D, None);
+            
+            let depth = gl.create_renderbuffer().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            gl.bind_renderbuffer(glow::RENDERBUFFER, Some(depth));
+            gl.renderbuffer_storage(glow::RENDERBUFFER, glow::DEPTH_COMPONENT24, gl_size(width), gl_size(height));
+            gl.bind_renderbuffer(glow::RENDERBUFFER, None);
+            
+            let fbo = gl.create_framebuffer().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+            gl.framebuffer_texture_2d(glow::FRAMEBUFFER, glow::COLOR_ATTACHMENT0, glow::TEXTURE_2D, Some(ids), 0);
+            gl.framebuffer_renderbuffer(glow::FRAMEBUFFER, glow::DEPTH_ATTACHMENT, glow::RENDERBUFFER, Some(depth));
+            
+            let status = gl.check_framebuffer_status(glow::FRAMEBUFFER);
+            gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+            
+            let buffer = Self { fbo, ids, depth, shader, width, height };
+            if status != glow::FRAMEBUFFER_COMPLETE {
+                buffer.delete(gl);
+                return Err(TemplateError::OpenGL(format!("ID framebuffer incomplete (status 0x{status:X})")));
+            }
+            Ok(buffer)
+        }
+    }
+
+    /// Draw each item's ID into the buffer, replacing its previous contents.
+    /// 
+    /// Materials and transparency are ignored: every item is opaque and depth-tested, so
+    /// the front-most item wins each pixel. The previous framebuffer binding and viewport
+    /// are not restored; rebind the window framebuffer afterwards.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `items` - Items to draw; item `i` gets ID `i + 1`
+    /// * `camera` - Camera the scene is viewed from
+    /// * `projection` - Projection matrix
+    pub fn render(&mut self, gl: &GlContext, items: &[SceneItem], camera: &Camera, projection: &Perspective3<f32>) {
+        crate::assert_gl_thread();
+        unsafe {
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.fbo));
+            gl.viewport(0, 0, gl_size(self.width), gl_size(self.height));
+            gl.clear_buffer_u32_slice(glow::COLOR, 0, &[0, 0, 0, 0]);
+            gl.clear_buffer_f32_slice(glow::DEPTH, 0, &[1.0]);
+        }
+        
+        self.shader.bind(gl);
+        self.shader.set_matrix4(gl, "projection", projection.as_matrix());
+        self.shader.set_matrix4(gl, "view", &camera.view_matrix());
+        for (id, item) in (1u32..).zip(items) {
+            self.shader.set_uint(gl, "objectId", id);
+            self.shader.set_matrix4(gl, "model", &item.model);
+            item.mesh.draw(gl);
+        }
+    }
+
+    /// Read the ID at a pixel of the last `render`.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `x` - Horizontal pixel position (origin left)
+    /// * `y` - Vertical pixel position (origin bottom, as in OpenGL)
+    /// 
+    /// # Returns
+    /// The index of the item drawn at that pixel, or `None` for background and positions outside the buffer
+    pub fn read(&self, gl: &GlContext, x: u32, y: u32) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        
+        let mut pixel = [0u8; 4];
+        unsafe {
+            gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(self.fbo));
+            gl.pixel_store_i32(glow::PACK_ALIGNMENT, 1);
+            gl.read_pixels(
+                gl_size(x),
+                gl_size(y),
+                1,
+                1,
+                glow::RED_INTEGER,
+                glow::UNSIGNED_INT,
+                glow::PixelPackData::Slice(Some(&mut pixel)),
+            );
+            gl.bind_framebuffer(glow::READ_FRAMEBUFFER, None);
+        }
+        
+        match u32::from_ne_bytes(pixel) {
+            0 => None,
+            id => Some(id as usize - 1),
+        }
+    }
+
+    /// Get the size in pixels.
+    #[must_use]
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Get the `R32UI` ID texture, e.g. for a segmentation view.
+    #[must_use]
+    pub fn id_texture(&self) -> glow::Texture {
+        self.ids
+    }
+
+    /// Release the framebuffer, its attachments and the shader.
+    pub fn delete(self, gl: &GlContext) {
+        unsafe {
+            gl.delete_framebuffer(self.fbo);
+            crate::leaks::track_deleted();
+            gl.delete_texture(self.ids);
+            crate::leaks::track_deleted();
+            gl.delete_renderbuffer(self.depth);
+            crate::leaks::track_deleted();
+        }
+        self.shader.delete(gl);
+    }
+}
\ No newline at end of file
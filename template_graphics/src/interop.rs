@@ -0,0 +1,121 @@
+//! Saving and restoring GL state around the renderer's draws when sharing a context.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use crate::GlContext;
+use glow::HasContext;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Snapshot of the GL state other code sharing the context is likely to depend on,
+/// restored when the guard is dropped.
+/// 
+/// Covers the bound program, VAO, array buffer, framebuffer, active texture unit and its
+/// 2D texture, viewport, scissor, blending, depth and culling settings. Reading state
+/// back stalls on some drivers, so use it for interop rather than in every draw.
+#[allow(clippy::struct_excessive_bools)]
+pub struct GlStateGuard<'a> {
+    gl: &'a GlContext,
+    program: Option<glow::Program>,
+    vertex_array: Option<glow::VertexArray>,
+    array_buffer: Option<glow::Buffer>,
+    framebuffer: Option<glow::Framebuffer>,
+    active_texture: u32,
+    texture: Option<glow::Texture>,
+    viewport: [i32; 4],
+    scissor_box: [i32; 4],
+    scissor_test: bool,
+    blend: bool,
+    /// Source RGB, destination RGB, source alpha, destination alpha.
+    blend_func: [u32; 4],
+    depth_test: bool,
+    depth_mask: bool,
+    depth_func: u32,
+    cull_face: bool,
+    cull_face_mode: u32,
+    front_face: u32,
+}
+
+impl<'a> GlStateGuard<'a> {
+    /// Read the current state.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context whose state is saved
+    pub fn new(gl: &'a GlContext) -> Self {
+        unsafe {
+            let get = |parameter| gl.get_parameter_i32(parameter).cast_unsigned();
+            let mut viewport = [0; 4];
+            gl.get_parameter_i32_slice(glow::VIEWPORT, &mut viewport);
+            let mut scissor_box = [0; 4];
+            gl.get_parameter_i32_slice(glow::SCISSOR_BOX, &mut scissor_box);
+            
+            Self {
+                gl,
+                program: gl.get_parameter_program(glow::CURRENT_PROGRAM),
+                vertex_array: gl.get_parameter_vertex_array(glow::VERTEX_ARRAY_BINDING),
+                array_buffer: gl.get_parameter_buffer(glow::ARRAY_BUFFER_BINDING),
+                framebuffer: gl.get_parameter_framebuffer(glow::FRAMEBUFFER_BINDING),
+                active_texture: get(glow::ACTIVE_TEXTURE),
+                texture: gl.get_parameter_texture(glow::TEXTURE_BINDING_2D),
+                viewport,
+                scissor_box,
+                scissor_test: gl.is_enabled(glow::SCISSOR_TEST),
+                blend: gl.is_enabled(glow::BLEND),
+                blend_func: [
+                    get(glow::BLEND_SRC_RGB),
+                    get(glow::BLEND_DST_RGB),
+                    get(glow::BLEND_SRC_ALPHA),
+                    get(glow::BLEND_DST_ALPHA),
+                ],
+                depth_test: gl.is_enabled(glow::DEPTH_TEST),
+                depth_mask: get(glow::DEPTH_WRITEMASK) != 0,
+                depth_func: get(glow::DEPTH_FUNC),
+                cull_face: gl.is_enabled(glow::CULL_FACE),
+                cull_face_mode: get(glow::CULL_FACE_MODE),
+                front_face: get(glow::FRONT_FACE),
+            }
+        }
+    }
+}
+
+impl Drop for GlStateGuard<'_> {
+    fn drop(&mut self) {
+        let gl = self.gl;
+        let set = |capability, enabled| unsafe {
+            if enabled {
+                gl.enable(capability);
+            } else {
+                gl.disable(capability);
+            }
+        };
+        
+        unsafe {
+            gl.use_program(self.program);
+            gl.bind_vertex_array(self.vertex_array);
+            gl.bind_buffer(glow::ARRAY_BUFFER, self.array_buffer);
+            gl.bind_framebuffer(glow::FRAMEBUFFER, self.framebuffer);
+            gl.active_texture(self.active_texture);
+            gl.bind_texture(glow::TEXTURE_2D, self.texture);
+            
+            let [x, y, width, height] = self.viewport;
+            gl.viewport(x, y, width, height);
+            let [x, y, width, height] = self.scissor_box;
+            gl.scissor(x, y, width, height);
+            
+            let [source_rgb, destination_rgb, source_alpha, destination_alpha] = self.blend_func;
+            gl.blend_func_separate(source_rgb, destination_rgb, source_alpha, destination_alpha);
+            gl.depth_mask(self.depth_mask);
+            gl.depth_func(self.depth_func);
+            gl.cull_face(self.cull_face_mode);
+            gl.front_face(self.front_face);
+        }
+        set(glow::SCISSOR_TEST, self.scissor_test);
+        set(glow::BLEND, self.blend);
+        set(glow::DEPTH_TEST, self.depth_test);
+        set(glow::CULL_FACE, self.cull_face);
+    }
+}
\ No newline at end of file
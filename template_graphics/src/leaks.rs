@@ -0,0 +1,56 @@
+//! Live OpenGL object counting for leak detection.
+//! 
+//! Counting only happens in debug builds with the `leak-check` feature enabled; otherwise
+//! the tracking calls compile to nothing.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+#[cfg(feature = "leak-check")]
+use std::sync::atomic::{AtomicIsize, Ordering};
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Number of GL objects created but not yet deleted, across all contexts.
+#[cfg(feature = "leak-check")]
+static LIVE_OBJECTS: AtomicIsize = AtomicIsize::new(0);
+
+// ============================================================
+// ==================== Global Functions ======================
+// ============================================================
+
+/// Get the number of GL objects (vertex arrays, buffers, programs, program pipelines,
+/// textures, framebuffers and renderbuffers) that were created but not yet deleted.
+/// 
+/// Always 0 in release builds. A negative count means an object was deleted twice.
+#[cfg(feature = "leak-check")]
+pub fn live_object_count() -> isize {
+    LIVE_OBJECTS.load(Ordering::Relaxed)
+}
+
+/// Record that a GL object was created.
+#[inline]
+pub(crate) fn track_created() {
+    #[cfg(all(feature = "leak-check", debug_assertions))]
+    LIVE_OBJECTS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Record that a GL object was deleted.
+#[inline]
+pub(crate) fn track_deleted() {
+    #[cfg(all(feature = "leak-check", debug_assertions))]
+    LIVE_OBJECTS.fetch_sub(1, Ordering::Relaxed);
+}
+
+/// Forget every tracked object after the context was lost.
+/// 
+/// Objects of a lost context are abandoned rather than deleted, so they would otherwise
+/// be reported as leaks forever.
+#[inline]
+pub(crate) fn track_context_lost() {
+    #[cfg(all(feature = "leak-check", debug_assertions))]
+    LIVE_OBJECTS.store(0, Ordering::Relaxed);
+}
\ No newline at end of file
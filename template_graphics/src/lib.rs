@@ -5,17 +5,102 @@
 // ============================================================
 
 pub mod context;
+pub mod resources;
 pub mod window;
 pub mod shader;
+pub mod pipeline;
+pub mod buffer;
+pub mod pool;
 pub mod mesh;
+pub mod mesh_data;
+pub mod vertex;
+pub mod validation;
+pub mod load_options;
 pub mod renderer;
+pub mod state;
+pub mod interop;
+pub mod camera;
+pub mod transform;
+pub mod bounds;
+pub mod scene;
+pub mod material;
+pub mod light;
+pub mod gizmo;
+pub mod debug_lines;
+pub mod clip;
+pub mod backend;
+pub mod particles;
+pub mod points;
+pub mod texture;
+pub mod compressed;
+pub mod texture3d;
+pub mod framebuffer;
+pub mod id_buffer;
+pub mod overdraw;
+pub mod fullscreen;
+pub mod postprocess;
+pub mod background;
+pub mod capabilities;
+pub mod shadow;
+pub mod text;
+pub mod gpu_timer;
+pub mod benchmark;
+pub mod worker;
+pub mod leaks;
+pub mod regression;
+#[cfg(feature = "hot-reload")]
+pub mod watcher;
+#[cfg(feature = "embedded-shaders")]
+pub mod embedded;
 
 // ============================================================
 // ========================= Imports ==========================
 // ============================================================
 
 pub use context::*;
+pub use resources::*;
 pub use window::*;
 pub use shader::*;
+pub use pipeline::*;
+pub use buffer::*;
+pub use pool::*;
 pub use mesh::*;
+pub use mesh_data::*;
+pub use vertex::*;
+pub use validation::*;
+pub use load_options::*;
 pub use renderer::*;
+pub use state::*;
+pub use interop::*;
+pub use camera::*;
+pub use transform::*;
+pub use bounds::*;
+pub use scene::*;
+pub use material::*;
+pub use light::*;
+pub use gizmo::*;
+pub use debug_lines::*;
+pub use clip::*;
+pub use backend::*;
+pub use particles::*;
+pub use points::*;
+pub use texture::*;
+pub use compressed::*;
+pub use texture3d::*;
+pub use framebuffer::*;
+pub use id_buffer::*;
+pub use overdraw::*;
+pub use fullscreen::*;
+pub use postprocess::*;
+pub use background::*;
+pub use capabilities::*;
+pub use shadow::*;
+pub use text::*;
+pub use gpu_timer::*;
+pub use benchmark::*;
+pub use worker::*;
+pub use regression::*;
+#[cfg(feature = "leak-check")]
+pub use leaks::*;
+#[cfg(feature = "hot-reload")]
+pub use watcher::*;
@@ -0,0 +1,96 @@
+//! Point lights for the `point_lit` shaders.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use crate::{GlContext, Shader};
+use crate::context::gl_count;
+use nalgebra::Point3;
+use tracing;
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Number of point lights `point_lit.frag` declares uniforms for.
+pub const MAX_POINT_LIGHTS: usize = 8;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// A light that shines in every direction from a point and fades with distance.
+/// 
+/// Intensity at distance `d` is `1 / (constant + linear * d + quadratic * d²)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointLight {
+    pub position: Point3<f32>,
+    pub color: [f32; 3],
+    pub constant: f32,
+    pub linear: f32,
+    pub quadratic: f32,
+}
+
+impl PointLight {
+    /// Create a light whose attenuation fades it out over about 50 units.
+    /// 
+    /// # Arguments
+    /// * `position` - World-space position
+    /// * `color` - Linear RGB color, may exceed 1 for brighter lights
+    #[must_use]
+    pub fn new(position: Point3<f32>, color: [f32; 3]) -> Self {
+        Self { position, color, constant: 1.0, linear: 0.09, quadratic: 0.032 }
+    }
+
+    /// Use custom attenuation factors.
+    #[must_use]
+    pub fn with_attenuation(mut self, constant: f32, linear: f32, quadratic: f32) -> Self {
+        self.constant = constant;
+        self.linear = linear;
+        self.quadratic = quadratic;
+        self
+    }
+
+    /// Get the intensity factor at a distance, matching `point_lit.frag`.
+    /// 
+    /// # Arguments
+    /// * `distance` - Distance from the light
+    /// 
+    /// # Returns
+    /// The factor the light's color is multiplied by
+    #[must_use]
+    pub fn attenuation(&self, distance: f32) -> f32 {
+        1.0 / (self.constant + self.linear * distance + self.quadratic * distance * distance).max(f32::EPSILON)
+    }
+}
+
+// ============================================================
+// ==================== Global Functions ======================
+// ============================================================
+
+/// Upload point lights to a bound `point_lit.frag` shader.
+/// 
+/// Sets the `pointLightPositions`, `pointLightColors` and `pointLightAttenuation` arrays
+/// and the active `pointLightCount`. Lights beyond `MAX_POINT_LIGHTS` are ignored with a
+/// warning.
+/// 
+/// # Arguments
+/// * `gl` - OpenGL context
+/// * `shader` - Bound shader declaring the point light uniforms
+/// * `lights` - Lights to apply
+pub fn set_point_lights(gl: &GlContext, shader: &mut Shader, lights: &[PointLight]) {
+    if lights.len() > MAX_POINT_LIGHTS {
+        tracing::warn!("{} point lights given, only the first {} are used", lights.len(), MAX_POINT_LIGHTS);
+    }
+    let lights = &lights[..lights.len().min(MAX_POINT_LIGHTS)];
+    
+    let positions: Vec<[f32; 3]> = lights.iter().map(|light| light.position.coords.into()).collect();
+    let colors: Vec<[f32; 3]> = lights.iter().map(|light| light.color).collect();
+    let attenuation: Vec<[f32; 3]> = lights.iter().map(|light| [light.constant, light.linear, light.quadratic]).collect();
+    
+    shader.set_vec3_array(gl, "pointLightPositions", &positions);
+    shader.set_vec3_array(gl, "pointLightColors", &colors);
+    shader.set_vec3_array(gl, "pointLightAttenuation", &attenuation);
+    shader.set_int(gl, "pointLightCount", gl_count(lights.len()));
+}
\ No newline at end of file
@@ -0,0 +1,111 @@
+//! Post-processing options applied to vertex data as it is loaded.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use crate::{Aabb, InvalidTriangles};
+use nalgebra::{Matrix4, Point3, Vector3};
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// How loaded geometry is repositioned and checked before it becomes a mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MeshLoadOptions {
+    /// Move the bounding box center to the origin.
+    pub center: bool,
+    /// Scale uniformly so the longest bounding box side is 1.
+    pub normalize_scale: bool,
+    /// Whether invalid triangles are only reported or dropped.
+    pub invalid_triangles: InvalidTriangles,
+}
+
+impl MeshLoadOptions {
+    /// Options that both center and normalize, so any model fits a unit box at the origin.
+    #[must_use]
+    pub fn fit_unit_box() -> Self {
+        Self { center: true, normalize_scale: true, ..Self::default() }
+    }
+
+    /// Reposition interleaved vertex data in place.
+    /// 
+    /// Only the first three floats (the position) of each vertex are changed.
+    /// 
+    /// # Arguments
+    /// * `vertices` - Interleaved vertex data starting with x, y, z
+    /// * `floats_per_vertex` - Stride in floats (6 for position + color)
+    /// 
+    /// # Returns
+    /// The transform from the adjusted positions back to the original ones
+    pub fn apply(&self, vertices: &mut [f32], floats_per_vertex: usize) -> Matrix4<f32> {
+        let positions = vertices
+            .chunks_exact(floats_per_vertex)
+            .map(|vertex| Point3::new(vertex[0], vertex[1], vertex[2]));
+        let Some(bounds) = Aabb::from_points(positions) else {
+            return Matrix4::identity();
+        };
+        
+        let offset = if self.center { bounds.center().coords } else { Vector3::zeros() };
+        let extent = (bounds.max - bounds.min).max();
+        let scale = if self.normalize_scale && extent > f32::EPSILON { 1.0 / extent } else { 1.0 };
+        
+        for vertex in vertices.chunks_exact_mut(floats_per_vertex) {
+            for (axis, value) in vertex[..3].iter_mut().enumerate() {
+                *value = (*value - offset[axis]) * scale;
+            }
+        }
+        
+        Matrix4::new_translation(&offset) * Matrix4::new_scaling(1.0 / scale)
+    }
+}
+
+// ============================================================
+// ========================== Tests ===========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two vertices (position + color) spanning (2, 2, 2) to (6, 4, 3).
+    fn vertices() -> Vec<f32> {
+        vec![2.0, 2.0, 2.0, 1.0, 0.5, 0.0, 6.0, 4.0, 3.0, 0.0, 0.5, 1.0]
+    }
+
+    #[test]
+    fn centering_moves_the_bounds_center_to_the_origin() {
+        let mut vertices = vertices();
+        let options = MeshLoadOptions { center: true, ..MeshLoadOptions::default() };
+        let undo = options.apply(&mut vertices, 6);
+        
+        assert_eq!(vertices, vec![-2.0, -1.0, -0.5, 1.0, 0.5, 0.0, 2.0, 1.0, 0.5, 0.0, 0.5, 1.0]);
+        assert_eq!(undo.transform_point(&Point3::new(-2.0, -1.0, -0.5)), Point3::new(2.0, 2.0, 2.0));
+    }
+
+    #[test]
+    fn fitting_a_unit_box_scales_the_longest_side_to_one() {
+        let mut vertices = vertices();
+        let undo = MeshLoadOptions::fit_unit_box().apply(&mut vertices, 6);
+        
+        let positions: Vec<Point3<f32>> = vertices.chunks_exact(6).map(|vertex| Point3::new(vertex[0], vertex[1], vertex[2])).collect();
+        let bounds = Aabb::from_points(positions.iter().copied()).unwrap();
+        assert_eq!(bounds.min, Point3::new(-0.5, -0.25, -0.125));
+        assert_eq!(bounds.max, Point3::new(0.5, 0.25, 0.125));
+        
+        // Colors are untouched and the undo transform restores the original positions
+        assert_eq!(&vertices[3..6], &[1.0, 0.5, 0.0]);
+        assert_eq!(undo.transform_point(&positions[0]), Point3::new(2.0, 2.0, 2.0));
+        assert_eq!(undo.transform_point(&positions[1]), Point3::new(6.0, 4.0, 3.0));
+    }
+
+    #[test]
+    fn default_options_and_empty_data_leave_vertices_alone() {
+        let mut vertices = vertices();
+        assert_eq!(MeshLoadOptions::default().apply(&mut vertices, 6), Matrix4::identity());
+        assert_eq!(vertices, self::vertices());
+        
+        assert_eq!(MeshLoadOptions::fit_unit_box().apply(&mut [], 6), Matrix4::identity());
+    }
+}
\ No newline at end of file
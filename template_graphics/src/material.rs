@@ -0,0 +1,115 @@
+//! Materials: named uniform values and textures applied to a shader together.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::TemplateResult;
+use crate::{GlContext, Shader, Texture};
+use nalgebra::Matrix4;
+use std::collections::HashMap;
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Texture unit `set_normal_map` binds to, leaving unit 0 for the albedo map.
+pub const NORMAL_MAP_UNIT: u32 = 1;
+
+// ============================================================
+// ====================== Types & Enums =======================
+// ============================================================
+
+/// Value of a single uniform stored in a material.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UniformValue {
+    Matrix4(Matrix4<f32>),
+    Vec3([f32; 3]),
+    Vec4([f32; 4]),
+    Float(f32),
+    Int(i32),
+}
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Uniform values and textures that describe how a surface is shaded.
+/// 
+/// The per-frame matrices (`projection`, `view`, `model`) are set by the renderer; a
+/// material holds everything else, so a draw only needs `apply` instead of one setter
+/// call per uniform.
+#[derive(Default)]
+pub struct Material<'a> {
+    uniforms: HashMap<String, UniformValue>,
+    textures: Vec<(String, &'a Texture, u32)>,
+}
+
+impl<'a> Material<'a> {
+    /// Create a material without uniforms or textures.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a uniform value, replacing any previous value with the same name.
+    /// 
+    /// # Arguments
+    /// * `name` - Uniform name in the shader
+    /// * `value` - Value set on every `apply`
+    pub fn set(&mut self, name: &str, value: UniformValue) -> &mut Self {
+        self.uniforms.insert(name.to_string(), value);
+        self
+    }
+
+    /// Bind a texture to a sampler uniform, replacing any previous texture for that sampler.
+    /// 
+    /// # Arguments
+    /// * `name` - Sampler uniform name in the shader
+    /// * `texture` - Texture to bind
+    /// * `unit` - Texture unit to bind it to
+    pub fn set_texture(&mut self, name: &str, texture: &'a Texture, unit: u32) -> &mut Self {
+        self.textures.retain(|(sampler, _, _)| sampler != name);
+        self.textures.push((name.to_string(), texture, unit));
+        self
+    }
+
+    /// Use a tangent-space normal map, bound to the `normalMap` sampler of `normal_mapped.frag`.
+    /// 
+    /// Normal maps hold directions, not colors, so load them as plain (non-sRGB) RGBA.
+    /// 
+    /// # Arguments
+    /// * `texture` - Normal map, bound to `NORMAL_MAP_UNIT`
+    pub fn set_normal_map(&mut self, texture: &'a Texture) -> &mut Self {
+        self.set_texture("normalMap", texture, NORMAL_MAP_UNIT)
+    }
+
+    /// Get a stored uniform value.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<UniformValue> {
+        self.uniforms.get(name).copied()
+    }
+
+    /// Set every stored uniform and bind every texture on a shader.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `shader` - Shader to configure; it must already be bound
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if a texture unit exceeds `MAX_TEXTURE_IMAGE_UNITS`
+    pub fn apply(&self, gl: &GlContext, shader: &mut Shader) -> TemplateResult<()> {
+        for (name, value) in &self.uniforms {
+            match *value {
+                UniformValue::Matrix4(matrix) => shader.set_matrix4(gl, name, &matrix),
+                UniformValue::Vec3(vector) => shader.set_vec3(gl, name, vector),
+                UniformValue::Vec4(vector) => shader.set_vec4(gl, name, vector),
+                UniformValue::Float(value) => shader.set_float(gl, name, value),
+                UniformValue::Int(value) => shader.set_int(gl, name, value),
+            }
+        }
+        
+        let textures: Vec<_> = self.textures.iter().map(|(name, texture, unit)| (name.as_str(), *texture, *unit)).collect();
+        shader.bind_textures(gl, &textures)
+    }
+}
\ No newline at end of file
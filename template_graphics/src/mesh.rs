@@ -4,21 +4,86 @@
 // ========================= Imports ==========================
 // ============================================================
 
-use crate::GlContext;
+use template_core::{TemplateResult, TemplateError};
+use crate::{Aabb, BufferPool, GlContext, InvalidTriangles, MeshData, PackedColorVertex, Shader, TangentVertex, VertexBuffer, VertexLayout};
+use crate::context::{gl_count, gl_size};
 use glow::HasContext;
+use nalgebra::{Matrix4, Point3, Vector3};
+use std::ffi::{c_void, CStr};
+use std::sync::Arc;
 use bytemuck;
 use tracing;
 
+// ============================================================
+// ========================== Types ===========================
+// ============================================================
+
+/// `glPrimitiveRestartIndex`, which glow does not wrap.
+pub(crate) type PrimitiveRestartIndex = unsafe extern "system" fn(index: u32);
+
 // ============================================================
 // ===================== Structs & Impls ======================
 // ============================================================
 
+/// Storage size of the entries in an index buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexType {
+    /// 16-bit indices (up to 65536 vertices, half the memory).
+    U16,
+    /// 32-bit indices.
+    U32,
+}
+
+impl IndexType {
+    /// Get the matching OpenGL type for `draw_elements`.
+    #[must_use]
+    pub fn gl_type(self) -> u32 {
+        match self {
+            IndexType::U16 => glow::UNSIGNED_SHORT,
+            IndexType::U32 => glow::UNSIGNED_INT,
+        }
+    }
+
+    /// Get the size of one index in bytes.
+    #[must_use]
+    pub fn size(self) -> usize {
+        match self {
+            IndexType::U16 => 2,
+            IndexType::U32 => 4,
+        }
+    }
+    /// Get the largest value this type can store, the only restart index OpenGL ES accepts.
+    #[must_use]
+    pub fn max_index(self) -> u32 {
+        match self {
+            IndexType::U16 => u32::from(u16::MAX),
+            IndexType::U32 => u32::MAX,
+        }
+    }
+}
+
+/// Element buffer of an indexed mesh with a CPU-side copy of its indices.
+struct IndexBuffer {
+    ebo: glow::Buffer,
+    index_type: IndexType,
+    indices: Vec<u32>,
+    /// Index that ends the current strip and starts a new one.
+    restart_index: Option<u32>,
+}
+
 /// A renderable mesh with vertex data.
 pub struct Mesh {
     vao: glow::VertexArray,
-    #[allow(dead_code)]
-    vbo: glow::Buffer,
+    vbo: Arc<VertexBuffer>,
     vertex_count: i32,
+    mode: u32,
+    floats_per_vertex: usize,
+    layout: VertexLayout,
+    vertices: Vec<f32>,
+    bounds: Aabb,
+    index_buffer: Option<IndexBuffer>,
+    /// Control points per patch when drawn as `glow::PATCHES`.
+    patch_vertices: Option<u32>,
 }
 
 impl Mesh {
@@ -29,67 +94,1017 @@ impl Mesh {
     /// * `vertices` - Vertex data (6 floats per vertex: x, y, z, r, g, b)
     /// 
     /// # Returns
-    /// A new mesh ready for rendering
+    /// A new mesh ready for rendering. Panics like `with_mode` on failure; call
+    /// `try_with_mode(gl, vertices, glow::TRIANGLES)` to handle oversized meshes.
     pub fn new(gl: &GlContext, vertices: &[f32]) -> Self {
-        tracing::debug!("Creating mesh with {} vertices", vertices.len() / 6);
+        Self::with_mode(gl, vertices, glow::TRIANGLES)
+    }
+
+    /// Create a new mesh drawn as line segments (every two vertices form one line).
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertices` - Vertex data (6 floats per vertex: x, y, z, r, g, b)
+    pub fn new_lines(gl: &GlContext, vertices: &[f32]) -> Self {
+        Self::with_mode(gl, vertices, glow::LINES)
+    }
+
+    /// Create a new point cloud mesh (one point per vertex), e.g. for `PointRenderer`.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertices` - Vertex data (6 floats per vertex: x, y, z, r, g, b)
+    pub fn new_points(gl: &GlContext, vertices: &[f32]) -> Self {
+        Self::with_mode(gl, vertices, glow::POINTS)
+    }
+
+    /// Create a new mesh drawn with the given primitive mode.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertices` - Vertex data (6 floats per vertex: x, y, z, r, g, b)
+    /// * `mode` - OpenGL primitive mode (e.g. `glow::TRIANGLES`, `glow::LINES`)
+    /// 
+    /// # Panics
+    /// If the vertex buffer cannot be created or holds more than `i32::MAX` vertices; use
+    /// `try_with_mode` to handle that instead
+    pub fn with_mode(gl: &GlContext, vertices: &[f32], mode: u32) -> Self {
+        Self::try_with_mode(gl, vertices, mode).unwrap_or_else(|e| panic!("failed to create mesh: {e}"))
+    }
+
+    /// Create a new mesh drawn with the given primitive mode, returning errors instead of panicking.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertices` - Vertex data (6 floats per vertex: x, y, z, r, g, b)
+    /// * `mode` - OpenGL primitive mode (e.g. `glow::TRIANGLES`, `glow::LINES`)
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the vertex buffer cannot be created, `TemplateError::InvalidMesh`
+    /// if it holds more than `i32::MAX` vertices
+    pub fn try_with_mode(gl: &GlContext, vertices: &[f32], mode: u32) -> TemplateResult<Self> {
+        Self::build(gl, vertices, mode, 3)
+    }
+
+    /// Create a mesh drawn as tessellation patches for a `Shader::with_tessellation` program.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertices` - Vertex data (6 floats per vertex: x, y, z, r, g, b)
+    /// * `patch_vertices` - Control points per patch, e.g. 3 for triangle patches
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if tessellation is unsupported, `TemplateError::InvalidMesh` if the
+    /// vertices do not form whole patches
+    pub fn new_patches(gl: &GlContext, vertices: &[f32], patch_vertices: u32) -> TemplateResult<Self> {
+        if !crate::supports_tessellation(gl) {
+            return Err(TemplateError::OpenGL("patch meshes need desktop OpenGL 4.0 tessellation".to_string()));
+        }
+        let vertex_count = vertices.len() / 6;
+        if patch_vertices == 0 || !vertex_count.is_multiple_of(patch_vertices as usize) {
+            return Err(TemplateError::InvalidMesh(format!(
+                "{vertex_count} vertices do not divide into patches of {patch_vertices}"
+            )));
+        }
         
-        unsafe {
-            // Create OpenGL objects
-            let vao = gl.create_vertex_array().unwrap();
-            let vbo = gl.create_buffer().unwrap();
-            
-            // Bind VAO to capture vertex attribute state
-            gl.bind_vertex_array(Some(vao));
-            gl.bind_buffer(glow::ARRAY_BUFFER, Some(vbo));
-            
-            // Upload vertex data to GPU
-            gl.buffer_data_u8_slice(
-                glow::ARRAY_BUFFER,
-                bytemuck::cast_slice(vertices),
-                glow::STATIC_DRAW,
-            );
-            
-            // Configure vertex attributes
-            // Position (location 0): 3 floats starting at offset 0
-            gl.enable_vertex_attrib_array(0);
-            gl.vertex_attrib_pointer_f32(0, 3, glow::FLOAT, false, 24, 0);
+        let mut mesh = Self::build(gl, vertices, glow::PATCHES, 3)?;
+        mesh.patch_vertices = Some(patch_vertices);
+        Ok(mesh)
+    }
+
+    /// Create a triangle mesh whose vertices are shared through an index buffer.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertices` - Vertex data (6 floats per vertex: x, y, z, r, g, b)
+    /// * `indices` - Three vertex indices per triangle
+    /// * `index_type` - Storage size of the uploaded indices
+    /// * `invalid_triangles` - Whether triangles that fail `validate_mesh` are kept or dropped
+    /// 
+    /// Problems found by `validate_mesh` (NaN positions, zero-area triangles) are logged as
+    /// warnings; the triangles are only removed with `InvalidTriangles::Drop`.
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if an index is out of range and invalid triangles are kept or
+    /// does not fit `index_type`, `TemplateError::OpenGL` if a buffer cannot be created
+    pub fn new_indexed(gl: &GlContext, vertices: &[f32], indices: &[u32], index_type: IndexType, invalid_triangles: InvalidTriangles) -> TemplateResult<Self> {
+        let positions: Vec<[f32; 3]> = vertices.chunks_exact(6).map(|vertex| [vertex[0], vertex[1], vertex[2]]).collect();
+        let indices = invalid_triangles.apply(&positions, indices)?;
+        
+        let mut mesh = Self::build(gl, vertices, glow::TRIANGLES, 3)?;
+        if let Err(e) = mesh.attach_indices(gl, &indices, index_type) {
+            mesh.delete(gl);
+            return Err(e);
+        }
+        Ok(mesh)
+    }
+
+    /// Create a triangle strip mesh in which a restart index begins a new strip.
+    /// 
+    /// Lets one draw call cover many strips, such as the rows of a terrain grid or
+    /// separate ribbons, without degenerate joining triangles.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertices` - Vertex data (6 floats per vertex: x, y, z, r, g, b)
+    /// * `indices` - Strip indices, with `restart_index` between strips
+    /// * `index_type` - Storage size of the uploaded indices
+    /// * `restart_index` - Strip separator; must be `index_type.max_index()` on OpenGL ES
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if the restart index does not fit `index_type` or another
+    /// index is out of range, `TemplateError::OpenGL` if the restart index is not the maximum
+    /// on OpenGL ES or the driver cannot set one
+    pub fn new_strip(gl: &GlContext, vertices: &[f32], indices: &[u32], index_type: IndexType, restart_index: u32) -> TemplateResult<Self> {
+        if restart_index > index_type.max_index() {
+            return Err(TemplateError::InvalidMesh(format!("restart index {restart_index} does not fit {index_type:?} indices")));
+        }
+        if gl.version().is_embedded && restart_index != index_type.max_index() {
+            return Err(TemplateError::OpenGL(format!(
+                "OpenGL ES only restarts strips at index {} for {:?} indices",
+                index_type.max_index(), index_type
+            )));
+        }
+        if !gl.version().is_embedded && gl.primitive_restart_index.is_none() {
+            return Err(TemplateError::OpenGL("glPrimitiveRestartIndex is not available".to_string()));
+        }
+        
+        let vertex_count = vertices.len() / 6;
+        if let Some(&index) = indices.iter().find(|&&index| index != restart_index && index as usize >= vertex_count) {
+            return Err(TemplateError::InvalidMesh(format!("index {index} out of range for {vertex_count} vertices")));
+        }
+        
+        let mut mesh = Self::build(gl, vertices, glow::TRIANGLE_STRIP, 3)?;
+        if let Err(e) = mesh.attach_indices(gl, indices, index_type) {
+            mesh.delete(gl);
+            return Err(e);
+        }
+        if let Some(index_buffer) = &mut mesh.index_buffer {
+            index_buffer.restart_index = Some(restart_index);
+        }
+        Ok(mesh)
+    }
+
+    /// Upload vertex data prepared with `MeshData::build`.
+    /// 
+    /// This only issues GL calls; interleaving, validation and bounds were computed when
+    /// the data was built, possibly on another thread.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `data` - Prepared triangle data
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if a buffer cannot be created, `TemplateError::InvalidMesh` if the
+    /// data holds more than `i32::MAX` vertices or indices
+    pub fn from_data(gl: &GlContext, data: &MeshData) -> TemplateResult<Self> {
+        let layout = VertexLayout::position_color(3);
+        let mut mesh = Self::upload(gl, bytemuck::cast_slice(data.vertices()), &layout, glow::TRIANGLES, data.vertices().to_vec(), 6, data.bounds())?;
+        if let Some(indices) = data.indices() {
+            let index_type = if data.vertex_count() <= usize::from(u16::MAX) + 1 { IndexType::U16 } else { IndexType::U32 };
+            if let Err(e) = mesh.attach_indices(gl, indices, index_type) {
+                mesh.delete(gl);
+                return Err(e);
+            }
+        }
+        Ok(mesh)
+    }
+
+    /// Create a new mesh from vertex data with positions and RGBA colors.
+    /// 
+    /// Use with `basic_rgba.vert` + `basic_rgba.frag` and blending for per-vertex transparency.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertices` - Vertex data (7 floats per vertex: x, y, z, r, g, b, a)
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the vertex buffer cannot be created, `TemplateError::InvalidMesh`
+    /// if it holds more than `i32::MAX` vertices
+    pub fn new_rgba(gl: &GlContext, vertices: &[f32]) -> TemplateResult<Self> {
+        Self::build(gl, vertices, glow::TRIANGLES, 4)
+    }
+
+    /// Create a new mesh from vertices with packed 8-bit RGBA colors.
+    /// 
+    /// Colors are uploaded as normalized unsigned bytes, so no float conversion is needed
+    /// and each color takes 4 bytes instead of 16. Use with `basic_rgba.vert` + `basic_rgba.frag`.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertices` - Vertices with float positions and `u8` RGBA colors
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the vertex buffer cannot be created, `TemplateError::InvalidMesh`
+    /// if it holds more than `i32::MAX` vertices
+    pub fn new_packed_rgba(gl: &GlContext, vertices: &[PackedColorVertex]) -> TemplateResult<Self> {
+        Self::with_layout(gl, bytemuck::cast_slice(vertices), &VertexLayout::position_color_u8(), glow::TRIANGLES)
+    }
+
+    /// Create an indexed mesh for normal mapping, computing tangents from the texture coordinates.
+    /// 
+    /// Draw it with `normal_mapped.vert` + `normal_mapped.frag` and a material with an albedo
+    /// and a normal map (see `Material::set_normal_map`).
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `positions` - Vertex positions
+    /// * `normals` - Unit vertex normals
+    /// * `uvs` - Texture coordinates
+    /// * `indices` - Three vertex indices per triangle
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if the attribute arrays differ in length or an index is out of range
+    pub fn new_normal_mapped(gl: &GlContext, positions: &[[f32; 3]], normals: &[[f32; 3]], uvs: &[[f32; 2]], indices: &[u32]) -> TemplateResult<Self> {
+        if normals.len() != positions.len() || uvs.len() != positions.len() {
+            return Err(TemplateError::InvalidMesh(format!(
+                "{} positions, {} normals and {} texture coordinates do not match",
+                positions.len(), normals.len(), uvs.len()
+            )));
+        }
+        if let Some(&index) = indices.iter().find(|&&index| index as usize >= positions.len()) {
+            return Err(TemplateError::InvalidMesh(format!("index {} out of range for {} vertices", index, positions.len())));
+        }
+        
+        let tangents = Self::compute_tangents(positions, normals, uvs, indices);
+        let vertices: Vec<TangentVertex> = (0..positions.len())
+            .map(|i| TangentVertex { position: positions[i], normal: normals[i], uv: uvs[i], tangent: tangents[i] })
+            .collect();
+        
+        let mut mesh = Self::with_layout(gl, bytemuck::cast_slice(&vertices), &VertexLayout::position_normal_uv_tangent(), glow::TRIANGLES)?;
+        if let Err(e) = mesh.attach_indices(gl, indices, IndexType::U32) {
+            mesh.delete(gl);
+            return Err(e);
+        }
+        Ok(mesh)
+    }
+
+    /// Compute per-vertex tangents for normal mapping from triangle positions and texture coordinates.
+    /// 
+    /// Each triangle's tangent (the direction of increasing u) is accumulated on its
+    /// vertices, then made perpendicular to the vertex normal. The `w` component is the
+    /// handedness: -1 where the UVs are mirrored, so the shader flips the bitangent.
+    /// 
+    /// # Arguments
+    /// * `positions` - Vertex positions
+    /// * `normals` - Unit vertex normals
+    /// * `uvs` - Texture coordinates
+    /// * `indices` - Three vertex indices per triangle, all in range
+    /// 
+    /// # Returns
+    /// One unit tangent plus handedness per vertex
+    #[must_use]
+    pub fn compute_tangents(positions: &[[f32; 3]], normals: &[[f32; 3]], uvs: &[[f32; 2]], indices: &[u32]) -> Vec<[f32; 4]> {
+        let mut tangents = vec![Vector3::zeros(); positions.len()];
+        let mut bitangents = vec![Vector3::zeros(); positions.len()];
+        
+        for triangle in indices.chunks_exact(3) {
+            let [a, b, c] = [triangle[0] as usize, triangle[1] as usize, triangle[2] as usize];
+            let edge1 = Vector3::from(positions[b]) - Vector3::from(positions[a]);
+            let edge2 = Vector3::from(positions[c]) - Vector3::from(positions[a]);
+            let (du1, dv1) = (uvs[b][0] - uvs[a][0], uvs[b][1] - uvs[a][1]);
+            let (du2, dv2) = (uvs[c][0] - uvs[a][0], uvs[c][1] - uvs[a][1]);
             
-            // Color (location 1): 3 floats starting at offset 12 (3 * 4 bytes)
-            gl.enable_vertex_attrib_array(1);
-            gl.vertex_attrib_pointer_f32(1, 3, glow::FLOAT, false, 24, 12);
+            let determinant = du1 * dv2 - du2 * dv1;
+            if determinant.abs() < f32::EPSILON {
+                // Degenerate UVs give no direction; the fallback below picks one
+                continue;
+            }
+            let tangent = (edge1 * dv2 - edge2 * dv1) / determinant;
+            let bitangent = (edge2 * du1 - edge1 * du2) / determinant;
+            for index in [a, b, c] {
+                tangents[index] += tangent;
+                bitangents[index] += bitangent;
+            }
+        }
+        
+        normals
+            .iter()
+            .zip(tangents.iter().zip(&bitangents))
+            .map(|(normal, (tangent, bitangent))| {
+                let normal = Vector3::from(*normal);
+                // Gram-Schmidt: remove the part of the tangent along the normal
+                let tangent = (tangent - normal * normal.dot(tangent))
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(|| normal.cross(&Vector3::x()).try_normalize(f32::EPSILON).unwrap_or_else(Vector3::z));
+                let handedness = if normal.cross(&tangent).dot(bitangent) < 0.0 { -1.0 } else { 1.0 };
+                [tangent.x, tangent.y, tangent.z, handedness]
+            })
+            .collect()
+    }
+
+    /// Create a new mesh from raw interleaved bytes described by a vertex layout.
+    /// 
+    /// The first attribute must be the position as 3 floats at offset 0. Only positions are
+    /// kept CPU-side, so `vertices` returns 3 floats per vertex for these meshes.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `data` - Interleaved vertex data, `layout.stride()` bytes per vertex
+    /// * `layout` - Attribute layout of each vertex
+    /// * `mode` - OpenGL primitive mode (e.g. `glow::TRIANGLES`, `glow::LINES`)
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if the layout does not start with a 3-float position or
+    /// `data` is not a whole number of vertices or holds more than `i32::MAX` of them,
+    /// `TemplateError::OpenGL` if the vertex buffer cannot be created
+    pub fn with_layout(gl: &GlContext, data: &[u8], layout: &VertexLayout, mode: u32) -> TemplateResult<Self> {
+        check_layout(data, layout)?;
+        let positions = Self::extract_positions(data, layout.stride());
+        let bounds = Self::calculate_bounds(&positions, 3);
+        Self::upload(gl, data, layout, mode, positions, 3, bounds)
+    }
+
+    /// Combine several meshes into one indexed mesh so static geometry draws in a single call.
+    /// 
+    /// Each source's positions are transformed by its model matrix on the CPU; colors are
+    /// copied unchanged. Vertices are concatenated and each source's indices (or its vertices
+    /// in sequence, if it has none) are offset by the vertices merged before it. All sources must share a primitive mode and RGB/RGBA vertex format.
+    /// Strips, fans, line loops and restart-indexed meshes cannot be merged, since
+    /// concatenating them would join unrelated primitives.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `sources` - Meshes to merge, each with the model matrix it would be drawn with
+    /// 
+    /// # Returns
+    /// The merged mesh
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if the sources are empty or incompatible
+    pub fn merge(gl: &GlContext, sources: &[(&Mesh, Matrix4<f32>)]) -> TemplateResult<Self> {
+        let Some((first, _)) = sources.first() else {
+            return Err(TemplateError::InvalidMesh("cannot merge an empty list of meshes".to_string()));
+        };
+        
+        let floats_per_vertex = first.floats_per_vertex;
+        if floats_per_vertex == 3 {
+            return Err(TemplateError::InvalidMesh("meshes with custom vertex layouts cannot be merged".to_string()));
+        }
+        
+        let mut merged = Vec::new();
+        let mut indices = Vec::new();
+        for (mesh, model) in sources {
+            if mesh.floats_per_vertex != floats_per_vertex || mesh.mode != first.mode || mesh.patch_vertices != first.patch_vertices {
+                return Err(TemplateError::InvalidMesh(format!(
+                    "cannot merge meshes with different layouts ({} vs {} floats per vertex, mode 0x{:X} vs 0x{:X})",
+                    floats_per_vertex, mesh.floats_per_vertex, first.mode, mesh.mode
+                )));
+            }
+            if mesh.restart_index().is_some() || matches!(mesh.mode, glow::TRIANGLE_STRIP | glow::TRIANGLE_FAN | glow::LINE_STRIP | glow::LINE_LOOP) {
+                return Err(TemplateError::InvalidMesh(format!(
+                    "cannot merge connected primitives (mode 0x{:X}); only independent triangles, lines, points or patches concatenate",
+                    mesh.mode
+                )));
+            }
             
-            // Unbind VAO to prevent accidental modification
+            let base_vertex = u32::try_from(merged.len() / floats_per_vertex)
+                .map_err(|_| TemplateError::InvalidMesh("merged mesh has more than u32::MAX vertices".to_string()))?;
+            for index in mesh.draw_order() {
+                let index = u32::try_from(index).ok().and_then(|index| base_vertex.checked_add(index))
+                    .ok_or_else(|| TemplateError::InvalidMesh("merged mesh has more than u32::MAX vertices".to_string()))?;
+                indices.push(index);
+            }
+            for vertex in mesh.vertices.chunks_exact(floats_per_vertex) {
+                let position = model.transform_point(&Point3::new(vertex[0], vertex[1], vertex[2]));
+                merged.extend_from_slice(&[position.x, position.y, position.z]);
+                merged.extend_from_slice(&vertex[3..]);
+            }
+        }
+        
+        tracing::debug!("Merged {} meshes", sources.len());
+        let index_type = if merged.len() / floats_per_vertex <= usize::from(u16::MAX) + 1 { IndexType::U16 } else { IndexType::U32 };
+        let mut mesh = if first.mode == glow::TRIANGLES && floats_per_vertex == 6 {
+            Self::new_indexed(gl, &merged, &indices, index_type, InvalidTriangles::Report)?
+        } else {
+            // new_indexed only builds RGB triangles; other modes get the same index buffer directly
+            let color_size = if floats_per_vertex == 7 { 4 } else { 3 };
+            let mut mesh = Self::build(gl, &merged, first.mode, color_size)?;
+            if let Err(e) = mesh.attach_indices(gl, &indices, index_type) {
+                mesh.delete(gl);
+                return Err(e);
+            }
+            mesh
+        };
+        mesh.patch_vertices = first.patch_vertices;
+        Ok(mesh)
+    }
+
+    fn build(gl: &GlContext, vertices: &[f32], mode: u32, color_size: i32) -> TemplateResult<Self> {
+        let layout = VertexLayout::position_color(color_size);
+        let floats_per_vertex = layout.stride() / std::mem::size_of::<f32>();
+        let bounds = Self::calculate_bounds(vertices, floats_per_vertex);
+        Self::upload(gl, bytemuck::cast_slice(vertices), &layout, mode, vertices.to_vec(), floats_per_vertex, bounds)
+    }
+
+    fn upload(gl: &GlContext, data: &[u8], layout: &VertexLayout, mode: u32, vertices: Vec<f32>, floats_per_vertex: usize, bounds: Aabb) -> TemplateResult<Self> {
+        // Check before uploading so an oversized mesh creates no GL objects
+        let vertex_count = checked_vertex_count(data.len() / layout.stride())?;
+        tracing::debug!("Creating mesh with {} vertices", vertex_count);
+        
+        // Upload vertex data to GPU
+        let vbo = VertexBuffer::new(gl, data)?;
+        Self::with_buffer(gl, vbo, layout, mode, vertices, floats_per_vertex, bounds)
+    }
+
+    /// Upload this mesh again from its CPU-side copy, e.g. into a context recreated after loss.
+    /// 
+    /// Keeps the layout, primitive mode, indices, restart index and patch size. The old
+    /// mesh's GL objects are left alone, since they belong to the context it was created in.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context to create the new mesh in
+    /// 
+    /// # Returns
+    /// The new mesh
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if only the positions of this mesh are kept CPU-side (see `with_layout`),
+    /// `TemplateError::OpenGL` if a GL object cannot be created
+    pub fn recreate(&self, gl: &GlContext) -> TemplateResult<Self> {
+        if self.layout.stride() != self.floats_per_vertex * std::mem::size_of::<f32>() {
+            return Err(TemplateError::InvalidMesh("only the positions of this mesh are kept CPU-side, so it cannot be recreated".to_string()));
+        }
+        
+        let mut mesh = Self::upload(gl, bytemuck::cast_slice(&self.vertices), &self.layout, self.mode, self.vertices.clone(), self.floats_per_vertex, self.bounds)?;
+        mesh.patch_vertices = self.patch_vertices;
+        if let Some(index_buffer) = &self.index_buffer {
+            if let Err(e) = mesh.attach_indices(gl, &index_buffer.indices, index_buffer.index_type) {
+                mesh.delete(gl);
+                return Err(e);
+            }
+            if let Some(new_index_buffer) = &mut mesh.index_buffer {
+                new_index_buffer.restart_index = index_buffer.restart_index;
+            }
+        }
+        Ok(mesh)
+    }
+
+    /// Create a VAO reading an existing vertex buffer.
+    fn with_buffer(gl: &GlContext, vbo: Arc<VertexBuffer>, layout: &VertexLayout, mode: u32, vertices: Vec<f32>, floats_per_vertex: usize, bounds: Aabb) -> TemplateResult<Self> {
+        let vertex_count = checked_vertex_count(vbo.size() / layout.stride())?;
+        // Create OpenGL objects
+        let vao = unsafe { gl.create_vertex_array() }.map_err(TemplateError::OpenGL)?;
+        crate::leaks::track_created();
+        attach_vertex_buffer(gl, vao, &vbo, layout);
+        
+        tracing::debug!("Mesh created successfully");
+        
+        Ok(Self {
+            vao,
+            vbo,
+            vertex_count,
+            mode,
+            floats_per_vertex,
+            layout: layout.clone(),
+            vertices,
+            bounds,
+            index_buffer: None,
+            patch_vertices: None,
+        })
+    }
+
+    /// Create a triangle mesh whose vertex buffer and VAO are taken from a pool.
+    /// 
+    /// Meant for geometry rebuilt often; retire the mesh with `recycle` instead of `delete`
+    /// so the next mesh of the same byte size reuses its objects.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `pool` - Pool to take the buffer and VAO from
+    /// * `vertices` - Vertex data (6 floats per vertex: x, y, z, r, g, b)
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if the vertex count exceeds `i32::MAX`, `TemplateError::OpenGL`
+    /// if a GL object cannot be created
+    pub fn new_pooled(gl: &GlContext, pool: &mut BufferPool, vertices: &[f32]) -> TemplateResult<Self> {
+        let layout = VertexLayout::position_color(3);
+        let vertex_count = checked_vertex_count(vertices.len() / 6)?;
+        tracing::debug!("Creating pooled mesh with {} vertices", vertex_count);
+        
+        let vbo = VertexBuffer::from_pool(gl, pool, bytemuck::cast_slice(vertices))?;
+        let vao = match pool.acquire_vertex_array(gl) {
+            Ok(vao) => vao,
+            Err(e) => {
+                VertexBuffer::recycle(vbo, gl, pool);
+                return Err(e);
+            }
+        };
+        attach_vertex_buffer(gl, vao, &vbo, &layout);
+        
+        Ok(Self {
+            vao,
+            vbo,
+            vertex_count,
+            mode: glow::TRIANGLES,
+            floats_per_vertex: 6,
+            layout,
+            vertices: vertices.to_vec(),
+            bounds: Self::calculate_bounds(vertices, 6),
+            index_buffer: None,
+            patch_vertices: None,
+        })
+    }
+
+    /// Create a mesh that reads another mesh's vertex buffer through its own VAO.
+    /// 
+    /// No vertex data is copied on the GPU; only the attribute setup differs, e.g. to feed
+    /// a shadow pass shader with other locations. The buffer stays alive until both meshes
+    /// are deleted. Index data, if any, is uploaded again for the new mesh. Note that
+    /// `update_colors` on either mesh changes what both draw.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `source` - Mesh whose vertex buffer is shared
+    /// * `layout` - Attribute setup of the new VAO; must have the same stride as the source's
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if the layout stride does not match the shared data
+    pub fn share_vertices(gl: &GlContext, source: &Mesh, layout: &VertexLayout) -> TemplateResult<Self> {
+        if layout.stride() != source.layout.stride() {
+            return Err(TemplateError::InvalidMesh(format!(
+                "layout stride {} does not match the shared buffer's stride {}",
+                layout.stride(), source.layout.stride()
+            )));
+        }
+        
+        let mut mesh = Self::with_buffer(
+            gl,
+            Arc::clone(&source.vbo),
+            layout,
+            source.mode,
+            source.vertices.clone(),
+            source.floats_per_vertex,
+            source.bounds,
+        )?;
+        mesh.patch_vertices = source.patch_vertices;
+        if let Some(index_buffer) = &source.index_buffer {
+            if let Err(e) = mesh.attach_indices(gl, &index_buffer.indices, index_buffer.index_type) {
+                mesh.delete(gl);
+                return Err(e);
+            }
+        }
+        Ok(mesh)
+    }
+
+    /// Get the vertex buffer, e.g. to check how many meshes share it with `Arc::strong_count`.
+    #[must_use]
+    pub fn vertex_buffer(&self) -> &Arc<VertexBuffer> {
+        &self.vbo
+    }
+
+    /// Upload an index buffer and record it in the mesh's VAO.
+    fn attach_indices(&mut self, gl: &GlContext, indices: &[u32], index_type: IndexType) -> TemplateResult<()> {
+        // Indexed draws pass the index count as an i32 too
+        checked_vertex_count(indices.len())?;
+        let bytes = match index_type {
+            IndexType::U16 => {
+                let narrow = indices
+                    .iter()
+                    .map(|&index| u16::try_from(index))
+                    .collect::<Result<Vec<u16>, _>>()
+                    .map_err(|_| TemplateError::InvalidMesh(format!("{} vertices need 32-bit indices", self.vertex_count)))?;
+                bytemuck::cast_slice(&narrow).to_vec()
+            }
+            IndexType::U32 => bytemuck::cast_slice(indices).to_vec(),
+        };
+        
+        unsafe {
+            let ebo = gl.create_buffer().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            // The element buffer binding is recorded in the VAO
+            gl.bind_vertex_array(Some(self.vao));
+            gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(ebo));
+            gl.buffer_data_u8_slice(glow::ELEMENT_ARRAY_BUFFER, &bytes, glow::STATIC_DRAW);
             gl.bind_vertex_array(None);
             
-            tracing::debug!("Mesh created successfully");
-            
-            Self {
-                vao,
-                vbo, // Kept alive for RAII cleanup
-                vertex_count: Self::calculate_vertex_count(vertices),
-            }
+            self.index_buffer = Some(IndexBuffer { ebo, index_type, indices: indices.to_vec(), restart_index: None });
         }
+        
+        tracing::debug!("Mesh uses {} {:?} indices", indices.len(), index_type);
+        Ok(())
+    }
+
+    /// Point the mesh's attributes at the locations a shader assigns to the given input names.
+    /// 
+    /// Meshes assume `layout (location = N)` qualifiers matching their vertex layout. Call
+    /// this once per shader (and again after reloading it) when the shader omits them or
+    /// orders its inputs differently. The VAO keeps the new locations until the next call.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `shader` - Shader the mesh will be drawn with
+    /// * `names` - Vertex input name of each attribute, e.g. `POSITION_COLOR_NAMES`
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if a name is missing from the shader; the mesh is left unchanged
+    pub fn bind_attributes(&mut self, gl: &GlContext, shader: &Shader, names: &[&str]) -> TemplateResult<()> {
+        let layout = self.layout.resolve_locations(gl, shader, names)?;
+        
+        unsafe {
+            gl.bind_vertex_array(Some(self.vao));
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo.handle()));
+            self.layout.disable(gl);
+            layout.apply(gl);
+            gl.bind_vertex_array(None);
+            gl.bind_buffer(glow::ARRAY_BUFFER, None);
+        }
+        
+        tracing::debug!("Mesh attributes bound to {:?}", layout.attributes().iter().map(|attribute| attribute.location).collect::<Vec<_>>());
+        self.layout = layout;
+        Ok(())
+    }
+
+    /// Get the vertex layout, with the locations the attributes are currently bound to.
+    #[must_use]
+    pub fn layout(&self) -> &VertexLayout {
+        &self.layout
     }
 
-    /// Render the mesh using triangles.
+    /// Render the mesh using its primitive mode.
     /// 
     /// # Arguments
     /// * `gl` - OpenGL context for rendering
     pub fn draw(&self, gl: &GlContext) {
+        let _span = tracing::trace_span!("mesh_draw").entered();
+        crate::assert_gl_thread();
         tracing::trace!("Drawing mesh with {} vertices", self.vertex_count);
         
         unsafe {
             // Bind VAO containing vertex attribute configuration
             gl.bind_vertex_array(Some(self.vao));
+            if let Some(patch_vertices) = self.patch_vertices {
+                gl.patch_parameter_i32(glow::PATCH_VERTICES, gl_size(patch_vertices));
+            }
             
             // Issue draw call
-            gl.draw_arrays(glow::TRIANGLES, 0, self.vertex_count);
+            match &self.index_buffer {
+                Some(index_buffer) => {
+                    // Other indexed meshes skip the state changes
+                    let restart = index_buffer.restart_index.is_some();
+                    if restart {
+                        set_primitive_restart(gl, index_buffer.restart_index);
+                    }
+                    gl.draw_elements(self.mode, gl_count(index_buffer.indices.len()), index_buffer.index_type.gl_type(), 0);
+                    if restart {
+                        set_primitive_restart(gl, None);
+                    }
+                }
+                None => gl.draw_arrays(self.mode, 0, self.vertex_count),
+            }
             
             // Clean up binding
             gl.bind_vertex_array(None);
         }
     }
 
+    /// Render a contiguous part of the mesh, e.g. one material group of a multi-material model.
+    /// 
+    /// For indexed meshes the range selects indices; otherwise it selects vertices.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context for rendering
+    /// * `start` - First index (or vertex) to draw
+    /// * `count` - Number of indices (or vertices) to draw
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if the range extends past the end of the mesh
+    pub fn draw_range(&self, gl: &GlContext, start: i32, count: i32) -> TemplateResult<()> {
+        let _span = tracing::trace_span!("mesh_draw_range").entered();
+        crate::assert_gl_thread();
+        
+        let total = self.index_buffer.as_ref().map_or(self.vertex_count, |index_buffer| gl_count(index_buffer.indices.len()));
+        if start < 0 || count < 0 || start.checked_add(count).as_ref().is_none_or(|end| *end > total) {
+            return Err(TemplateError::InvalidMesh(format!(
+                "draw range starting at {start} with {count} elements is outside the mesh's {total} elements"
+            )));
+        }
+        let offset = match &self.index_buffer {
+            Some(index_buffer) => index_byte_offset(start, index_buffer.index_type)?,
+            None => 0,
+        };
+        
+        unsafe {
+            gl.bind_vertex_array(Some(self.vao));
+            if let Some(patch_vertices) = self.patch_vertices {
+                gl.patch_parameter_i32(glow::PATCH_VERTICES, gl_size(patch_vertices));
+            }
+            match &self.index_buffer {
+                Some(index_buffer) => {
+                    // Other indexed meshes skip the state changes
+                    let restart = index_buffer.restart_index.is_some();
+                    if restart {
+                        set_primitive_restart(gl, index_buffer.restart_index);
+                    }
+                    gl.draw_elements(self.mode, count, index_buffer.index_type.gl_type(), offset);
+                    if restart {
+                        set_primitive_restart(gl, None);
+                    }
+                }
+                None => gl.draw_arrays(self.mode, start, count),
+            }
+            gl.bind_vertex_array(None);
+        }
+        Ok(())
+    }
+
+    /// Render a range of indices with a constant added to every index before fetching vertices.
+    /// 
+    /// Lets many sub-meshes share one vertex and index buffer: each sub-mesh's indices stay
+    /// relative to its own first vertex, and `base_vertex` says where that vertex sits in the
+    /// combined buffer. Restart indices are compared before the offset is added.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context for rendering
+    /// * `start` - First index to draw
+    /// * `count` - Number of indices to draw
+    /// * `base_vertex` - Offset added to each index
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the context lacks base-vertex draws, `TemplateError::InvalidMesh`
+    /// if the mesh is not indexed, the range extends past the index buffer, or an offset index
+    /// falls outside the vertex buffer
+    pub fn draw_range_base_vertex(&self, gl: &GlContext, start: i32, count: i32, base_vertex: i32) -> TemplateResult<()> {
+        let _span = tracing::trace_span!("mesh_draw_range_base_vertex").entered();
+        crate::assert_gl_thread();
+        
+        let Some(index_buffer) = &self.index_buffer else {
+            return Err(TemplateError::InvalidMesh("base-vertex draws need an indexed mesh".to_string()));
+        };
+        if !crate::supports_base_vertex(gl) {
+            return Err(TemplateError::OpenGL("glDrawElementsBaseVertex needs OpenGL 3.2 or OpenGL ES 3.2".to_string()));
+        }
+        let total = gl_count(index_buffer.indices.len());
+        let end = start.checked_add(count).filter(|&end| start >= 0 && count >= 0 && end <= total).ok_or_else(|| {
+            TemplateError::InvalidMesh(format!(
+                "draw range starting at {start} with {count} indices is outside the mesh's {total} indices"
+            ))
+        })?;
+        let offset = index_byte_offset(start, index_buffer.index_type)?;
+        
+        let range = &index_buffer.indices[start.unsigned_abs() as usize..end.unsigned_abs() as usize];
+        let highest = range
+            .iter()
+            .filter(|&&index| Some(index) != index_buffer.restart_index)
+            .max()
+            .map_or(0, |&index| i64::from(index));
+        if base_vertex < 0 || highest + i64::from(base_vertex) >= i64::from(self.vertex_count) {
+            return Err(TemplateError::InvalidMesh(format!(
+                "base vertex {} puts index {} outside the mesh's {} vertices",
+                base_vertex, highest + i64::from(base_vertex), self.vertex_count
+            )));
+        }
+        
+        unsafe {
+            gl.bind_vertex_array(Some(self.vao));
+            if let Some(patch_vertices) = self.patch_vertices {
+                gl.patch_parameter_i32(glow::PATCH_VERTICES, gl_size(patch_vertices));
+            }
+            let restart = index_buffer.restart_index.is_some();
+            if restart {
+                set_primitive_restart(gl, index_buffer.restart_index);
+            }
+            gl.draw_elements_base_vertex(self.mode, count, index_buffer.index_type.gl_type(), offset, base_vertex);
+            if restart {
+                set_primitive_restart(gl, None);
+            }
+            gl.bind_vertex_array(None);
+        }
+        Ok(())
+    }
+
+    /// Overwrite the colors of a run of vertices in place, leaving positions untouched.
+    /// 
+    /// Only the color components of each interleaved vertex are uploaded, one
+    /// `buffer_sub_data` call per vertex. The CPU-side copy is updated to match.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `offset` - Index of the first vertex to recolor
+    /// * `colors` - New colors, 3 floats per vertex for RGB meshes or 4 for RGBA meshes
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if the mesh has no float colors or the range is out of bounds
+    pub fn update_colors(&mut self, gl: &GlContext, offset: usize, colors: &[f32]) -> TemplateResult<()> {
+        let color_size = self.floats_per_vertex - 3;
+        if color_size == 0 {
+            return Err(TemplateError::InvalidMesh("mesh has no float color attribute to update".to_string()));
+        }
+        if !colors.len().is_multiple_of(color_size) {
+            return Err(TemplateError::InvalidMesh(format!(
+                "color data length {} is not a multiple of {} components",
+                colors.len(), color_size
+            )));
+        }
+        
+        let count = colors.len() / color_size;
+        if offset.checked_add(count).is_none_or(|end| end > self.vbo.size() / self.layout.stride()) {
+            return Err(TemplateError::InvalidMesh(format!(
+                "cannot recolor {} vertices at offset {} of a mesh with {} vertices",
+                count, offset, self.vertex_count
+            )));
+        }
+        
+        unsafe {
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo.handle()));
+            for (index, color) in colors.chunks_exact(color_size).enumerate() {
+                // Colors follow the 3 position floats of each vertex
+                let start = (offset + index) * self.floats_per_vertex + 3;
+                self.vertices[start..start + color_size].copy_from_slice(color);
+                let byte_offset = gl_count(start * std::mem::size_of::<f32>());
+                gl.buffer_sub_data_u8_slice(glow::ARRAY_BUFFER, byte_offset, bytemuck::cast_slice(color));
+            }
+            gl.bind_buffer(glow::ARRAY_BUFFER, None);
+        }
+        Ok(())
+    }
+
+    /// Replace all vertex data with new data of the same size, for geometry rewritten every frame.
+    /// 
+    /// The buffer is orphaned first: `buffer_data` with no data hands the driver fresh storage,
+    /// so the upload does not wait for draws still reading last frame's vertices.
+    /// 
+    /// Choose the update path by how much changes and how often:
+    /// * `stream_vertices` - most or all vertices, every frame (particles, debug lines)
+    /// * `update_colors` - a few vertices now and then; in-place `buffer_sub_data`, which can
+    ///   stall while the GPU still reads the buffer
+    /// * creating a new mesh - the vertex count changes
+    /// 
+    /// Meshes sharing the buffer (see `share_vertices`) draw the new data too.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `data` - Interleaved vertices in the mesh's layout (`f32`s or vertex structs)
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if the data size differs from the buffer's
+    pub fn stream_vertices<T: bytemuck::Pod>(&mut self, gl: &GlContext, data: &[T]) -> TemplateResult<()> {
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+        if bytes.len() != self.vbo.size() {
+            return Err(TemplateError::InvalidMesh(format!(
+                "streamed {} bytes into a {}-byte vertex buffer; create a new mesh to change the vertex count",
+                bytes.len(), self.vbo.size()
+            )));
+        }
+        
+        unsafe {
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo.handle()));
+            gl.buffer_data_size(glow::ARRAY_BUFFER, gl_count(bytes.len()), glow::STREAM_DRAW);
+            gl.buffer_sub_data_u8_slice(glow::ARRAY_BUFFER, 0, bytes);
+            gl.bind_buffer(glow::ARRAY_BUFFER, None);
+        }
+        
+        // Float meshes keep every float CPU-side, layout meshes only positions
+        self.vertices = if self.floats_per_vertex * std::mem::size_of::<f32>() == self.layout.stride() {
+            bytes.chunks_exact(4).map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]])).collect()
+        } else {
+            Self::extract_positions(bytes, self.layout.stride())
+        };
+        self.bounds = Self::calculate_bounds(&self.vertices, self.floats_per_vertex);
+        Ok(())
+    }
+
+    /// Read the mesh's vertex data back from the GPU.
+    /// 
+    /// Useful to inspect or save meshes whose data was written on the GPU (e.g. by
+    /// `stream_vertices` or transform feedback) rather than kept from construction. This
+    /// stalls the pipeline, so it is meant for debugging, not per-frame use. The result is
+    /// `layout().stride()` bytes per vertex reinterpreted as floats; packed attributes such as
+    /// `u8` colors come back as raw bit patterns.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// 
+    /// # Returns
+    /// The interleaved vertex data
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the buffer cannot be read
+    pub fn read_vertices(&self, gl: &GlContext) -> TemplateResult<Vec<f32>> {
+        let data = self.vbo.read(gl, 0, self.vbo.size())?;
+        Ok(data.chunks_exact(4).map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]])).collect())
+    }
+
+    /// Read the leading 3 position floats of each interleaved vertex.
+    fn extract_positions(data: &[u8], stride: usize) -> Vec<f32> {
+        data
+            .chunks_exact(stride)
+            .flat_map(|vertex| {
+                (0..3).map(move |i| f32::from_ne_bytes([vertex[i * 4], vertex[i * 4 + 1], vertex[i * 4 + 2], vertex[i * 4 + 3]]))
+            })
+            .collect()
+    }
+
+    /// Release the GPU buffers owned by this mesh.
+    /// 
+    /// A vertex buffer shared with other meshes is only deleted by the last of them.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context the mesh was created with
+    pub fn delete(self, gl: &GlContext) {
+        VertexBuffer::release(self.vbo, gl);
+        unsafe {
+            gl.delete_vertex_array(self.vao);
+            crate::leaks::track_deleted();
+            if let Some(index_buffer) = self.index_buffer {
+                gl.delete_buffer(index_buffer.ebo);
+                crate::leaks::track_deleted();
+            }
+        }
+    }
+
+    /// Return the mesh's vertex buffer and VAO to a pool instead of deleting them.
+    /// 
+    /// The VAO is always pooled. The vertex buffer only is if it came from `new_pooled`;
+    /// other meshes' static buffers are deleted, as is any index buffer. A vertex buffer
+    /// still shared with another mesh stays with that mesh.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `pool` - Pool that takes the objects
+    pub fn recycle(self, gl: &GlContext, pool: &mut BufferPool) {
+        VertexBuffer::recycle(self.vbo, gl, pool);
+        unsafe {
+            // Clear the VAO's state so the next mesh starts from a blank one
+            gl.bind_vertex_array(Some(self.vao));
+            self.layout.disable(gl);
+            gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, None);
+            gl.bind_vertex_array(None);
+            if let Some(index_buffer) = self.index_buffer {
+                gl.delete_buffer(index_buffer.ebo);
+                crate::leaks::track_deleted();
+            }
+        }
+        pool.release_vertex_array(self.vao);
+    }
+
+    /// Get the strip restart index, if the mesh was created with `new_strip`.
+    #[must_use]
+    pub fn restart_index(&self) -> Option<u32> {
+        self.index_buffer.as_ref().and_then(|index_buffer| index_buffer.restart_index)
+    }
+
+    /// Get the vertex indices in draw order: the index buffer, or every vertex in sequence.
+    #[must_use]
+    pub fn draw_order(&self) -> Vec<usize> {
+        match &self.index_buffer {
+            Some(index_buffer) => index_buffer.indices.iter().map(|&index| index as usize).collect(),
+            None => (0..self.vbo.size() / self.layout.stride()).collect(),
+        }
+    }
+
+    /// Get the CPU-side copy of the interleaved vertex data.
+    #[must_use]
+    pub fn vertices(&self) -> &[f32] {
+        &self.vertices
+    }
+
+    /// Get the number of floats per vertex in `vertices` (6 for RGB, 7 for RGBA colors, 3 for custom layouts).
+    #[must_use]
+    pub fn floats_per_vertex(&self) -> usize {
+        self.floats_per_vertex
+    }
+
+    /// Get the OpenGL primitive mode the mesh is drawn with.
+    #[must_use]
+    pub fn mode(&self) -> u32 {
+        self.mode
+    }
+
+    /// Get the object-space bounding box of the mesh.
+    #[must_use]
+    pub fn bounds(&self) -> Aabb {
+        self.bounds
+    }
+
+    /// Compute one normal per vertex from the triangles each vertex belongs to.
+    /// 
+    /// # Returns
+    /// Unit-length face normals in object space, or an empty list for non-triangle meshes
+    pub fn normals(&self) -> Vec<Vector3<f32>> {
+        if self.mode != glow::TRIANGLES {
+            return Vec::new();
+        }
+        
+        let stride = self.floats_per_vertex;
+        let position = |index: usize| Vector3::new(self.vertices[index * stride], self.vertices[index * stride + 1], self.vertices[index * stride + 2]);
+        
+        // Shared vertices of indexed meshes average the normals of their triangles
+        let mut normals = vec![Vector3::zeros(); self.vbo.size() / self.layout.stride()];
+        for triangle in self.draw_order().chunks_exact(3) {
+            let (a, b, c) = (position(triangle[0]), position(triangle[1]), position(triangle[2]));
+            let normal = (b - a).cross(&(c - a)).try_normalize(f32::EPSILON).unwrap_or_else(Vector3::zeros);
+            for &index in triangle {
+                normals[index] += normal;
+            }
+        }
+        
+        normals
+            .into_iter()
+            .map(|normal| normal.try_normalize(f32::EPSILON).unwrap_or_else(Vector3::zeros))
+            .collect()
+    }
+
     /// Calculate vertex count from raw vertex data with position and color.
     /// 
     /// # Arguments
@@ -97,7 +1112,184 @@ impl Mesh {
     /// 
     /// # Returns
     /// Number of vertices
-    pub fn calculate_vertex_count(vertices: &[f32]) -> i32 {
-        (vertices.len() / 6) as i32
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if the vertex count exceeds `i32::MAX`
+    pub fn calculate_vertex_count(vertices: &[f32]) -> TemplateResult<i32> {
+        checked_vertex_count(vertices.len() / 6)
+    }
+
+    /// Calculate vertex count from raw vertex data with position and RGBA color.
+    /// 
+    /// # Arguments
+    /// * `vertices` - Raw vertex data (7 floats per vertex: x, y, z, r, g, b, a)
+    /// 
+    /// # Returns
+    /// Number of vertices
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if the vertex count exceeds `i32::MAX`
+    pub fn calculate_vertex_count_rgba(vertices: &[f32]) -> TemplateResult<i32> {
+        checked_vertex_count(vertices.len() / 7)
+    }
+
+    /// Calculate the bounding box of raw interleaved vertex data.
+    /// 
+    /// # Arguments
+    /// * `vertices` - Raw vertex data starting with x, y, z for each vertex
+    /// * `floats_per_vertex` - Number of floats per vertex
+    /// 
+    /// # Returns
+    /// The enclosing box, or a degenerate box at the origin for empty data
+    #[must_use]
+    pub fn calculate_bounds(vertices: &[f32], floats_per_vertex: usize) -> Aabb {
+        let positions = vertices.chunks_exact(floats_per_vertex).map(|vertex| Point3::new(vertex[0], vertex[1], vertex[2]));
+        Aabb::from_points(positions).unwrap_or_else(|| Aabb::new(Point3::origin(), Point3::origin()))
+    }
+}
+
+// ============================================================
+// ===================== Helper Functions =====================
+// ============================================================
+
+/// Convert a vertex or index count to the `i32` that OpenGL draw calls take.
+/// 
+/// # Returns
+/// The count, or `TemplateError::InvalidMesh` if it exceeds `i32::MAX`
+fn checked_vertex_count(count: usize) -> TemplateResult<i32> {
+    i32::try_from(count).map_err(|_| TemplateError::InvalidMesh(format!(
+        "{} vertices exceed the {} a single draw call can address; split the mesh",
+        count, i32::MAX
+    )))
+}
+
+/// Convert a first index to the byte offset into an index buffer that draw calls take.
+/// 
+/// # Returns
+/// The offset, or `TemplateError::InvalidMesh` if it exceeds `i32::MAX`
+fn index_byte_offset(start: i32, index_type: IndexType) -> TemplateResult<i32> {
+    gl_count(index_type.size()).checked_mul(start)
+        .ok_or_else(|| TemplateError::InvalidMesh(format!("index offset {start} overflows a byte offset")))
+}
+
+/// Check that raw vertex data can be read as positions through a layout.
+/// 
+/// # Returns
+/// `TemplateError::InvalidMesh` if the first attribute is not 3 unnormalized floats or `data` does
+/// not hold a whole number of vertices
+fn check_layout(data: &[u8], layout: &VertexLayout) -> TemplateResult<()> {
+    match layout.attributes().first() {
+        Some(position) if position.data_type == glow::FLOAT && position.components == 3 && !position.normalized => {}
+        _ => return Err(TemplateError::InvalidMesh("vertex layout must start with a 3-float position".to_string())),
+    }
+    let stride = layout.stride();
+    if !data.len().is_multiple_of(stride) {
+        return Err(TemplateError::InvalidMesh(format!(
+            "vertex data length {} is not a multiple of the {}-byte stride",
+            data.len(), stride
+        )));
+    }
+    Ok(())
+}
+
+/// Point a VAO's attributes at a vertex buffer.
+fn attach_vertex_buffer(gl: &GlContext, vao: glow::VertexArray, vbo: &VertexBuffer, layout: &VertexLayout) {
+    unsafe {
+        // Bind VAO to capture vertex attribute state
+        gl.bind_vertex_array(Some(vao));
+        gl.bind_buffer(glow::ARRAY_BUFFER, Some(vbo.handle()));
+        
+        // Configure vertex attributes (position at location 0, color at location 1)
+        layout.apply(gl);
+        
+        // Unbind VAO to prevent accidental modification
+        gl.bind_vertex_array(None);
+        gl.bind_buffer(glow::ARRAY_BUFFER, None);
     }
 }
+
+/// Enable primitive restart at an index for the next indexed draw, or disable it with `None`.
+/// 
+/// OpenGL ES always restarts at the index type's maximum, so this only changes state on
+/// desktop contexts.
+fn set_primitive_restart(gl: &GlContext, restart_index: Option<u32>) {
+    if gl.version().is_embedded {
+        return;
+    }
+    unsafe {
+        match restart_index {
+            Some(index) => {
+                gl.enable(glow::PRIMITIVE_RESTART);
+                if let Some(primitive_restart_index) = gl.primitive_restart_index {
+                    primitive_restart_index(index);
+                }
+            }
+            None => gl.disable(glow::PRIMITIVE_RESTART),
+        }
+    }
+}
+
+// ============================================================
+// ==================== Global Functions ======================
+// ============================================================
+
+/// Load `glPrimitiveRestartIndex` through a context's loader.
+/// 
+/// # Returns
+/// The entry point, or `None` if the driver lacks it
+pub(crate) fn load_primitive_restart_function(load: impl Fn(&CStr) -> *const c_void) -> Option<PrimitiveRestartIndex> {
+    let address = load(c"glPrimitiveRestartIndex");
+    // SAFETY: the address was returned by the GL loader for `glPrimitiveRestartIndex`,
+    // whose C signature matches `PrimitiveRestartIndex`.
+    (!address.is_null()).then(|| unsafe { std::mem::transmute::<*const c_void, PrimitiveRestartIndex>(address) })
+}
+
+// ============================================================
+// ========================== Tests ===========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VertexAttribute;
+
+    #[test]
+    fn positions_are_extracted_from_float_and_packed_colors() {
+        let float_layout = VertexLayout::position_color(4);
+        let float_vertices: [f32; 14] = [1.0, 2.0, 3.0, 1.0, 0.0, 0.0, 1.0, 4.0, 5.0, 6.0, 0.0, 1.0, 0.0, 1.0];
+        let float_data: &[u8] = bytemuck::cast_slice(&float_vertices);
+        
+        let packed_layout = VertexLayout::position_color_u8();
+        let packed_vertices = [
+            PackedColorVertex::new([1.0, 2.0, 3.0], [255, 0, 0, 255]),
+            PackedColorVertex::new([4.0, 5.0, 6.0], [0, 255, 0, 255]),
+        ];
+        let packed_data: &[u8] = bytemuck::cast_slice(&packed_vertices);
+        
+        assert!(check_layout(float_data, &float_layout).is_ok());
+        assert!(check_layout(packed_data, &packed_layout).is_ok());
+        let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        assert_eq!(Mesh::extract_positions(float_data, float_layout.stride()), expected);
+        assert_eq!(Mesh::extract_positions(packed_data, packed_layout.stride()), expected);
+    }
+
+    #[test]
+    fn layouts_without_float_positions_are_rejected() {
+        let data = [0u8; 32];
+        let color_first = VertexLayout::new(vec![VertexAttribute::normalized_u8(0, 4), VertexAttribute::float(1, 3)]);
+        let flat = VertexLayout::new(vec![VertexAttribute::float(0, 2)]);
+        let empty = VertexLayout::new(Vec::new());
+        
+        assert!(matches!(check_layout(&data, &color_first), Err(TemplateError::InvalidMesh(_))));
+        assert!(matches!(check_layout(&data, &flat), Err(TemplateError::InvalidMesh(_))));
+        assert!(matches!(check_layout(&data, &empty), Err(TemplateError::InvalidMesh(_))));
+        assert!(matches!(check_layout(&data[..20], &VertexLayout::position_color_u8()), Err(TemplateError::InvalidMesh(_))));
+    }
+
+    #[test]
+    fn oversized_counts_are_invalid_meshes() {
+        assert_eq!(checked_vertex_count(6).unwrap(), 6);
+        assert!(matches!(checked_vertex_count(usize::MAX), Err(TemplateError::InvalidMesh(_))));
+        assert!(matches!(index_byte_offset(i32::MAX, IndexType::U32), Err(TemplateError::InvalidMesh(_))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,180 @@
+//! CPU-side mesh preparation that can run off the GL thread.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::{log_issues, validate_mesh, Aabb, InvalidTriangles, Mesh};
+
+// ============================================================
+// ====================== Types & Enums ======================
+// ============================================================
+
+/// Per-vertex colors in any of the formats glTF allows for `COLOR_0`.
+/// 
+/// Integer channels are normalized (0..255 or 0..65535 becomes 0..1). Convert with
+/// `to_rgb` before `MeshData::build`, which keeps RGB for the `basic` shader.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VertexColors<'a> {
+    /// No color attribute; every vertex is white.
+    None,
+    RgbF32(&'a [[f32; 3]]),
+    RgbaF32(&'a [[f32; 4]]),
+    RgbU8(&'a [[u8; 3]]),
+    RgbaU8(&'a [[u8; 4]]),
+    RgbU16(&'a [[u16; 3]]),
+    RgbaU16(&'a [[u16; 4]]),
+}
+
+impl VertexColors<'_> {
+    /// Convert to float RGB, dropping alpha.
+    /// 
+    /// # Arguments
+    /// * `vertex_count` - Number of vertices; white is returned for each when there are no colors
+    #[must_use]
+    pub fn to_rgb(&self, vertex_count: usize) -> Vec<[f32; 3]> {
+        let u8_channel = |value: u8| f32::from(value) / f32::from(u8::MAX);
+        let u16_channel = |value: u16| f32::from(value) / f32::from(u16::MAX);
+        
+        match *self {
+            VertexColors::None => vec![[1.0, 1.0, 1.0]; vertex_count],
+            VertexColors::RgbF32(colors) => colors.to_vec(),
+            VertexColors::RgbaF32(colors) => colors.iter().map(|[r, g, b, _]| [*r, *g, *b]).collect(),
+            VertexColors::RgbU8(colors) => colors.iter().map(|color| color.map(u8_channel)).collect(),
+            VertexColors::RgbaU8(colors) => colors.iter().map(|[r, g, b, _]| [*r, *g, *b].map(u8_channel)).collect(),
+            VertexColors::RgbU16(colors) => colors.iter().map(|color| color.map(u16_channel)).collect(),
+            VertexColors::RgbaU16(colors) => colors.iter().map(|[r, g, b, _]| [*r, *g, *b].map(u16_channel)).collect(),
+        }
+    }
+}
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Interleaved, validated triangle data ready for `Mesh::from_data`.
+/// 
+/// Loading a mesh is split in two phases:
+/// 1. `MeshData::build` does all CPU work (interleaving, validation, repair, bounds). It
+///    makes no GL calls, so it can run on any thread, e.g. in a `WorkerTask` or a thread pool.
+/// 2. `Mesh::from_data` only uploads the buffers and must run on the GL thread.
+#[derive(Debug, Clone)]
+pub struct MeshData {
+    vertices: Vec<f32>,
+    indices: Option<Vec<u32>>,
+    bounds: Aabb,
+}
+
+impl MeshData {
+    /// Interleave positions and colors and check the triangles.
+    /// 
+    /// Problems are logged as warnings. Indexed triangles that are out of range, degenerate
+    /// or touch a NaN vertex are only removed with `InvalidTriangles::Drop`.
+    /// 
+    /// # Arguments
+    /// * `positions` - Vertex positions
+    /// * `colors` - One RGB color per vertex, or empty for white
+    /// * `indices` - Three vertex indices per triangle, or `None` for a plain triangle list
+    /// * `invalid_triangles` - Whether invalid indexed triangles are kept or dropped
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if the color count does not match the position count, or an
+    /// index is out of range and invalid triangles are kept
+    pub fn build(positions: &[[f32; 3]], colors: &[[f32; 3]], indices: Option<&[u32]>, invalid_triangles: InvalidTriangles) -> TemplateResult<Self> {
+        if !colors.is_empty() && colors.len() != positions.len() {
+            return Err(TemplateError::InvalidMesh(format!(
+                "{} colors given for {} vertices",
+                colors.len(), positions.len()
+            )));
+        }
+        
+        let indices = if let Some(indices) = indices {
+            Some(invalid_triangles.apply(positions, indices)?)
+        } else {
+            let sequential: Vec<u32> = (0..u32::try_from(positions.len()).unwrap_or(u32::MAX)).collect();
+            if let Err(issues) = validate_mesh(positions, &sequential) {
+                log_issues(&issues);
+            }
+            None
+        };
+        
+        let mut vertices = Vec::with_capacity(positions.len() * 6);
+        for (index, position) in positions.iter().enumerate() {
+            vertices.extend_from_slice(position);
+            vertices.extend_from_slice(colors.get(index).unwrap_or(&[1.0, 1.0, 1.0]));
+        }
+        
+        let bounds = Mesh::calculate_bounds(&vertices, 6);
+        Ok(Self { vertices, indices, bounds })
+    }
+
+    /// Interleave positions with colors in any `VertexColors` format, such as a glTF `COLOR_0` accessor.
+    /// 
+    /// # Arguments
+    /// * `positions` - Vertex positions
+    /// * `colors` - Vertex colors, or `VertexColors::None` for white
+    /// * `indices` - Three vertex indices per triangle, or `None` for a plain triangle list
+    /// * `invalid_triangles` - Whether invalid indexed triangles are kept or dropped
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if the color count does not match the position count, or an
+    /// index is out of range and invalid triangles are kept
+    pub fn build_with_colors(positions: &[[f32; 3]], colors: VertexColors<'_>, indices: Option<&[u32]>, invalid_triangles: InvalidTriangles) -> TemplateResult<Self> {
+        Self::build(positions, &colors.to_rgb(positions.len()), indices, invalid_triangles)
+    }
+
+    /// Get the interleaved vertex data (6 floats per vertex: x, y, z, r, g, b).
+    #[must_use]
+    pub fn vertices(&self) -> &[f32] {
+        &self.vertices
+    }
+
+    /// Get the triangle indices, or `None` for a plain triangle list.
+    #[must_use]
+    pub fn indices(&self) -> Option<&[u32]> {
+        self.indices.as_deref()
+    }
+
+    /// Get the number of vertices.
+    #[must_use]
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len() / 6
+    }
+
+    /// Get the object-space bounding box.
+    #[must_use]
+    pub fn bounds(&self) -> Aabb {
+        self.bounds
+    }
+}
+
+// ============================================================
+// ========================== Tests ===========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_colors_are_normalized() {
+        assert_eq!(VertexColors::RgbU8(&[[255, 0, 51]]).to_rgb(1), vec![[1.0, 0.0, 0.2]]);
+        assert_eq!(VertexColors::RgbU16(&[[65535, 0, 13107]]).to_rgb(1), vec![[1.0, 0.0, 0.2]]);
+    }
+
+    #[test]
+    fn alpha_is_dropped() {
+        assert_eq!(VertexColors::RgbaF32(&[[0.25, 0.5, 0.75, 0.1]]).to_rgb(1), vec![[0.25, 0.5, 0.75]]);
+        assert_eq!(VertexColors::RgbaU8(&[[255, 0, 0, 7]]).to_rgb(1), vec![[1.0, 0.0, 0.0]]);
+        assert_eq!(VertexColors::RgbaU16(&[[0, 65535, 0, 7]]).to_rgb(1), vec![[0.0, 1.0, 0.0]]);
+    }
+
+    #[test]
+    fn missing_colors_are_white() {
+        assert_eq!(VertexColors::None.to_rgb(2), vec![[1.0, 1.0, 1.0]; 2]);
+        
+        let data = MeshData::build_with_colors(&[[0.0; 3], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]], VertexColors::None, None, InvalidTriangles::Report).unwrap();
+        assert!(data.vertices().chunks_exact(6).all(|vertex| vertex[3..] == [1.0, 1.0, 1.0]));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,145 @@
+//! Overdraw heatmap showing how many fragments cover each pixel.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::TemplateResult;
+use crate::{BlendMode, Camera, Framebuffer, FullscreenQuad, GlApi, GlContext, PostProcessChain, RenderPass, SceneItem, Shader, Texture};
+use glow::HasContext;
+use nalgebra::Perspective3;
+use tracing;
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Brightness each fragment adds to the count target.
+/// 
+/// 8-bit channels saturate after `1 / OVERDRAW_STEP` layers, far beyond where the ramp tops out.
+pub const OVERDRAW_STEP: f32 = 1.0 / 32.0;
+
+/// Layer count shown as full red by default.
+pub const DEFAULT_MAX_OVERDRAW: f32 = 8.0;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Maps accumulated overdraw counts to a black-blue-green-yellow-red ramp.
+pub struct OverdrawHeatmapPass {
+    shader: Shader,
+    max_layers: f32,
+}
+
+impl OverdrawHeatmapPass {
+    /// Compile the heatmap shader.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `api` - Graphics API, used to pick the shader preamble
+    /// * `max_layers` - Layer count mapped to the hot end of the ramp
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the heatmap shader cannot be loaded
+    pub fn new(gl: &GlContext, api: GlApi, max_layers: f32) -> TemplateResult<Self> {
+        let shader = Shader::with_preamble(gl, "fullscreen.vert", "overdraw_heatmap.frag", Some(api.shader_preamble()))?;
+        Ok(Self { shader, max_layers })
+    }
+}
+
+impl RenderPass for OverdrawHeatmapPass {
+    fn execute(&mut self, gl: &GlContext, quad: &FullscreenQuad, input: &Texture, _output: Option<&Framebuffer>) {
+        self.shader.bind(gl);
+        input.bind(gl, 0);
+        self.shader.set_int(gl, "screenTexture", 0);
+        self.shader.set_float(gl, "layerStep", OVERDRAW_STEP);
+        self.shader.set_float(gl, "maxLayers", self.max_layers.max(1.0));
+        quad.draw(gl);
+    }
+
+    fn delete(self: Box<Self>, gl: &GlContext) {
+        self.shader.delete(gl);
+    }
+}
+
+/// Renders a scene as an overdraw heatmap to diagnose fill-rate cost.
+/// 
+/// Every item is drawn with a constant-color shader, without depth testing and with
+/// additive blending, so each pixel accumulates one step per fragment that covers it,
+/// including fragments a depth test would have rejected. A post pass then colors the
+/// counts: black for untouched pixels, red at `DEFAULT_MAX_OVERDRAW` layers and above.
+pub struct OverdrawView {
+    target: Framebuffer,
+    shader: Shader,
+    heatmap: PostProcessChain,
+}
+
+impl OverdrawView {
+    /// Create the count target and compile the shaders.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `api` - Graphics API the shader preamble is chosen for
+    /// * `width` - Width in pixels
+    /// * `height` - Height in pixels
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if a shader cannot be loaded, or
+    /// `TemplateError::OpenGL` or `TemplateError::Texture` if a target cannot be created
+    pub fn new(gl: &GlContext, api: GlApi, width: u32, height: u32) -> TemplateResult<Self> {
+        tracing::debug!("Creating {}x{} overdraw view", width, height);
+        let shader = Shader::with_preamble(gl, "basic.vert", "overdraw.frag", Some(api.shader_preamble()))?;
+        let target = Framebuffer::new(gl, width, height)?;
+        let mut heatmap = PostProcessChain::new(gl, width, height)?;
+        heatmap.add_pass(OverdrawHeatmapPass::new(gl, api, DEFAULT_MAX_OVERDRAW)?);
+        Ok(Self { target, shader, heatmap })
+    }
+
+    /// Count the fragments of every item, then draw the heatmap to the window.
+    /// 
+    /// Leaves depth testing enabled and blending disabled; the caller restores any other state.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `items` - Items to draw; materials and transparency are ignored
+    /// * `camera` - Camera the scene is viewed from
+    /// * `projection` - Projection matrix
+    pub fn render(&mut self, gl: &GlContext, items: &[SceneItem], camera: &Camera, projection: &Perspective3<f32>) {
+        crate::assert_gl_thread();
+        self.target.bind(gl);
+        unsafe {
+            gl.clear_buffer_f32_slice(glow::COLOR, 0, &[0.0, 0.0, 0.0, 1.0]);
+            gl.disable(glow::DEPTH_TEST);
+            gl.enable(glow::BLEND);
+        }
+        BlendMode::Additive.apply(gl);
+        
+        self.shader.bind(gl);
+        self.shader.set_matrix4(gl, "projection", projection.as_matrix());
+        self.shader.set_matrix4(gl, "view", &camera.view_matrix());
+        self.shader.set_float(gl, "layerStep", OVERDRAW_STEP);
+        for item in items {
+            self.shader.set_matrix4(gl, "model", &item.model);
+            item.mesh.draw(gl);
+        }
+        
+        unsafe {
+            gl.disable(glow::BLEND);
+        }
+        self.heatmap.run(gl, self.target.color_texture(), None);
+    }
+
+    /// Get the size in pixels.
+    #[must_use]
+    pub fn size(&self) -> (u32, u32) {
+        self.target.size()
+    }
+
+    /// Release the count target, the heatmap chain and the shader.
+    pub fn delete(self, gl: &GlContext) {
+        self.target.delete(gl);
+        self.heatmap.delete(gl);
+        self.shader.delete(gl);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,297 @@
+//! CPU-simulated particle system rendered with instanced billboard quads.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::{Camera, GlApi, GlContext, Shader};
+use crate::context::gl_count;
+use glow::HasContext;
+use nalgebra::{Perspective3, Vector3};
+use bytemuck;
+use tracing;
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Floats per particle instance: position (3), color (4), size (1).
+const INSTANCE_FLOATS: usize = 8;
+
+/// Quad corners drawn as a triangle strip.
+const QUAD_CORNERS: [f32; 8] = [-1.0, -1.0, 1.0, -1.0, -1.0, 1.0, 1.0, 1.0];
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// State of a single live particle.
+#[derive(Debug, Clone, Copy)]
+pub struct Particle {
+    pub position: Vector3<f32>,
+    pub velocity: Vector3<f32>,
+    pub life: f32,
+    pub lifetime: f32,
+}
+
+/// Emission and simulation parameters of a particle system.
+#[derive(Debug, Clone, Copy)]
+pub struct ParticleSettings {
+    /// World-space point particles are emitted from.
+    pub origin: Vector3<f32>,
+    /// Particles emitted per second.
+    pub emit_rate: f32,
+    /// Constant acceleration applied to every particle.
+    pub gravity: Vector3<f32>,
+    /// Seconds a particle lives before it is removed.
+    pub lifetime: f32,
+    /// Initial speed along the emission direction.
+    pub speed: f32,
+    /// Random horizontal velocity added on emission.
+    pub spread: f32,
+    /// Billboard half size in world units.
+    pub size: f32,
+    /// Color at birth.
+    pub start_color: [f32; 4],
+    /// Color at death (alpha fades towards this).
+    pub end_color: [f32; 4],
+}
+
+impl Default for ParticleSettings {
+    /// An upward orange fountain.
+    fn default() -> Self {
+        Self {
+            origin: Vector3::zeros(),
+            emit_rate: 200.0,
+            gravity: Vector3::new(0.0, -2.0, 0.0),
+            lifetime: 2.0,
+            speed: 2.0,
+            spread: 0.5,
+            size: 0.03,
+            start_color: [1.0, 0.8, 0.2, 1.0],
+            end_color: [1.0, 0.1, 0.0, 0.0],
+        }
+    }
+}
+
+/// Particle system that simulates on the CPU and re-uploads an instance buffer every frame.
+pub struct ParticleSystem {
+    pub settings: ParticleSettings,
+    particles: Vec<Particle>,
+    max_particles: usize,
+    emit_accumulator: f32,
+    rng_state: u32,
+    shader: Shader,
+    vao: glow::VertexArray,
+    quad_vbo: glow::Buffer,
+    instance_vbo: glow::Buffer,
+    instance_data: Vec<f32>,
+}
+
+impl ParticleSystem {
+    /// Create a particle system with room for `max_particles` live particles.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `api` - Graphics API used to pick the shader preamble
+    /// * `max_particles` - Upper bound on simultaneously live particles
+    /// * `settings` - Emission and simulation parameters
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the particle shader cannot be
+    /// loaded, or `TemplateError::OpenGL` if the buffers cannot be created
+    pub fn new(gl: &GlContext, api: GlApi, max_particles: usize, settings: ParticleSettings) -> TemplateResult<Self> {
+        tracing::debug!("Creating particle system for {} particles", max_particles);
+        
+        let shader = Shader::with_preamble(gl, "particle.vert", "particle.frag", Some(api.shader_preamble()))?;
+        
+        unsafe {
+            let vao = gl.create_vertex_array().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            let quad_vbo = gl.create_buffer().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            let instance_vbo = gl.create_buffer().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            
+            gl.bind_vertex_array(Some(vao));
+            
+            // Corner (location 0): shared by all instances
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(quad_vbo));
+            gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, bytemuck::cast_slice(&QUAD_CORNERS), glow::STATIC_DRAW);
+            gl.enable_vertex_attrib_array(0);
+            gl.vertex_attrib_pointer_f32(0, 2, glow::FLOAT, false, 8, 0);
+            
+            // Position (1), color (2) and size (3): advance once per instance
+            let stride = gl_count(INSTANCE_FLOATS * std::mem::size_of::<f32>());
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(instance_vbo));
+            gl.enable_vertex_attrib_array(1);
+            gl.vertex_attrib_pointer_f32(1, 3, glow::FLOAT, false, stride, 0);
+            gl.vertex_attrib_divisor(1, 1);
+            gl.enable_vertex_attrib_array(2);
+            gl.vertex_attrib_pointer_f32(2, 4, glow::FLOAT, false, stride, 12);
+            gl.vertex_attrib_divisor(2, 1);
+            gl.enable_vertex_attrib_array(3);
+            gl.vertex_attrib_pointer_f32(3, 1, glow::FLOAT, false, stride, 28);
+            gl.vertex_attrib_divisor(3, 1);
+            
+            gl.bind_vertex_array(None);
+            
+            Ok(Self {
+                settings,
+                particles: Vec::with_capacity(max_particles),
+                max_particles,
+                emit_accumulator: 0.0,
+                rng_state: 0x9E37_79B9,
+                shader,
+                vao,
+                quad_vbo,
+                instance_vbo,
+                instance_data: Vec::with_capacity(max_particles * INSTANCE_FLOATS),
+            })
+        }
+    }
+
+    /// Advance the simulation: age and move live particles, then emit new ones.
+    /// 
+    /// # Arguments
+    /// * `delta_time` - Seconds since the previous update
+    pub fn update(&mut self, delta_time: f32) {
+        let gravity = self.settings.gravity;
+        self.particles.retain_mut(|particle| {
+            particle.life -= delta_time;
+            particle.velocity += gravity * delta_time;
+            particle.position += particle.velocity * delta_time;
+            particle.life > 0.0
+        });
+        
+        self.emit_accumulator += self.settings.emit_rate * delta_time;
+        while self.emit_accumulator >= 1.0 {
+            self.emit_accumulator -= 1.0;
+            if self.particles.len() < self.max_particles {
+                let particle = self.spawn();
+                self.particles.push(particle);
+            }
+        }
+    }
+
+    /// Get the number of live particles.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// Get the maximum number of simultaneously live particles.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.max_particles
+    }
+
+    /// Check whether there are no live particles.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Upload the live particles and draw them with additive blending.
+    /// 
+    /// The blend enable state, blend function and depth write mask are restored to whatever
+    /// they were before.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `camera` - Camera the particles are viewed from
+    /// * `projection` - Projection matrix
+    pub fn draw(&mut self, gl: &GlContext, camera: &Camera, projection: &Perspective3<f32>) {
+        if self.particles.is_empty() {
+            return;
+        }
+        
+        self.instance_data.clear();
+        for particle in &self.particles {
+            let t = 1.0 - particle.life / particle.lifetime;
+            let (start, end) = (self.settings.start_color, self.settings.end_color);
+            self.instance_data.extend_from_slice(&[
+                particle.position.x,
+                particle.position.y,
+                particle.position.z,
+                start[0] + (end[0] - start[0]) * t,
+                start[1] + (end[1] - start[1]) * t,
+                start[2] + (end[2] - start[2]) * t,
+                start[3] + (end[3] - start[3]) * t,
+                self.settings.size,
+            ]);
+        }
+        
+        self.shader.bind(gl);
+        self.shader.set_matrix4(gl, "projection", projection.as_matrix());
+        self.shader.set_matrix4(gl, "view", &camera.view_matrix());
+        
+        let (blend, blend_func, depth_write) = unsafe {
+            let blend_factor = |parameter| gl.get_parameter_i32(parameter).cast_unsigned();
+            (
+                gl.is_enabled(glow::BLEND),
+                [glow::BLEND_SRC_RGB, glow::BLEND_DST_RGB, glow::BLEND_SRC_ALPHA, glow::BLEND_DST_ALPHA].map(blend_factor),
+                gl.get_parameter_bool(glow::DEPTH_WRITEMASK),
+            )
+        };
+        unsafe {
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.instance_vbo));
+            gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, bytemuck::cast_slice(&self.instance_data), glow::STREAM_DRAW);
+            gl.bind_buffer(glow::ARRAY_BUFFER, None);
+            
+            gl.enable(glow::BLEND);
+            gl.blend_func(glow::SRC_ALPHA, glow::ONE);
+            gl.depth_mask(false);
+            
+            gl.bind_vertex_array(Some(self.vao));
+            gl.draw_arrays_instanced(glow::TRIANGLE_STRIP, 0, 4, gl_count(self.particles.len()));
+            gl.bind_vertex_array(None);
+            
+            gl.depth_mask(depth_write);
+            let [src_rgb, dst_rgb, src_alpha, dst_alpha] = blend_func;
+            gl.blend_func_separate(src_rgb, dst_rgb, src_alpha, dst_alpha);
+            if !blend {
+                gl.disable(glow::BLEND);
+            }
+        }
+    }
+
+    /// Release the GPU resources owned by this particle system.
+    pub fn delete(self, gl: &GlContext) {
+        unsafe {
+            gl.delete_vertex_array(self.vao);
+            crate::leaks::track_deleted();
+            gl.delete_buffer(self.quad_vbo);
+            crate::leaks::track_deleted();
+            gl.delete_buffer(self.instance_vbo);
+            crate::leaks::track_deleted();
+        }
+        self.shader.delete(gl);
+    }
+
+    fn spawn(&mut self) -> Particle {
+        let spread = self.settings.spread;
+        let direction = Vector3::new(
+            (self.next_random() * 2.0 - 1.0) * spread,
+            1.0,
+            (self.next_random() * 2.0 - 1.0) * spread,
+        );
+        
+        Particle {
+            position: self.settings.origin,
+            velocity: direction * self.settings.speed,
+            life: self.settings.lifetime,
+            lifetime: self.settings.lifetime,
+        }
+    }
+
+    /// Xorshift random number in `[0, 1)`.
+    #[allow(clippy::cast_precision_loss)]
+    fn next_random(&mut self) -> f32 {
+        self.rng_state ^= self.rng_state << 13;
+        self.rng_state ^= self.rng_state >> 17;
+        self.rng_state ^= self.rng_state << 5;
+        (self.rng_state >> 8) as f32 / (1u32 << 24) as f32
+    }
+}
\ No newline at end of file
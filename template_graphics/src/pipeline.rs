@@ -0,0 +1,167 @@
+//! Program pipelines combining separately compiled shader stages.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::{GlContext, Shader};
+use glow::HasContext;
+use std::ffi::{c_void, CStr};
+
+// ============================================================
+// ========================== Types ===========================
+// ============================================================
+
+type GenProgramPipelines = unsafe extern "system" fn(n: i32, pipelines: *mut u32);
+type DeleteProgramPipelines = unsafe extern "system" fn(n: i32, pipelines: *const u32);
+type BindProgramPipeline = unsafe extern "system" fn(pipeline: u32);
+type UseProgramStages = unsafe extern "system" fn(pipeline: u32, stages: u32, program: u32);
+type ProgramParameteri = unsafe extern "system" fn(program: u32, pname: u32, value: i32);
+
+/// Separate shader object entry points, which glow does not wrap.
+pub(crate) struct PipelineFunctions {
+    gen_program_pipelines: GenProgramPipelines,
+    delete_program_pipelines: DeleteProgramPipelines,
+    bind_program_pipeline: BindProgramPipeline,
+    use_program_stages: UseProgramStages,
+    program_parameteri: ProgramParameteri,
+}
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Mixes separable vertex and fragment stages without relinking.
+/// 
+/// Build the stages with `Shader::separable_vertex` / `Shader::separable_fragment`, attach
+/// them with `set_stage`, and swap a stage at runtime by attaching another one. Binding a
+/// regular program with `Shader::bind` takes precedence over the pipeline, which is also how
+/// uniforms are set: bind the stage, set its uniforms, then `bind` the pipeline again to draw.
+/// 
+/// Requires OpenGL 4.1, OpenGL ES 3.1 or `GL_ARB_separate_shader_objects`.
+pub struct ProgramPipeline {
+    pipeline: u32,
+}
+
+impl ProgramPipeline {
+    /// Create an empty pipeline.
+    /// 
+    /// # Returns
+    /// The pipeline
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if separate shader objects are unsupported
+    pub fn new(gl: &GlContext) -> TemplateResult<Self> {
+        if !Self::is_supported(gl) {
+            return Err(TemplateError::OpenGL("separate shader objects are not supported".to_string()));
+        }
+        
+        let mut pipeline = 0;
+        unsafe {
+            (functions(gl).gen_program_pipelines)(1, &raw mut pipeline);
+        }
+        if pipeline == 0 {
+            return Err(TemplateError::OpenGL("failed to create a program pipeline".to_string()));
+        }
+        crate::leaks::track_created();
+        Ok(Self { pipeline })
+    }
+
+    /// Check whether the context supports separable programs and pipelines.
+    pub fn is_supported(gl: &GlContext) -> bool {
+        let version = gl.version();
+        let required = if version.is_embedded { (3, 1) } else { (4, 1) };
+        let exposed = (version.major, version.minor) >= required
+            || gl.supported_extensions().contains("GL_ARB_separate_shader_objects");
+        exposed && gl.pipeline.is_some()
+    }
+
+    /// Use a separable stage for its pipeline stage, replacing the previous one.
+    /// 
+    /// Attach the stage again after reloading it, since reloading creates a new program.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context the pipeline was created with
+    /// * `stage` - Stage built with `Shader::separable_vertex` or `Shader::separable_fragment`
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if `stage` is not a separable stage
+    pub fn set_stage(&self, gl: &GlContext, stage: &Shader) -> TemplateResult<()> {
+        let Some(stage_bit) = stage.separable_stage() else {
+            return Err(TemplateError::OpenGL("only separable shader stages can be attached to a pipeline".to_string()));
+        };
+        
+        unsafe {
+            (functions(gl).use_program_stages)(self.pipeline, stage_bit, stage.program().0.get());
+        }
+        Ok(())
+    }
+
+    /// Bind the pipeline for drawing, unbinding any regular program that would override it.
+    pub fn bind(&self, gl: &GlContext) {
+        crate::assert_gl_thread();
+        unsafe {
+            gl.use_program(None);
+            (functions(gl).bind_program_pipeline)(self.pipeline);
+        }
+    }
+
+    /// Unbind whatever pipeline is current.
+    pub fn unbind(gl: &GlContext) {
+        if let Some(functions) = &gl.pipeline {
+            unsafe {
+                (functions.bind_program_pipeline)(0);
+            }
+        }
+    }
+
+    /// Release the pipeline object. The attached stages are not deleted.
+    pub fn delete(self, gl: &GlContext) {
+        unsafe {
+            (functions(gl).delete_program_pipelines)(1, &raw const self.pipeline);
+        }
+        crate::leaks::track_deleted();
+    }
+}
+
+// ============================================================
+// ===================== Helper Functions =====================
+// ============================================================
+
+/// Get the loaded entry points; only reachable after `ProgramPipeline::is_supported` passed.
+fn functions(gl: &GlContext) -> &PipelineFunctions {
+    gl.pipeline
+        .as_ref()
+        .expect("program pipeline functions used without checking ProgramPipeline::is_supported")
+}
+
+// ============================================================
+// ==================== Global Functions ======================
+// ============================================================
+
+/// Load the separate shader object entry points through a context's loader.
+/// 
+/// # Returns
+/// The entry points, or `None` if the driver lacks any of them
+pub(crate) fn load_pipeline_functions(load: impl Fn(&CStr) -> *const c_void) -> Option<PipelineFunctions> {
+    let find = |names: &[&CStr]| names.iter().map(|name| load(name)).find(|address| !address.is_null());
+    // SAFETY: each address was returned by the GL loader for the named entry point, whose
+    // C signature matches the function pointer type it is cast to.
+    unsafe {
+        Some(PipelineFunctions {
+            gen_program_pipelines: std::mem::transmute::<*const c_void, GenProgramPipelines>(find(&[c"glGenProgramPipelines", c"glGenProgramPipelinesEXT"])?),
+            delete_program_pipelines: std::mem::transmute::<*const c_void, DeleteProgramPipelines>(find(&[c"glDeleteProgramPipelines", c"glDeleteProgramPipelinesEXT"])?),
+            bind_program_pipeline: std::mem::transmute::<*const c_void, BindProgramPipeline>(find(&[c"glBindProgramPipeline", c"glBindProgramPipelineEXT"])?),
+            use_program_stages: std::mem::transmute::<*const c_void, UseProgramStages>(find(&[c"glUseProgramStages", c"glUseProgramStagesEXT"])?),
+            program_parameteri: std::mem::transmute::<*const c_void, ProgramParameteri>(find(&[c"glProgramParameteri", c"glProgramParameteriEXT"])?),
+        })
+    }
+}
+
+/// Mark a program as separable before it is linked.
+pub(crate) fn set_program_separable(gl: &GlContext, program: glow::Program) {
+    unsafe {
+        (functions(gl).program_parameteri)(program.0.get(), glow::PROGRAM_SEPARABLE, 1);
+    }
+}
\ No newline at end of file
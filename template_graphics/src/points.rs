@@ -0,0 +1,103 @@
+//! Round, distance-attenuated point sprites for point clouds.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::TemplateResult;
+use crate::{Camera, GlApi, GlContext, Mesh, Shader};
+use glow::HasContext;
+use nalgebra::{Matrix4, Perspective3};
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Size parameters of rendered points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointSettings {
+    /// Point diameter in world units; points shrink with distance like real geometry.
+    pub size: f32,
+    /// Smallest on-screen diameter in pixels, so far points stay visible.
+    pub min_size: f32,
+    /// Largest on-screen diameter in pixels, so near points do not fill the screen.
+    pub max_size: f32,
+}
+
+impl Default for PointSettings {
+    /// 2 cm points drawn between 1 and 32 pixels wide.
+    fn default() -> Self {
+        Self {
+            size: 0.02,
+            min_size: 1.0,
+            max_size: 32.0,
+        }
+    }
+}
+
+/// Draws point meshes (see `Mesh::new_points`) as antialiased discs.
+pub struct PointRenderer {
+    pub settings: PointSettings,
+    shader: Shader,
+    api: GlApi,
+}
+
+impl PointRenderer {
+    /// Compile the point sprite shader.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `api` - Graphics API used to pick the shader preamble
+    /// * `settings` - Point size parameters
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the point sprite shader cannot be loaded
+    pub fn new(gl: &GlContext, api: GlApi, settings: PointSettings) -> TemplateResult<Self> {
+        let shader = Shader::with_preamble(gl, "point.vert", "point.frag", Some(api.shader_preamble()))?;
+        Ok(Self { settings, shader, api })
+    }
+
+    /// Draw a point mesh.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `mesh` - Mesh created with `Mesh::new_points`
+    /// * `model` - Model matrix
+    /// * `camera` - Camera the points are viewed from
+    /// * `projection` - Projection matrix
+    /// * `viewport_height` - Height of the target in pixels, used to convert sizes to pixels
+    #[allow(clippy::cast_precision_loss)]
+    pub fn draw(&mut self, gl: &GlContext, mesh: &Mesh, model: &Matrix4<f32>, camera: &Camera, projection: &Perspective3<f32>, viewport_height: u32) {
+        self.shader.bind(gl);
+        self.shader.set_matrix4(gl, "projection", projection.as_matrix());
+        self.shader.set_matrix4(gl, "view", &camera.view_matrix());
+        self.shader.set_matrix4(gl, "model", model);
+        self.shader.set_float(gl, "pointSize", self.settings.size);
+        self.shader.set_float(gl, "minPointSize", self.settings.min_size);
+        self.shader.set_float(gl, "maxPointSize", self.settings.max_size);
+        self.shader.set_float(gl, "viewportHeight", viewport_height as f32);
+        
+        unsafe {
+            // OpenGL ES always takes the size from the shader and has no switch for it
+            if self.api == GlApi::OpenGl {
+                gl.enable(glow::PROGRAM_POINT_SIZE);
+            }
+            gl.enable(glow::BLEND);
+            gl.blend_func(glow::SRC_ALPHA, glow::ONE_MINUS_SRC_ALPHA);
+        }
+        
+        mesh.draw(gl);
+        
+        unsafe {
+            gl.disable(glow::BLEND);
+            if self.api == GlApi::OpenGl {
+                gl.disable(glow::PROGRAM_POINT_SIZE);
+            }
+        }
+    }
+
+    /// Release the shader.
+    pub fn delete(self, gl: &GlContext) {
+        self.shader.delete(gl);
+    }
+}
\ No newline at end of file
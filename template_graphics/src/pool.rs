@@ -0,0 +1,132 @@
+//! Recycling of vertex buffers and VAOs for frequently rebuilt meshes.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::GlContext;
+use glow::HasContext;
+use std::collections::HashMap;
+use tracing;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Free list of GL buffers and vertex arrays that meshes take from and give back.
+/// 
+/// Creating and deleting buffers every frame for dynamic geometry churns the driver's
+/// allocator. Meshes built with `Mesh::new_pooled` and retired with `Mesh::recycle`
+/// instead reuse a buffer of the same byte size and any free VAO. Like deletion, recycling
+/// is explicit, since `Drop` has no GL context to work with. Pooled objects stay alive
+/// (and counted by the leak checker) until the pool is deleted.
+#[derive(Debug, Default)]
+pub struct BufferPool {
+    buffers: HashMap<usize, Vec<glow::Buffer>>,
+    vertex_arrays: Vec<glow::VertexArray>,
+    reused: usize,
+}
+
+impl BufferPool {
+    /// Create an empty pool.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take a buffer holding `data`, reusing a free one of the same size if there is one.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `data` - Bytes to upload
+    /// 
+    /// # Returns
+    /// The array buffer
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if no pooled buffer is free and a new one cannot be created
+    pub fn acquire_buffer(&mut self, gl: &GlContext, data: &[u8]) -> TemplateResult<glow::Buffer> {
+        unsafe {
+            if let Some(buffer) = self.buffers.get_mut(&data.len()).and_then(Vec::pop) {
+                self.reused += 1;
+                tracing::trace!("Reusing pooled {}-byte buffer", data.len());
+                gl.bind_buffer(glow::ARRAY_BUFFER, Some(buffer));
+                gl.buffer_sub_data_u8_slice(glow::ARRAY_BUFFER, 0, data);
+                gl.bind_buffer(glow::ARRAY_BUFFER, None);
+                return Ok(buffer);
+            }
+            
+            let buffer = gl.create_buffer().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(buffer));
+            gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, data, glow::DYNAMIC_DRAW);
+            gl.bind_buffer(glow::ARRAY_BUFFER, None);
+            Ok(buffer)
+        }
+    }
+
+    /// Give a buffer back for reuse.
+    /// 
+    /// # Arguments
+    /// * `buffer` - Buffer taken from `acquire_buffer`
+    /// * `size` - Its size in bytes
+    pub fn release_buffer(&mut self, buffer: glow::Buffer, size: usize) {
+        self.buffers.entry(size).or_default().push(buffer);
+    }
+
+    /// Take a vertex array, reusing a free one if there is one.
+    /// 
+    /// Reused VAOs have had their attributes disabled and no element buffer bound.
+    /// 
+    /// # Returns
+    /// The vertex array
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if no pooled vertex array is free and a new one cannot be created
+    pub fn acquire_vertex_array(&mut self, gl: &GlContext) -> TemplateResult<glow::VertexArray> {
+        if let Some(vao) = self.vertex_arrays.pop() {
+            self.reused += 1;
+            return Ok(vao);
+        }
+        let vao = unsafe { gl.create_vertex_array() }.map_err(TemplateError::OpenGL)?;
+        crate::leaks::track_created();
+        Ok(vao)
+    }
+
+    /// Give a vertex array back for reuse; the caller resets its attribute state first.
+    pub fn release_vertex_array(&mut self, vao: glow::VertexArray) {
+        self.vertex_arrays.push(vao);
+    }
+
+    /// Get the number of free buffers and vertex arrays waiting for reuse.
+    pub fn free_count(&self) -> usize {
+        self.buffers.values().map(Vec::len).sum::<usize>() + self.vertex_arrays.len()
+    }
+
+    /// Get how many acquisitions were served from the pool instead of creating an object.
+    #[must_use]
+    pub fn reuse_count(&self) -> usize {
+        self.reused
+    }
+
+    /// Delete every free object.
+    /// 
+    /// Objects still held by meshes are not owned by the pool; delete those meshes normally.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    pub fn delete(self, gl: &GlContext) {
+        tracing::debug!("Deleting buffer pool with {} free objects", self.free_count());
+        unsafe {
+            for buffer in self.buffers.into_values().flatten() {
+                gl.delete_buffer(buffer);
+                crate::leaks::track_deleted();
+            }
+            for vao in self.vertex_arrays {
+                gl.delete_vertex_array(vao);
+                crate::leaks::track_deleted();
+            }
+        }
+    }
+}
\ No newline at end of file
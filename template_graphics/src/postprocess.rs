@@ -0,0 +1,350 @@
+//! Composable post-processing passes.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::TemplateResult;
+use crate::{ColorFormat, Framebuffer, FullscreenQuad, GlApi, GlContext, Shader, Texture, Texture3D};
+use crate::context::gl_size;
+use glow::HasContext;
+use tracing;
+
+// ============================================================
+// ========================== Traits ==========================
+// ============================================================
+
+/// A single fullscreen effect reading one texture and writing one render target.
+pub trait RenderPass {
+    /// Run the pass.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `quad` - Fullscreen triangle to draw with
+    /// * `input` - Texture produced by the previous pass (or the scene)
+    /// * `output` - Target framebuffer, or `None` for the window; already bound by the caller
+    fn execute(&mut self, gl: &GlContext, quad: &FullscreenQuad, input: &Texture, output: Option<&Framebuffer>);
+
+    /// Release the GPU resources owned by the pass.
+    fn delete(self: Box<Self>, gl: &GlContext);
+}
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Converts the input to grayscale using Rec. 709 luminance weights.
+pub struct GrayscalePass {
+    shader: Shader,
+}
+
+impl GrayscalePass {
+    /// Compile the grayscale shader.
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the shader cannot be loaded
+    pub fn new(gl: &GlContext, api: GlApi) -> TemplateResult<Self> {
+        let shader = Shader::with_preamble(gl, "fullscreen.vert", "grayscale.frag", Some(api.shader_preamble()))?;
+        Ok(Self { shader })
+    }
+}
+
+impl RenderPass for GrayscalePass {
+    fn execute(&mut self, gl: &GlContext, quad: &FullscreenQuad, input: &Texture, _output: Option<&Framebuffer>) {
+        self.shader.bind(gl);
+        input.bind(gl, 0);
+        self.shader.set_int(gl, "screenTexture", 0);
+        quad.draw(gl);
+    }
+
+    fn delete(self: Box<Self>, gl: &GlContext) {
+        self.shader.delete(gl);
+    }
+}
+
+/// Smooths jagged edges with a single-pass approximation of FXAA.
+pub struct FxaaPass {
+    shader: Shader,
+}
+
+impl FxaaPass {
+    /// Compile the FXAA shader.
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the shader cannot be loaded
+    pub fn new(gl: &GlContext, api: GlApi) -> TemplateResult<Self> {
+        let shader = Shader::with_preamble(gl, "fullscreen.vert", "fxaa.frag", Some(api.shader_preamble()))?;
+        Ok(Self { shader })
+    }
+}
+
+impl RenderPass for FxaaPass {
+    fn execute(&mut self, gl: &GlContext, quad: &FullscreenQuad, input: &Texture, _output: Option<&Framebuffer>) {
+        self.shader.bind(gl);
+        input.bind(gl, 0);
+        self.shader.set_int(gl, "screenTexture", 0);
+        quad.draw(gl);
+    }
+
+    fn delete(self: Box<Self>, gl: &GlContext) {
+        self.shader.delete(gl);
+    }
+}
+
+/// Shows a depth texture as grayscale, from black at the near plane to white at the far plane.
+/// 
+/// Run it with a depth texture as input (see `Framebuffer::with_depth_texture`). Depth is
+/// linearized first, so gray levels are proportional to distance; this makes precision
+/// loss and z-fighting far from the camera visible. Assumes a standard (not reverse-Z)
+/// perspective projection.
+pub struct DepthVisualizePass {
+    shader: Shader,
+    near: f32,
+    far: f32,
+}
+
+impl DepthVisualizePass {
+    /// Compile the depth visualization shader.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `api` - Graphics API, used to pick the shader preamble
+    /// * `near` - Near plane distance of the projection the depth was rendered with
+    /// * `far` - Far plane distance of that projection
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the shader cannot be loaded
+    pub fn new(gl: &GlContext, api: GlApi, near: f32, far: f32) -> TemplateResult<Self> {
+        let shader = Shader::with_preamble(gl, "fullscreen.vert", "depth_visualize.frag", Some(api.shader_preamble()))?;
+        Ok(Self { shader, near, far })
+    }
+
+    /// Update the clip distances after the projection changed.
+    pub fn set_range(&mut self, near: f32, far: f32) {
+        self.near = near;
+        self.far = far;
+    }
+}
+
+impl RenderPass for DepthVisualizePass {
+    fn execute(&mut self, gl: &GlContext, quad: &FullscreenQuad, input: &Texture, _output: Option<&Framebuffer>) {
+        self.shader.bind(gl);
+        input.bind(gl, 0);
+        self.shader.set_int(gl, "depthTexture", 0);
+        self.shader.set_float(gl, "near", self.near);
+        self.shader.set_float(gl, "far", self.far);
+        quad.draw(gl);
+    }
+
+    fn delete(self: Box<Self>, gl: &GlContext) {
+        self.shader.delete(gl);
+    }
+}
+
+/// Remaps colors through a 3D lookup table (color grading).
+pub struct LutPass {
+    shader: Shader,
+    lut: Texture3D,
+}
+
+impl LutPass {
+    /// Compile the LUT shader and take ownership of the lookup table.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `api` - Graphics API, used to pick the shader preamble
+    /// * `lut` - Lookup table, e.g. from `Texture3D::load_lut`
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the shader cannot be loaded
+    pub fn new(gl: &GlContext, api: GlApi, lut: Texture3D) -> TemplateResult<Self> {
+        let shader = Shader::with_preamble(gl, "fullscreen.vert", "lut.frag", Some(api.shader_preamble()))?;
+        Ok(Self { shader, lut })
+    }
+}
+
+impl RenderPass for LutPass {
+    fn execute(&mut self, gl: &GlContext, quad: &FullscreenQuad, input: &Texture, _output: Option<&Framebuffer>) {
+        self.shader.bind(gl);
+        input.bind(gl, 0);
+        self.lut.bind(gl, 1);
+        self.shader.set_int(gl, "screenTexture", 0);
+        self.shader.set_int(gl, "lut", 1);
+        quad.draw(gl);
+        unsafe {
+            gl.active_texture(glow::TEXTURE0);
+        }
+    }
+
+    fn delete(self: Box<Self>, gl: &GlContext) {
+        self.shader.delete(gl);
+        self.lut.delete(gl);
+    }
+}
+
+/// Runs a list of passes in order, ping-ponging between two intermediate framebuffers.
+/// 
+/// Passes can be switched off individually; disabled passes are skipped.
+pub struct PostProcessChain {
+    passes: Vec<(Box<dyn RenderPass>, bool)>,
+    targets: [Framebuffer; 2],
+    format: ColorFormat,
+    quad: FullscreenQuad,
+    width: u32,
+    height: u32,
+}
+
+impl PostProcessChain {
+    /// Create an empty chain with intermediate targets of the given size.
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` or `TemplateError::Texture` if the targets or the fullscreen
+    /// triangle cannot be created
+    pub fn new(gl: &GlContext, width: u32, height: u32) -> TemplateResult<Self> {
+        Ok(Self {
+            passes: Vec::new(),
+            targets: [Framebuffer::new(gl, width, height)?, Framebuffer::new(gl, width, height)?],
+            format: ColorFormat::Rgba8,
+            quad: FullscreenQuad::new(gl)?,
+            width,
+            height,
+        })
+    }
+
+    /// Append an enabled pass to the end of the chain.
+    pub fn add_pass(&mut self, pass: impl RenderPass + 'static) {
+        self.passes.push((Box::new(pass), true));
+    }
+
+    /// Enable or disable the pass at `index` (in the order passes were added).
+    pub fn set_enabled(&mut self, index: usize, enabled: bool) {
+        if let Some((_, pass_enabled)) = self.passes.get_mut(index) {
+            *pass_enabled = enabled;
+        }
+    }
+
+    /// Check whether the pass at `index` exists and is enabled.
+    #[must_use]
+    pub fn is_enabled(&self, index: usize) -> bool {
+        self.passes.get(index).is_some_and(|(_, enabled)| *enabled)
+    }
+
+    /// Check whether running the chain would draw anything.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.passes.iter().any(|(_, enabled)| *enabled)
+    }
+
+    /// Get the number of passes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.passes.len()
+    }
+
+    /// Check whether the chain has no passes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.passes.is_empty()
+    }
+
+    /// Recreate the intermediate targets for a new output size.
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` or `TemplateError::Texture` if the new targets cannot be created
+    pub fn resize(&mut self, gl: &GlContext, width: u32, height: u32) -> TemplateResult<()> {
+        if (width, height) == (self.width, self.height) {
+            return Ok(());
+        }
+        
+        tracing::debug!("Resizing post-process chain to {}x{}", width, height);
+        self.recreate_targets(gl, width, height, self.format)
+    }
+
+    /// Choose the format of the intermediate targets, recreating them if it changed.
+    /// 
+    /// Use `ColorFormat::Srgb8Alpha8` when the passes work in linear color, so values
+    /// between passes keep their precision in the darks. The last pass still writes to
+    /// the window unconverted (see `Framebuffer::unbind`).
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` or `TemplateError::Texture` if the new targets cannot be created
+    pub fn set_color_format(&mut self, gl: &GlContext, format: ColorFormat) -> TemplateResult<()> {
+        if format == self.format {
+            return Ok(());
+        }
+        self.recreate_targets(gl, self.width, self.height, format)
+    }
+
+    fn recreate_targets(&mut self, gl: &GlContext, width: u32, height: u32, format: ColorFormat) -> TemplateResult<()> {
+        let [first, second] = std::mem::replace(
+            &mut self.targets,
+            [Framebuffer::with_format(gl, width, height, format)?, Framebuffer::with_format(gl, width, height, format)?],
+        );
+        first.delete(gl);
+        second.delete(gl);
+        self.width = width;
+        self.height = height;
+        self.format = format;
+        Ok(())
+    }
+
+    /// Run every enabled pass, feeding each one the previous pass's output.
+    /// 
+    /// Nothing is drawn when no pass is enabled.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `input` - Texture the first pass reads (usually the rendered scene)
+    /// * `output` - Framebuffer the last pass writes, or `None` for the window
+    pub fn run(&mut self, gl: &GlContext, input: &Texture, output: Option<&Framebuffer>) {
+        let mut enabled: Vec<_> = self.passes.iter_mut().filter(|(_, enabled)| *enabled).map(|(pass, _)| pass).collect();
+        let _span = tracing::debug_span!("post_process", passes = enabled.len()).entered();
+        
+        if enabled.is_empty() {
+            return;
+        }
+        
+        unsafe {
+            gl.disable(glow::DEPTH_TEST);
+        }
+        
+        let last = enabled.len() - 1;
+        let mut source: Option<usize> = None;
+        
+        for (index, pass) in enabled.iter_mut().enumerate() {
+            let input_texture = source.map_or(input, |target| self.targets[target].color_texture());
+            
+            if index == last {
+                if let Some(framebuffer) = output {
+                    framebuffer.bind(gl);
+                } else {
+                    unsafe {
+                        Framebuffer::unbind(gl);
+                        gl.viewport(0, 0, gl_size(self.width), gl_size(self.height));
+                    }
+                }
+                pass.execute(gl, &self.quad, input_texture, output);
+            } else {
+                let target = source.map_or(0, |previous| 1 - previous);
+                self.targets[target].bind(gl);
+                pass.execute(gl, &self.quad, input_texture, Some(&self.targets[target]));
+                source = Some(target);
+            }
+        }
+        
+        unsafe {
+            gl.enable(glow::DEPTH_TEST);
+        }
+    }
+
+    /// Release the passes and the intermediate targets.
+    pub fn delete(self, gl: &GlContext) {
+        for (pass, _) in self.passes {
+            pass.delete(gl);
+        }
+        let [first, second] = self.targets;
+        first.delete(gl);
+        second.delete(gl);
+        self.quad.delete(gl);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,165 @@
+//! Visual regression checks comparing rendered frames against reference images.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::Renderer;
+use image::{Rgba, RgbaImage};
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Result of comparing two images pixel by pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameComparison {
+    /// Pixels with at least one channel differing by more than the tolerance.
+    pub mismatched_pixels: usize,
+    /// Largest difference of any channel in any pixel.
+    pub max_difference: u8,
+}
+
+impl FrameComparison {
+    /// Check whether every pixel was within the tolerance.
+    #[must_use]
+    pub fn matches(&self) -> bool {
+        self.mismatched_pixels == 0
+    }
+}
+
+// ============================================================
+// ==================== Global Functions ======================
+// ============================================================
+
+/// Compare two images of the same size.
+/// 
+/// Drivers round blending and interpolation differently, so exact equality is too strict.
+/// A pixel only counts as mismatched if one of its channels differs by more than `tolerance`;
+/// a tolerance of 2 to 4 usually absorbs driver noise while catching real changes.
+/// 
+/// # Arguments
+/// * `actual` - Rendered image
+/// * `reference` - Expected image
+/// * `tolerance` - Largest accepted per-channel difference
+/// 
+/// # Returns
+/// The comparison, or `None` if the sizes differ
+#[must_use]
+pub fn compare_images(actual: &RgbaImage, reference: &RgbaImage, tolerance: u8) -> Option<FrameComparison> {
+    if actual.dimensions() != reference.dimensions() {
+        return None;
+    }
+    
+    let mut comparison = FrameComparison { mismatched_pixels: 0, max_difference: 0 };
+    for (&a, &b) in actual.pixels().zip(reference.pixels()) {
+        let difference = channel_difference(a, b);
+        comparison.max_difference = comparison.max_difference.max(difference);
+        if difference > tolerance {
+            comparison.mismatched_pixels += 1;
+        }
+    }
+    Some(comparison)
+}
+
+/// Build an image highlighting mismatched pixels in red over a dimmed copy of the reference.
+/// 
+/// # Arguments
+/// * `actual` - Rendered image
+/// * `reference` - Expected image of the same size
+/// * `tolerance` - Largest accepted per-channel difference
+#[must_use]
+pub fn diff_image(actual: &RgbaImage, reference: &RgbaImage, tolerance: u8) -> RgbaImage {
+    RgbaImage::from_fn(reference.width(), reference.height(), |x, y| {
+        let expected = reference.get_pixel(x, y);
+        if channel_difference(*actual.get_pixel(x, y), *expected) > tolerance {
+            Rgba([255, 0, 0, 255])
+        } else {
+            let gray = u8::try_from((u16::from(expected[0]) + u16::from(expected[1]) + u16::from(expected[2])) / 12).unwrap_or(u8::MAX);
+            Rgba([gray, gray, gray, 255])
+        }
+    })
+}
+
+/// Compare the frame currently in the renderer's back buffer against a reference PNG.
+/// 
+/// Call it after drawing and before `present`. On a mismatch the rendered frame is saved
+/// next to the reference as `<name>.actual.png` and the highlighted differences as
+/// `<name>.diff.png`. If the reference does not exist yet, only `.actual.png` is written,
+/// so a first run produces an image to review and rename.
+/// 
+/// # Arguments
+/// * `renderer` - Renderer whose current frame is checked
+/// * `reference_png` - Path to the expected image
+/// * `tolerance` - Largest accepted per-channel difference (see `compare_images`)
+/// 
+/// # Panics
+/// If the frame does not match the reference, so it can be used directly in tests
+pub fn assert_frame_matches(renderer: &Renderer, reference_png: &Path, tolerance: u8) {
+    if let Err(e) = check_frame(renderer, reference_png, tolerance) {
+        panic!("{}", e);
+    }
+}
+
+// ============================================================
+// ==================== Helper Functions ======================
+// ============================================================
+
+/// Run the comparison behind `assert_frame_matches`, returning the failure as an error.
+fn check_frame(renderer: &Renderer, reference_png: &Path, tolerance: u8) -> TemplateResult<()> {
+    let actual = renderer.read_pixels();
+    let actual_path = sibling_path(reference_png, "actual");
+    let save = |image: &RgbaImage, path: &Path| {
+        image
+            .save(path)
+            .map_err(|e| TemplateError::Io(io::Error::other(format!("failed to write {}: {}", path.display(), e))))
+    };
+    
+    if !reference_png.exists() {
+        save(&actual, &actual_path)?;
+        return Err(TemplateError::Io(io::Error::new(io::ErrorKind::NotFound, format!(
+            "reference image {} does not exist; wrote the rendered frame to {}",
+            reference_png.display(), actual_path.display()
+        ))));
+    }
+    
+    let reference = image::open(reference_png)
+        .map_err(|e| TemplateError::Io(io::Error::other(format!("failed to read {}: {}", reference_png.display(), e))))?
+        .into_rgba8();
+    
+    let Some(comparison) = compare_images(&actual, &reference, tolerance) else {
+        save(&actual, &actual_path)?;
+        return Err(TemplateError::FrameMismatch(format!(
+            "frame is {}x{} but {} is {}x{}",
+            actual.width(), actual.height(), reference_png.display(), reference.width(), reference.height()
+        )));
+    };
+    
+    if comparison.matches() {
+        tracing::debug!("Frame matches {} (max difference {})", reference_png.display(), comparison.max_difference);
+        return Ok(());
+    }
+    
+    let diff_path = sibling_path(reference_png, "diff");
+    save(&actual, &actual_path)?;
+    save(&diff_image(&actual, &reference, tolerance), &diff_path)?;
+    Err(TemplateError::FrameMismatch(format!(
+        "{} pixels differ from {} by more than {} (max {}); see {}",
+        comparison.mismatched_pixels, reference_png.display(), tolerance, comparison.max_difference, diff_path.display()
+    )))
+}
+
+/// Largest absolute difference between the channels of two pixels.
+fn channel_difference(a: Rgba<u8>, b: Rgba<u8>) -> u8 {
+    a.0.iter().zip(b.0.iter()).map(|(x, y)| x.abs_diff(*y)).max().unwrap_or(0)
+}
+
+/// Turn `dir/name.png` into `dir/name.<suffix>.png`.
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().map_or_else(|| "frame".into(), |stem| stem.to_string_lossy());
+    path.with_file_name(format!("{stem}.{suffix}.png"))
+}
\ No newline at end of file
@@ -4,20 +4,61 @@
 // ========================= Imports ==========================
 // ============================================================
 
-use template_core::TemplateResult;
-use crate::{Window, GlContext, GlContextBuilder};
+use template_core::{TemplateResult, TemplateError};
+use crate::{Window, WindowEventLoop, GlApi, GlContext, GlContextBuilder, Mesh, Shader, Camera, CameraProjection, SceneItem, Gizmos, ClipRect, GlCapabilities, RenderState, conservative_raster_capability, supports_viewport_array, Background, GradientBackground, FrontFace, TextRenderer, DebugLines, GpuTimer, IdBuffer, OverdrawView, GlStateGuard};
+use crate::context::{gl_count, gl_size};
 use glow::HasContext;
-use winit::event_loop::EventLoop;
+use nalgebra::{Matrix4, Perspective3, Point3};
+use std::cell::{Cell, RefCell, RefMut};
 use tracing;
 
 // ============================================================
 // ===================== Structs & Impls ======================
 // ============================================================
 
+/// Per-frame measurements collected by the renderer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RenderStats {
+    /// GPU time of a recent frame in nanoseconds, from `clear` to `present`.
+    /// 
+    /// Lags a frame behind and stays 0 without timer query support.
+    pub gpu_time_ns: u64,
+}
+
+/// How draw operations leave the GL state they touched.
+#[derive(Debug, Clone, Copy, Default)]
+struct DrawOptions {
+    /// Unbind the shader after `render_scene`.
+    unbind_after_draw: bool,
+    /// Save and restore the GL state around draws.
+    interop: bool,
+}
+
 /// Main renderer that coordinates window, context, and rendering operations.
+/// 
+/// GPU objects created from `gl` (meshes, shaders, textures, ...) must be deleted before the
+/// renderer is dropped, since dropping it destroys the context they live in. The renderer
+/// releases its own cached objects when dropped.
 pub struct Renderer {
     pub window: Window,
     pub gl: GlContext,
+    builder: GlContextBuilder,
+    state: RenderState,
+    draw_options: DrawOptions,
+    conservative_raster: bool,
+    idle_when_static: bool,
+    dirty: Cell<bool>,
+    background: Background,
+    gradient: RefCell<Option<GradientBackground>>,
+    size: Cell<(u32, u32)>,
+    gizmos: RefCell<Option<Gizmos>>,
+    text: RefCell<Option<TextRenderer>>,
+    debug_lines: RefCell<DebugLines>,
+    gpu_timer: RefCell<GpuTimer>,
+    id_buffer: RefCell<Option<IdBuffer>>,
+    overdraw: RefCell<Option<OverdrawView>>,
+    wireframe_edges: RefCell<Option<Mesh>>,
+    clip_stack: RefCell<Vec<ClipRect>>,
 }
 
 impl Renderer {
@@ -27,41 +68,409 @@ impl Renderer {
     /// * `width` - Window width in pixels
     /// * `height` - Window height in pixels  
     /// * `title` - Window title
-    /// * `event_loop` - Winit event loop
+    /// * `event_loop` - Winit event loop (before or while running)
     /// 
     /// # Returns
     /// A configured renderer ready for use
-    pub fn new(width: u32, height: u32, title: &str, event_loop: &EventLoop<()>) -> TemplateResult<Self> {
+    /// 
+    /// # Errors
+    /// `TemplateError::WindowCreation` if the window or its OpenGL context cannot be created
+    pub fn new(width: u32, height: u32, title: &str, event_loop: &impl WindowEventLoop) -> TemplateResult<Self> {
+        Self::with_api(width, height, title, GlApi::default(), event_loop)
+    }
+
+    /// Create a new renderer targeting a specific graphics API.
+    /// 
+    /// # Arguments
+    /// * `width` - Window width in pixels
+    /// * `height` - Window height in pixels  
+    /// * `title` - Window title
+    /// * `api` - Desktop OpenGL or OpenGL ES
+    /// * `event_loop` - Winit event loop (before or while running)
+    /// 
+    /// # Errors
+    /// `TemplateError::WindowCreation` if the window or a context for `api` cannot be created
+    pub fn with_api(width: u32, height: u32, title: &str, api: GlApi, event_loop: &impl WindowEventLoop) -> TemplateResult<Self> {
+        Self::with_builder(width, height, title, GlContextBuilder::new().with_api(api), event_loop)
+    }
+
+    /// Create a new renderer from a configured context builder (API, swap mode, visibility).
+    /// 
+    /// # Arguments
+    /// * `width` - Window width in pixels
+    /// * `height` - Window height in pixels  
+    /// * `title` - Window title
+    /// * `builder` - Context settings
+    /// * `event_loop` - Winit event loop (before or while running)
+    /// 
+    /// # Errors
+    /// `TemplateError::WindowCreation` if the window or context cannot be created, or
+    /// `TemplateError::OpenGL` if the GPU timer queries cannot be created
+    pub fn with_builder(width: u32, height: u32, title: &str, builder: GlContextBuilder, event_loop: &impl WindowEventLoop) -> TemplateResult<Self> {
         tracing::info!("Initializing renderer {}x{}", width, height);
         
-        let window = Window::new(width, height, title, event_loop)?;
-        let gl = GlContextBuilder::new().build(&window)?;
+        let window = Window::new(width, height, title, builder.api(), builder.swap_mode(), builder.visible(), event_loop)?;
+        let gl = builder.build(&window)?;
         
-        // Configure OpenGL state
-        unsafe {
-            gl.enable(glow::DEPTH_TEST);
-            gl.enable(glow::CULL_FACE);
-            gl.cull_face(glow::BACK);
-            gl.clear_color(0.2, 0.3, 0.3, 1.0); // Dark teal background
-        }
+        let state = RenderState::default();
+        state.apply(&gl);
         
+        tracing::debug!("{:?}", GlCapabilities::query(&gl));
         tracing::info!("Renderer initialized successfully");
         
-        Ok(Self { window, gl })
+        let initial_size = window.handle().inner_size();
+        let gpu_timer = GpuTimer::new(&gl)?;
+        
+        Ok(Self {
+            window,
+            gl,
+            builder,
+            state,
+            draw_options: DrawOptions::default(),
+            conservative_raster: false,
+            idle_when_static: false,
+            dirty: Cell::new(true),
+            background: Background::Solid(state.clear_color),
+            gradient: RefCell::new(None),
+            size: Cell::new((initial_size.width, initial_size.height)),
+            gizmos: RefCell::new(None),
+            text: RefCell::new(None),
+            debug_lines: RefCell::new(DebugLines::new()),
+            gpu_timer: RefCell::new(gpu_timer),
+            id_buffer: RefCell::new(None),
+            overdraw: RefCell::new(None),
+            wireframe_edges: RefCell::new(None),
+            clip_stack: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Recreate the OpenGL context after it was lost (e.g. GPU switch or display sleep).
+    /// 
+    /// The new context uses the API, swap mode and visibility the renderer was built with.
+    /// Only the renderer's own state is restored. Meshes, shaders and other GL objects
+    /// created by the application belong to the old context and must be recreated by the caller.
+    /// 
+    /// # Errors
+    /// `TemplateError::WindowCreation` or `TemplateError::OpenGL` if the new context or the
+    /// objects the renderer owns in it cannot be created
+    pub fn recreate_context(&mut self) -> TemplateResult<()> {
+        self.window.recreate_context()?;
+        // Same API, swap mode and visibility as the context being replaced
+        self.gl = self.builder.build(&self.window)?;
+        crate::leaks::track_context_lost();
+        
+        self.apply_state();
+        // Cached objects belong to the dead context, so they are dropped without deleting
+        self.gizmos.replace(None);
+        self.text.replace(None);
+        self.debug_lines.replace(DebugLines::new());
+        self.gpu_timer.replace(GpuTimer::new(&self.gl)?);
+        self.id_buffer.replace(None);
+        self.overdraw.replace(None);
+        self.wireframe_edges.replace(None);
+        self.gradient.replace(None);
+        self.clip_stack.borrow_mut().clear();
+        self.set_background(self.background)?;
+        
+        let (width, height) = self.size.get();
+        self.resize(width, height);
+        Ok(())
+    }
+
+    /// Re-apply the stored render state (depth test, face culling, clear color).
+    /// 
+    /// Call this after foreign GL code or a driver event may have reset global state.
+    pub fn apply_state(&self) {
+        self.state.apply(&self.gl);
+        if self.conservative_raster {
+            if let Some(capability) = conservative_raster_capability(&self.gl) {
+                unsafe {
+                    self.gl.enable(capability);
+                }
+            }
+        }
+    }
+
+    /// Get the render state the renderer keeps applied.
+    pub fn state(&self) -> RenderState {
+        self.state
+    }
+
+    /// Replace the stored render state and apply it immediately.
+    /// 
+    /// # Arguments
+    /// * `state` - New depth, culling and clear settings
+    pub fn set_state(&mut self, state: RenderState) {
+        self.state = state;
+        if let Background::Solid(_) = self.background {
+            self.background = Background::Solid(state.clear_color);
+        }
+        self.apply_state();
+    }
+
+    /// Set which triangle winding order is front-facing.
+    /// 
+    /// Back faces are culled, so a model with clockwise triangles renders inside-out or
+    /// not at all under the default counter-clockwise setting. Flip it for such models.
+    /// 
+    /// # Arguments
+    /// * `front_face` - `FrontFace::Ccw` (default) or `FrontFace::Cw`
+    pub fn set_front_face(&mut self, front_face: FrontFace) {
+        self.state.front_face = front_face;
+        self.apply_state();
+    }
+
+    /// Set the depth value `clear` resets the depth buffer to.
+    /// 
+    /// For a reverse-Z depth buffer (better precision over long distances), clear to 0.0
+    /// and pair it with `set_depth_func(glow::GREATER)`, using a projection that maps the
+    /// near plane to 1 and the far plane to 0. Changing only one of the two makes every
+    /// fragment fail the depth test.
+    /// 
+    /// # Arguments
+    /// * `depth` - Clear depth in `[0, 1]` (default 1.0)
+    pub fn set_clear_depth(&mut self, depth: f32) {
+        self.state.clear_depth = depth.clamp(0.0, 1.0);
+        self.apply_state();
+    }
+
+    /// Set the comparison fragments must pass against the depth buffer.
+    /// 
+    /// See `set_clear_depth` for the reverse-Z pairing.
+    /// 
+    /// # Arguments
+    /// * `func` - `glow::LESS` (default), `glow::GREATER`, `glow::LEQUAL`, ...
+    pub fn set_depth_func(&mut self, func: u32) {
+        self.state.depth_func = func;
+        self.apply_state();
+    }
+
+    /// Choose whether `render_scene` unbinds its shader program when it finishes.
+    /// 
+    /// Off by default. Enable it when other GL code (such as a UI overlay) draws after the
+    /// scene and expects no program to be bound.
+    /// 
+    /// # Arguments
+    /// * `enabled` - Whether to call `Shader::unbind` after drawing
+    pub fn set_unbind_after_draw(&mut self, enabled: bool) {
+        self.draw_options.unbind_after_draw = enabled;
+    }
+
+    /// Choose whether draw operations restore the GL state they found.
+    /// 
+    /// Off by default. Enable it when other GL code (egui, a host application) shares the
+    /// context: `render_scene`, `render_ids`, the gizmo, text and debug draws then save
+    /// bindings, blending, depth and culling state in a `GlStateGuard` and restore it when
+    /// they finish. Reading state back costs some CPU time per call.
+    /// 
+    /// # Arguments
+    /// * `enabled` - Whether to save and restore state around draws
+    pub fn set_interop_mode(&mut self, enabled: bool) {
+        self.draw_options.interop = enabled;
+    }
+
+    /// Save the GL state for restoring at the end of a draw operation in interop mode.
+    fn interop_guard(&self) -> Option<GlStateGuard<'_>> {
+        self.draw_options.interop.then(|| GlStateGuard::new(&self.gl))
+    }
+
+    /// Toggle conservative rasterization, which shades every pixel a triangle touches.
+    /// 
+    /// Useful for voxelization and exact coverage tests. Needs `GL_NV_conservative_raster`
+    /// or `GL_INTEL_conservative_rasterization`; check `capabilities().conservative_raster`.
+    /// 
+    /// # Arguments
+    /// * `enabled` - Whether to rasterize conservatively
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` when enabling it on a driver without either extension
+    pub fn set_conservative_raster(&mut self, enabled: bool) -> TemplateResult<()> {
+        let Some(capability) = conservative_raster_capability(&self.gl) else {
+            if enabled {
+                return Err(TemplateError::OpenGL("conservative rasterization is not supported by this driver".to_string()));
+            }
+            return Ok(());
+        };
+        
+        unsafe {
+            if enabled {
+                self.gl.enable(capability);
+            } else {
+                self.gl.disable(capability);
+            }
+        }
+        self.conservative_raster = enabled;
+        Ok(())
+    }
+
+    /// Check whether conservative rasterization is currently enabled.
+    pub fn conservative_raster(&self) -> bool {
+        self.conservative_raster
+    }
+
+    /// Query the driver's limits (texture size, MSAA samples, attributes, texture units, uniform blocks).
+    pub fn capabilities(&self) -> GlCapabilities {
+        GlCapabilities::query(&self.gl)
+    }
+
+    /// Get the graphics API this renderer was created for.
+    pub fn api(&self) -> GlApi {
+        self.builder.api()
+    }
+
+    /// Get the id of the window this renderer draws into.
+    pub fn id(&self) -> winit::window::WindowId {
+        self.window.id()
+    }
+
+    /// Make this renderer's context current so subsequent GL calls target its window.
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the context cannot be made current
+    pub fn make_current(&self) -> TemplateResult<()> {
+        self.window.make_current()
     }
 
-    /// Clear the color and depth buffers.
+    /// Clear the color and depth buffers and draw the background.
+    /// 
+    /// The first clear of a frame also starts GPU timing, which `present` stops.
     pub fn clear(&self) {
+        self.gpu_timer.borrow_mut().begin(&self.gl);
         unsafe {
             self.gl.clear(glow::COLOR_BUFFER_BIT | glow::DEPTH_BUFFER_BIT);
         }
+        
+        if let Background::Gradient { top, bottom } = self.background {
+            if let Some(gradient) = self.gradient.borrow_mut().as_mut() {
+                gradient.draw(&self.gl, top, bottom);
+            }
+        }
+    }
+
+    /// Clear only a rectangle of the current framebuffer, e.g. a UI panel that is redrawn on its own.
+    /// 
+    /// Uses the scissor test and restores the previous scissor enable state and box
+    /// afterwards, so active clip rectangles are unaffected. A gradient background is not
+    /// redrawn; the region is filled with the clear color.
+    /// 
+    /// # Arguments
+    /// * `x` - Left edge in window pixels
+    /// * `y` - Top edge in window pixels (y down)
+    /// * `width` - Rectangle width
+    /// * `height` - Rectangle height
+    /// * `flags` - Buffers to clear, e.g. `glow::COLOR_BUFFER_BIT | glow::DEPTH_BUFFER_BIT`
+    pub fn clear_region(&self, x: i32, y: i32, width: u32, height: u32, flags: u32) {
+        unsafe {
+            let was_enabled = self.gl.is_enabled(glow::SCISSOR_TEST);
+            let mut previous_box = [0; 4];
+            self.gl.get_parameter_i32_slice(glow::SCISSOR_BOX, &mut previous_box);
+            
+            self.apply_scissor(Some(ClipRect::new(x, y, width, height)));
+            self.gl.clear(flags);
+            
+            let [box_x, box_y, box_width, box_height] = previous_box;
+            self.gl.scissor(box_x, box_y, box_width, box_height);
+            if !was_enabled {
+                self.gl.disable(glow::SCISSOR_TEST);
+            }
+        }
     }
 
-    /// Present the rendered frame.
+    /// Choose what `clear` fills the frame with.
+    /// 
+    /// A solid background becomes the clear color. A gradient is drawn as a fullscreen
+    /// triangle after clearing, with depth testing and depth writes off so the scene
+    /// always renders on top.
+    /// 
+    /// # Arguments
+    /// * `background` - Solid color or vertical gradient
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` or `TemplateError::OpenGL` if the gradient shader or
+    /// triangle cannot be created
+    pub fn set_background(&mut self, background: Background) -> TemplateResult<()> {
+        match background {
+            Background::Solid(color) => {
+                self.state.clear_color = color;
+                self.apply_state();
+            }
+            Background::Gradient { .. } => {
+                if self.gradient.get_mut().is_none() {
+                    *self.gradient.get_mut() = Some(GradientBackground::new(&self.gl, self.api())?);
+                }
+            }
+        }
+        self.background = background;
+        Ok(())
+    }
+
+    /// Present the rendered frame and clear the debug line batch for the next one.
     pub fn present(&self) -> TemplateResult<()> {
+        self.gpu_timer.borrow_mut().end(&self.gl);
+        self.debug_lines.borrow_mut().clear();
         self.window.swap_buffers()
     }
 
+    /// Present the rendered frame, marking only the given window regions as changed.
+    /// 
+    /// See `Window::swap_buffers_with_damage`; the frame must still be fully rendered.
+    /// 
+    /// # Arguments
+    /// * `rects` - Changed regions as `(x, y, width, height)` in window pixels, y down
+    /// 
+    /// # Errors
+    /// `TemplateError::ContextLost` if the context was lost, or `TemplateError::OpenGL` if
+    /// the buffers cannot be swapped
+    pub fn present_with_damage(&self, rects: &[(i32, i32, i32, i32)]) -> TemplateResult<()> {
+        self.gpu_timer.borrow_mut().end(&self.gl);
+        self.debug_lines.borrow_mut().clear();
+        self.window.swap_buffers_with_damage(rects)
+    }
+
+    /// Get the measurements of recent frames.
+    pub fn stats(&self) -> RenderStats {
+        RenderStats {
+            gpu_time_ns: self.gpu_timer.borrow().elapsed_ns(),
+        }
+    }
+
+    /// Get the per-frame debug line batch.
+    /// 
+    /// Lines added before `render_scene` are drawn with the scene; the batch is cleared
+    /// by `present`.
+    pub fn debug_lines(&self) -> RefMut<'_, DebugLines> {
+        self.debug_lines.borrow_mut()
+    }
+
+    /// Schedule another frame for this renderer's window.
+    /// 
+    /// With `set_idle_when_static` enabled, a frame is only scheduled if `invalidate` was
+    /// called since the last request, so a static scene lets the event loop sleep.
+    pub fn request_redraw(&self) {
+        if !self.idle_when_static || self.dirty.replace(false) {
+            self.window.request_redraw();
+        }
+    }
+
+    /// Mark the frame as out of date so the next `request_redraw` schedules a redraw.
+    /// 
+    /// Call it on input, resizes and whenever the camera or a transform changed.
+    pub fn invalidate(&self) {
+        self.dirty.set(true);
+    }
+
+    /// Choose whether `request_redraw` skips frames while nothing was invalidated.
+    /// 
+    /// Off by default, which redraws continuously. Turning it on saves power for mostly
+    /// static scenes, at the cost of having to call `invalidate` whenever something changes.
+    /// 
+    /// # Arguments
+    /// * `enabled` - Whether to stop redrawing while the scene is static
+    pub fn set_idle_when_static(&mut self, enabled: bool) {
+        self.idle_when_static = enabled;
+        self.dirty.set(true);
+    }
+
     /// Update viewport when window is resized.
     /// 
     /// # Arguments
@@ -69,8 +478,718 @@ impl Renderer {
     /// * `height` - New viewport height
     pub fn resize(&self, width: u32, height: u32) {
         tracing::debug!("Resizing viewport to {}x{}", width, height);
+        self.size.set((width, height));
         unsafe {
             self.gl.viewport(0, 0, width as i32, height as i32);
         }
     }
+
+    /// Restrict rendering to a sub-rectangle of the window without changing the stored size.
+    /// 
+    /// Call `resize` with the window size (or `reset_viewport`) to cover the whole window again.
+    /// 
+    /// # Arguments
+    /// * `x` - Left edge in pixels
+    /// * `y` - Bottom edge in pixels (GL convention, y up)
+    /// * `width` - Viewport width
+    /// * `height` - Viewport height
+    pub fn set_viewport(&self, x: i32, y: i32, width: u32, height: u32) {
+        unsafe {
+            self.gl.viewport(x, y, gl_size(width), gl_size(height));
+        }
+    }
+
+    /// Set several viewports at once for a geometry shader that picks one per primitive.
+    /// 
+    /// Viewport `i` is used by primitives whose geometry shader writes `gl_ViewportIndex = i`,
+    /// so one draw call can fill every cubemap face or shadow cascade.
+    /// 
+    /// # Arguments
+    /// * `viewports` - `(x, y, width, height)` per viewport, bottom-left origin
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` without viewport array support or with more than `MAX_VIEWPORTS` entries
+    #[allow(clippy::cast_precision_loss)]
+    pub fn set_viewport_array(&self, viewports: &[(i32, i32, u32, u32)]) -> TemplateResult<()> {
+        if !supports_viewport_array(&self.gl) {
+            return Err(TemplateError::OpenGL("viewport arrays need OpenGL 4.1 or GL_ARB_viewport_array".to_string()));
+        }
+        let max_viewports = self.capabilities().max_viewports as usize;
+        if viewports.len() > max_viewports {
+            return Err(TemplateError::OpenGL(format!(
+                "{} viewports requested but MAX_VIEWPORTS is {}",
+                viewports.len(), max_viewports
+            )));
+        }
+        
+        let values: Vec<[f32; 4]> = viewports
+            .iter()
+            .map(|&(x, y, width, height)| [x as f32, y as f32, width as f32, height as f32])
+            .collect();
+        unsafe {
+            self.gl.viewport_f32_slice(0, gl_count(values.len()), &values);
+        }
+        Ok(())
+    }
+
+    /// Draw into several viewports, in one pass where viewport arrays are supported.
+    /// 
+    /// With support, the viewports are set with `set_viewport_array` and `draw` runs once
+    /// with `None`; its shader must route primitives with `gl_ViewportIndex`. Otherwise,
+    /// or if there are too many viewports, `draw` runs once per viewport with `Some(index)`
+    /// and that viewport set, so it can draw the matching view with an ordinary shader.
+    /// The full-window viewport is restored afterwards.
+    /// 
+    /// # Arguments
+    /// * `viewports` - `(x, y, width, height)` per viewport, bottom-left origin
+    /// * `draw` - Issues the draw calls for all viewports (`None`) or one viewport (`Some`)
+    pub fn draw_viewports(&self, viewports: &[(i32, i32, u32, u32)], mut draw: impl FnMut(Option<usize>)) {
+        match self.set_viewport_array(viewports) {
+            Ok(()) => draw(None),
+            Err(e) => {
+                tracing::debug!("Drawing viewports one at a time: {}", e);
+                for (index, &(x, y, width, height)) in viewports.iter().enumerate() {
+                    self.set_viewport(x, y, width, height);
+                    draw(Some(index));
+                }
+            }
+        }
+        self.reset_viewport();
+    }
+
+    /// Reset the viewport to cover the whole window.
+    pub fn reset_viewport(&self) {
+        let (width, height) = self.size.get();
+        self.set_viewport(0, 0, width, height);
+    }
+
+    /// Get the current viewport size in physical pixels.
+    pub fn size(&self) -> (u32, u32) {
+        self.size.get()
+    }
+
+    /// Submit all queued GL commands to the GPU without waiting for them to finish.
+    /// 
+    /// Useful before handing a frame to another context or process, or to start GPU work
+    /// early while the CPU does something else.
+    pub fn flush(&self) {
+        unsafe {
+            self.gl.flush();
+        }
+    }
+
+    /// Block until the GPU has finished every queued GL command.
+    /// 
+    /// Stalls the pipeline, so keep it to readback, screenshots and timing experiments.
+    pub fn finish(&self) {
+        unsafe {
+            self.gl.finish();
+        }
+    }
+
+    /// Read the color buffer of the bound framebuffer back to the CPU.
+    /// 
+    /// Reads the whole viewport-sized area, so call it after drawing and before `present`
+    /// when capturing the window. This waits for the GPU to finish the frame.
+    /// 
+    /// # Returns
+    /// The pixels with the first row at the top, as image files expect
+    pub fn read_pixels(&self) -> image::RgbaImage {
+        let (width, height) = self.size.get();
+        let mut pixels = vec![0u8; width as usize * height as usize * 4];
+        unsafe {
+            self.gl.pixel_store_i32(glow::PACK_ALIGNMENT, 1);
+            self.gl.read_pixels(
+                0,
+                0,
+                gl_size(width),
+                gl_size(height),
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelPackData::Slice(Some(&mut pixels)),
+            );
+        }
+        
+        // GL returns the bottom row first
+        image::RgbaImage::from_raw(width, height, pixels)
+            .map_or_else(|| image::RgbaImage::new(width, height), |image| image::imageops::flip_vertical(&image))
+    }
+
+    /// Convert a window-space pixel position (origin top-left, y down) to normalized device coordinates.
+    /// 
+    /// # Arguments
+    /// * `x` - Horizontal pixel position
+    /// * `y` - Vertical pixel position
+    /// 
+    /// # Returns
+    /// NDC position in `[-1, 1]` with y pointing up
+    #[allow(clippy::cast_precision_loss)]
+    pub fn screen_to_ndc(&self, x: f32, y: f32) -> (f32, f32) {
+        let (width, height) = self.size.get();
+        let ndc_x = 2.0 * x / width.max(1) as f32 - 1.0;
+        let ndc_y = 1.0 - 2.0 * y / height.max(1) as f32;
+        (ndc_x, ndc_y)
+    }
+
+    /// Convert normalized device coordinates to a window-space pixel position (origin top-left, y down).
+    /// 
+    /// # Arguments
+    /// * `x` - Horizontal NDC position
+    /// * `y` - Vertical NDC position (y up)
+    /// 
+    /// # Returns
+    /// Pixel position within the current viewport
+    #[allow(clippy::cast_precision_loss)]
+    pub fn ndc_to_screen(&self, x: f32, y: f32) -> (f32, f32) {
+        let (width, height) = self.size.get();
+        let screen_x = (x + 1.0) * 0.5 * width as f32;
+        let screen_y = (1.0 - y) * 0.5 * height as f32;
+        (screen_x, screen_y)
+    }
+
+    /// Restrict drawing to a rectangle, intersected with any enclosing clip rectangle.
+    /// 
+    /// # Arguments
+    /// * `x` - Left edge in window pixels
+    /// * `y` - Top edge in window pixels (y down)
+    /// * `width` - Rectangle width
+    /// * `height` - Rectangle height
+    pub fn push_clip_rect(&self, x: i32, y: i32, width: u32, height: u32) {
+        let mut stack = self.clip_stack.borrow_mut();
+        let rect = ClipRect::new(x, y, width, height);
+        let clipped = stack.last().map_or(rect, |parent| parent.intersect(&rect));
+        stack.push(clipped);
+        self.apply_scissor(Some(clipped));
+    }
+
+    /// Remove the innermost clip rectangle, restoring the enclosing one (or no clipping).
+    pub fn pop_clip_rect(&self) {
+        let mut stack = self.clip_stack.borrow_mut();
+        if stack.pop().is_none() {
+            tracing::warn!("pop_clip_rect called with an empty clip stack");
+        }
+        self.apply_scissor(stack.last().copied());
+    }
+
+    fn apply_scissor(&self, rect: Option<ClipRect>) {
+        unsafe {
+            match rect {
+                Some(rect) => {
+                    // GL scissor boxes have their origin at the bottom-left corner
+                    let (_, height) = self.size.get();
+                    let bottom = gl_size(height) - (rect.y + gl_size(rect.height));
+                    self.gl.enable(glow::SCISSOR_TEST);
+                    self.gl.scissor(rect.x, bottom, gl_size(rect.width), gl_size(rect.height));
+                }
+                None => self.gl.disable(glow::SCISSOR_TEST),
+            }
+        }
+    }
+
+    /// Draw only the scene items whose layer bit is set in a mask.
+    /// 
+    /// Lets opaque, transparent or overlay layers be drawn in separate passes with different
+    /// state in between. Filtered items are drawn with `render_scene`'s ordering rules.
+    /// 
+    /// # Arguments
+    /// * `shader` - Shader with `projection`, `view` and `model` matrix uniforms
+    /// * `camera` - Camera the scene is viewed from
+    /// * `projection` - Projection matrix
+    /// * `items` - Items to filter and draw
+    /// * `mask` - Bit mask of layers to draw, e.g. `1 << 0 | 1 << 2` or `ALL_LAYERS`
+    pub fn render_scene_layers(&self, shader: &mut Shader, camera: &Camera, projection: &Perspective3<f32>, items: &[SceneItem], mask: u32) {
+        let visible: Vec<SceneItem> = items.iter().filter(|item| item.in_layers(mask)).copied().collect();
+        self.render_scene(shader, camera, projection, &visible);
+    }
+
+    /// Draw a list of scene items with correct transparency ordering.
+    /// 
+    /// Opaque items are drawn first, front-to-back, to benefit from early depth rejection.
+    /// Transparent items follow back-to-front with alpha blending enabled and depth writes
+    /// disabled; the caller's blend state and depth write mask are restored afterwards.
+    /// Distances are measured from the camera to each item's world-space bounds center.
+    /// Items with a material apply it before their model matrix is set.
+    /// Lines queued in `debug_lines` are drawn last.
+    /// The shader is left bound unless `set_unbind_after_draw` is enabled.
+    /// 
+    /// # Arguments
+    /// * `shader` - Shader with `projection`, `view` and `model` matrix uniforms
+    /// * `camera` - Camera the scene is viewed from
+    /// * `projection` - Projection matrix
+    /// * `items` - Items to draw
+    pub fn render_scene(&self, shader: &mut Shader, camera: &Camera, projection: &Perspective3<f32>, items: &[SceneItem]) {
+        let _state = self.interop_guard();
+        let _span = tracing::debug_span!("render_scene", items = items.len()).entered();
+        
+        let distance = |item: &SceneItem| {
+            let center = item.mesh.bounds().transformed(&item.model).center();
+            nalgebra::distance_squared(&center, &camera.position)
+        };
+        
+        let (mut opaque, mut transparent): (Vec<_>, Vec<_>) = items
+            .iter()
+            .map(|item| (distance(item), item))
+            .partition(|(_, item)| !item.transparent);
+        opaque.sort_by(|a, b| a.0.total_cmp(&b.0));
+        transparent.sort_by(|a, b| b.0.total_cmp(&a.0));
+        
+        shader.bind(&self.gl);
+        let view = camera.view_matrix();
+        // An `mvp`-only shader has no projection or view uniforms to set
+        if items.iter().any(|item| !item.cpu_mvp) {
+            shader.set_matrix4(&self.gl, "projection", projection.as_matrix());
+            shader.set_matrix4(&self.gl, "view", &view);
+        }
+        let view_projection = projection.as_matrix() * view;
+        
+        for (_, item) in &opaque {
+            self.draw_item(shader, item, &view_projection);
+        }
+        
+        if !transparent.is_empty() {
+            let (blend, blend_func, depth_write) = unsafe {
+                let blend_factor = |parameter| self.gl.get_parameter_i32(parameter).cast_unsigned();
+                (
+                    self.gl.is_enabled(glow::BLEND),
+                    [glow::BLEND_SRC_RGB, glow::BLEND_DST_RGB, glow::BLEND_SRC_ALPHA, glow::BLEND_DST_ALPHA].map(blend_factor),
+                    self.gl.get_parameter_bool(glow::DEPTH_WRITEMASK),
+                )
+            };
+            unsafe {
+                self.gl.enable(glow::BLEND);
+                self.gl.depth_mask(false);
+            }
+            
+            for (_, item) in &transparent {
+                item.blend.apply(&self.gl);
+                self.draw_item(shader, item, &view_projection);
+            }
+            
+            // Hand back the caller's blending and depth writes, not fixed defaults
+            unsafe {
+                self.gl.depth_mask(depth_write);
+                let [src_rgb, dst_rgb, src_alpha, dst_alpha] = blend_func;
+                self.gl.blend_func_separate(src_rgb, dst_rgb, src_alpha, dst_alpha);
+                if !blend {
+                    self.gl.disable(glow::BLEND);
+                }
+            }
+        }
+        
+        if let Err(e) = self.debug_lines.borrow_mut().draw(&self.gl, self.api(), camera, projection) {
+            tracing::error!("Failed to draw debug lines: {}", e);
+        }
+        
+        if self.draw_options.unbind_after_draw {
+            Shader::unbind(&self.gl);
+        }
+    }
+
+    /// Apply an item's material and model (or combined MVP) matrix, then draw its mesh.
+    fn draw_item(&self, shader: &mut Shader, item: &SceneItem, view_projection: &Matrix4<f32>) {
+        if let Some(material) = item.material {
+            if let Err(e) = material.apply(&self.gl, shader) {
+                tracing::error!("Failed to apply material: {}", e);
+            }
+        }
+        if item.cpu_mvp {
+            shader.set_matrix4(&self.gl, "mvp", &(view_projection * item.model));
+        } else {
+            shader.set_matrix4(&self.gl, "model", &item.model);
+        }
+        item.mesh.draw(&self.gl);
+    }
+
+    /// Render the index of each item into the ID buffer for picking with `id_at`.
+    /// 
+    /// The window-sized `IdBuffer` is created on first use and recreated when the window
+    /// size changes. The previously bound framebuffer and viewport are restored afterwards.
+    /// 
+    /// # Arguments
+    /// * `items` - Items to draw, in the order their indices refer to
+    /// * `camera` - Camera the scene is viewed from
+    /// * `projection` - Projection matrix
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` or `TemplateError::OpenGL` if the ID buffer cannot be created
+    pub fn render_ids(&self, items: &[SceneItem], camera: &Camera, projection: &Perspective3<f32>) -> TemplateResult<()> {
+        let _state = self.interop_guard();
+        let _span = tracing::debug_span!("render_ids", items = items.len()).entered();
+        let (width, height) = self.size.get();
+        
+        let mut id_buffer = self.id_buffer.borrow_mut();
+        if id_buffer.as_ref().is_some_and(|buffer| buffer.size() != (width, height)) {
+            if let Some(buffer) = id_buffer.take() {
+                buffer.delete(&self.gl);
+            }
+        }
+        if id_buffer.is_none() {
+            *id_buffer = Some(IdBuffer::new(&self.gl, self.api(), width.max(1), height.max(1))?);
+        }
+        
+        // Picking can run inside an offscreen pass, so return to its target rather than the window
+        let (framebuffer, viewport) = unsafe {
+            let mut viewport = [0; 4];
+            self.gl.get_parameter_i32_slice(glow::VIEWPORT, &mut viewport);
+            (self.gl.get_parameter_framebuffer(glow::FRAMEBUFFER_BINDING), viewport)
+        };
+        if let Some(buffer) = id_buffer.as_mut() {
+            buffer.render(&self.gl, items, camera, projection);
+        }
+        unsafe {
+            self.gl.bind_framebuffer(glow::FRAMEBUFFER, framebuffer);
+            let [x, y, width, height] = viewport;
+            self.gl.viewport(x, y, width, height);
+        }
+        Ok(())
+    }
+
+    /// Draw an overdraw heatmap of the items to the window instead of their shaded colors.
+    /// 
+    /// The window-sized `OverdrawView` is created on first use and recreated when the
+    /// window size changes. The renderer's state is reapplied afterwards.
+    /// 
+    /// # Arguments
+    /// * `items` - Items to draw
+    /// * `camera` - Camera the scene is viewed from
+    /// * `projection` - Projection matrix
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` or `TemplateError::OpenGL` if the overdraw view cannot be created
+    pub fn render_overdraw(&self, items: &[SceneItem], camera: &Camera, projection: &Perspective3<f32>) -> TemplateResult<()> {
+        let _state = self.interop_guard();
+        let _span = tracing::debug_span!("render_overdraw", items = items.len()).entered();
+        let (width, height) = self.size.get();
+        
+        let mut overdraw = self.overdraw.borrow_mut();
+        if overdraw.as_ref().is_some_and(|view| view.size() != (width, height)) {
+            if let Some(view) = overdraw.take() {
+                view.delete(&self.gl);
+            }
+        }
+        if overdraw.is_none() {
+            *overdraw = Some(OverdrawView::new(&self.gl, self.api(), width.max(1), height.max(1))?);
+        }
+        
+        if let Some(view) = overdraw.as_mut() {
+            view.render(&self.gl, items, camera, projection);
+        }
+        self.apply_state();
+        self.reset_viewport();
+        Ok(())
+    }
+
+    /// Get the index of the item `render_ids` drew at a window pixel.
+    /// 
+    /// # Arguments
+    /// * `x` - Horizontal pixel position
+    /// * `y` - Vertical pixel position (origin top-left, y down)
+    /// 
+    /// # Returns
+    /// The item index, or `None` for background or before the first `render_ids`
+    pub fn id_at(&self, x: u32, y: u32) -> Option<usize> {
+        let id_buffer = self.id_buffer.borrow();
+        let buffer = id_buffer.as_ref()?;
+        let (_, height) = buffer.size();
+        buffer.read(&self.gl, x, height.checked_sub(y)?.checked_sub(1)?)
+    }
+
+    /// Draw the scene twice, side by side, once per eye.
+    /// 
+    /// The left camera renders into the left half of the window and the right camera into
+    /// the right half. Each eye gets its own projection built from the half-width aspect
+    /// ratio, so the image is not stretched. The full-window viewport is restored afterwards.
+    /// 
+    /// # Arguments
+    /// * `shader` - Shader with `projection`, `view` and `model` matrix uniforms
+    /// * `items` - Items to draw
+    /// * `left` - Left eye camera
+    /// * `right` - Right eye camera
+    /// * `projection` - Projection parameters shared by both eyes
+    pub fn render_stereo(&self, shader: &mut Shader, items: &[SceneItem], left: &Camera, right: &Camera, projection: &CameraProjection) {
+        let _span = tracing::debug_span!("render_stereo").entered();
+        let (width, height) = self.size.get();
+        let left_width = width / 2;
+        let right_width = width - left_width;
+        
+        self.set_viewport(0, 0, left_width, height);
+        self.render_scene(shader, left, &projection.perspective(left_width, height), items);
+        
+        self.set_viewport(gl_size(left_width), 0, right_width, height);
+        self.render_scene(shader, right, &projection.perspective(right_width, height), items);
+        
+        self.reset_viewport();
+    }
+
+    /// Draw a reference grid on the XZ plane.
+    /// 
+    /// The line shader and grid mesh are created on first use and cached.
+    /// 
+    /// # Arguments
+    /// * `camera` - Camera the grid is viewed from
+    /// * `projection` - Projection matrix
+    /// * `size` - Half extent of the grid
+    /// * `spacing` - Distance between grid lines
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` or `TemplateError::OpenGL` if the gizmo shader or meshes
+    /// cannot be created on first use
+    pub fn draw_grid(&self, camera: &Camera, projection: &Perspective3<f32>, size: f32, spacing: f32) -> TemplateResult<()> {
+        let _state = self.interop_guard();
+        let mut gizmos = self.gizmos()?;
+        if let Some(gizmos) = gizmos.as_mut() {
+            gizmos.draw_grid(&self.gl, camera, projection, size, spacing);
+        }
+        Ok(())
+    }
+
+    /// Draw red/green/blue lines along the X/Y/Z axes.
+    /// 
+    /// # Arguments
+    /// * `camera` - Camera the axes are viewed from
+    /// * `projection` - Projection matrix
+    /// * `length` - Length of each axis
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` or `TemplateError::OpenGL` if the gizmo shader or meshes
+    /// cannot be created on first use
+    pub fn draw_axes(&self, camera: &Camera, projection: &Perspective3<f32>, length: f32) -> TemplateResult<()> {
+        let _state = self.interop_guard();
+        let mut gizmos = self.gizmos()?;
+        if let Some(gizmos) = gizmos.as_mut() {
+            gizmos.draw_axes(&self.gl, camera, projection, length);
+        }
+        Ok(())
+    }
+
+    /// Draw a line of debug text in screen space with the built-in 5x7 pixel font.
+    /// 
+    /// Draws on top of everything already rendered; call it after the scene.
+    /// 
+    /// # Arguments
+    /// * `text` - Text to draw (`\n` starts a new line)
+    /// * `x` - Left edge in window pixels
+    /// * `y` - Top edge in window pixels (y down)
+    /// * `scale` - Screen pixels per font pixel
+    /// * `color` - RGBA text color
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` or `TemplateError::OpenGL` if the text shader or font
+    /// texture cannot be created on first use
+    pub fn draw_text(&self, text: &str, x: f32, y: f32, scale: f32, color: [f32; 4]) -> TemplateResult<()> {
+        let _state = self.interop_guard();
+        if let Some(text_renderer) = self.text_renderer()?.as_mut() {
+            text_renderer.draw(&self.gl, text, (x, y), scale, color, self.size.get());
+        }
+        
+        // Text drawing turns off depth testing and culling
+        self.apply_state();
+        Ok(())
+    }
+
+    /// Start collecting text to draw in one call with `flush_text_batch`.
+    /// 
+    /// Discards text added but not flushed earlier.
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` or `TemplateError::OpenGL` if the text renderer cannot
+    /// be created on first use
+    pub fn begin_text_batch(&self) -> TemplateResult<()> {
+        if let Some(text_renderer) = self.text_renderer()?.as_mut() {
+            text_renderer.clear();
+        }
+        Ok(())
+    }
+
+    /// Queue a string for `flush_text_batch` instead of drawing it now.
+    /// 
+    /// # Arguments
+    /// * `text` - Text to draw (`\n` starts a new line)
+    /// * `x` - Left edge in window pixels
+    /// * `y` - Top edge in window pixels (y down)
+    /// * `scale` - Screen pixels per font pixel
+    /// * `color` - RGBA text color
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` or `TemplateError::OpenGL` if the text renderer cannot
+    /// be created on first use
+    pub fn add_text(&self, text: &str, x: f32, y: f32, scale: f32, color: [f32; 4]) -> TemplateResult<()> {
+        if let Some(text_renderer) = self.text_renderer()?.as_mut() {
+            text_renderer.add(text, (x, y), scale, color);
+        }
+        Ok(())
+    }
+
+    /// Draw all text queued with `add_text` in a single draw call.
+    pub fn flush_text_batch(&self) {
+        let _state = self.interop_guard();
+        if let Some(text_renderer) = self.text.borrow_mut().as_mut() {
+            text_renderer.flush(&self.gl, self.size.get());
+        }
+        
+        // Text drawing turns off depth testing and culling
+        self.apply_state();
+    }
+
+    fn text_renderer(&self) -> TemplateResult<RefMut<'_, Option<TextRenderer>>> {
+        let mut text_renderer = self.text.borrow_mut();
+        if text_renderer.is_none() {
+            *text_renderer = Some(TextRenderer::new(&self.gl, self.api())?);
+        }
+        Ok(text_renderer)
+    }
+
+    fn gizmos(&self) -> TemplateResult<RefMut<'_, Option<Gizmos>>> {
+        let mut gizmos = self.gizmos.borrow_mut();
+        if gizmos.is_none() {
+            *gizmos = Some(Gizmos::new(&self.gl, self.api())?);
+        }
+        Ok(gizmos)
+    }
+
+    /// Toggle wireframe rasterization.
+    /// 
+    /// OpenGL ES has no `glPolygonMode`, so this only logs a warning there.
+    /// 
+    /// # Arguments
+    /// * `enabled` - Draw polygon edges only when true, filled polygons otherwise
+    pub fn set_wireframe(&self, enabled: bool) {
+        if self.api() == GlApi::Gles {
+            tracing::warn!("Wireframe mode is not supported on OpenGL ES");
+            return;
+        }
+        
+        let mode = if enabled { glow::LINE } else { glow::FILL };
+        unsafe {
+            self.gl.polygon_mode(glow::FRONT_AND_BACK, mode);
+        }
+    }
+
+    /// Offset the depth of filled polygons so lines drawn over them win the depth test.
+    /// 
+    /// # Arguments
+    /// * `offset` - `(factor, units)` passed to `glPolygonOffset`, or `None` to disable the offset
+    pub fn set_polygon_offset(&self, offset: Option<(f32, f32)>) {
+        unsafe {
+            match offset {
+                Some((factor, units)) => {
+                    self.gl.enable(glow::POLYGON_OFFSET_FILL);
+                    self.gl.polygon_offset(factor, units);
+                }
+                None => self.gl.disable(glow::POLYGON_OFFSET_FILL),
+            }
+        }
+    }
+
+    /// Draw a mesh shaded normally with its triangle edges outlined on top.
+    /// 
+    /// The solid pass is pushed back with a polygon offset, then the edges are drawn as a
+    /// line mesh so they sit on the faces without z-fighting. Unlike `set_wireframe` this
+    /// also works on OpenGL ES. The shader must be an unlit position/color shader (such as
+    /// `basic.vert` + `basic.frag`) with its `projection` and `view` uniforms already set.
+    /// 
+    /// The edge buffer is kept between calls and only reallocated when the edge count
+    /// changes, so outlining the same mesh every frame creates no GL objects.
+    /// 
+    /// # Arguments
+    /// * `mesh` - Triangle mesh to draw
+    /// * `shader` - Unlit shader used for both passes
+    /// * `model` - Model matrix the mesh is drawn with
+    /// * `wire_color` - RGB color of the edges
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the edge buffer cannot be created, `TemplateError::InvalidMesh`
+    /// if the edges hold more than `i32::MAX` vertices
+    pub fn draw_solid_wireframe(&self, mesh: &Mesh, shader: &mut Shader, model: &Matrix4<f32>, wire_color: [f32; 3]) -> TemplateResult<()> {
+        let _state = self.interop_guard();
+        shader.bind(&self.gl);
+        shader.set_matrix4(&self.gl, "model", model);
+        
+        self.set_polygon_offset(Some((1.0, 1.0)));
+        mesh.draw(&self.gl);
+        self.set_polygon_offset(None);
+        
+        if mesh.mode() != glow::TRIANGLES {
+            return Ok(());
+        }
+        
+        let stride = mesh.floats_per_vertex();
+        let vertices = mesh.vertices();
+        let order = mesh.draw_order();
+        let mut lines = Vec::with_capacity(order.len() * 12);
+        for triangle in order.chunks_exact(3) {
+            let corner = |index: usize| &vertices[triangle[index] * stride..triangle[index] * stride + 3];
+            for (start, end) in [(0, 1), (1, 2), (2, 0)] {
+                lines.extend_from_slice(corner(start));
+                lines.extend_from_slice(&wire_color);
+                lines.extend_from_slice(corner(end));
+                lines.extend_from_slice(&wire_color);
+            }
+        }
+        if lines.is_empty() {
+            return Ok(());
+        }
+        
+        let mut edges = self.wireframe_edges.borrow_mut();
+        match edges.as_mut() {
+            Some(edge_mesh) if edge_mesh.vertices().len() == lines.len() => edge_mesh.stream_vertices(&self.gl, &lines)?,
+            _ => {
+                let edge_mesh = Mesh::try_with_mode(&self.gl, &lines, glow::LINES)?;
+                if let Some(old) = edges.replace(edge_mesh) {
+                    old.delete(&self.gl);
+                }
+            }
+        }
+        if let Some(edge_mesh) = edges.as_ref() {
+            edge_mesh.draw(&self.gl);
+        }
+        Ok(())
+    }
+
+    /// Queue the normals of a mesh as short yellow lines in the debug line batch.
+    /// 
+    /// Call it before `render_scene`, which draws the batch.
+    /// 
+    /// # Arguments
+    /// * `mesh` - Mesh whose normals are visualized
+    /// * `model` - Model matrix the mesh is drawn with
+    /// * `length` - Length of each normal segment in object space
+    pub fn debug_draw_normals(&self, mesh: &Mesh, model: &Matrix4<f32>, length: f32) {
+        let mut lines = self.debug_lines.borrow_mut();
+        for (vertex, normal) in mesh.vertices().chunks_exact(mesh.floats_per_vertex()).zip(mesh.normals()) {
+            let base = Point3::new(vertex[0], vertex[1], vertex[2]);
+            let tip = base + normal * length;
+            lines.add_line(model.transform_point(&base), model.transform_point(&tip), [1.0, 1.0, 0.0]);
+        }
+    }
+}
+
+impl Drop for Renderer {
+    fn drop(&mut self) {
+        // Delete cached objects while the context still exists and is current
+        if let Err(e) = self.make_current() {
+            tracing::warn!("Could not make context current for teardown: {}", e);
+            return;
+        }
+        if let Some(gizmos) = self.gizmos.get_mut().take() {
+            gizmos.delete(&self.gl);
+        }
+        if let Some(text) = self.text.get_mut().take() {
+            text.delete(&self.gl);
+        }
+        std::mem::take(self.debug_lines.get_mut()).delete(&self.gl);
+        std::mem::take(self.gpu_timer.get_mut()).delete(&self.gl);
+        if let Some(id_buffer) = self.id_buffer.get_mut().take() {
+            id_buffer.delete(&self.gl);
+        }
+        if let Some(overdraw) = self.overdraw.get_mut().take() {
+            overdraw.delete(&self.gl);
+        }
+        if let Some(edges) = self.wireframe_edges.get_mut().take() {
+            edges.delete(&self.gl);
+        }
+        if let Some(gradient) = self.gradient.get_mut().take() {
+            gradient.delete(&self.gl);
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,109 @@
+//! Location of the resource directory that shaders, textures and models are loaded from.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use tracing;
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Environment variable that moves the resource directory.
+pub const RESOURCE_DIR_ENV: &str = "TEMPLATE_RESOURCE_DIR";
+
+/// Resource directory used when neither `GraphicsConfig` nor the environment sets one.
+pub const DEFAULT_RESOURCE_DIR: &str = "resources";
+
+/// Root set with `GraphicsConfig::apply`; takes precedence over the environment.
+static RESOURCE_ROOT: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Process-wide settings for the graphics loaders.
+/// 
+/// ```ignore
+/// GraphicsConfig::new().with_resource_root("/opt/demo/assets").apply();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GraphicsConfig {
+    resource_root: Option<PathBuf>,
+}
+
+impl GraphicsConfig {
+    /// Create a config that keeps the default resource root.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load resources from this directory instead of `$TEMPLATE_RESOURCE_DIR` or `resources/`.
+    #[must_use]
+    pub fn with_resource_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.resource_root = Some(root.into());
+        self
+    }
+
+    /// Make these settings current for every loader. Call before loading anything.
+    pub fn apply(self) {
+        if let Some(root) = &self.resource_root {
+            tracing::info!("Resource root set to {}", root.display());
+        }
+        // A poisoned lock only means a panic mid-write of a plain value; keep using it
+        *RESOURCE_ROOT.write().unwrap_or_else(std::sync::PoisonError::into_inner) = self.resource_root;
+    }
+}
+
+// ============================================================
+// ==================== Global Functions ======================
+// ============================================================
+
+/// Get the resource directory: the `GraphicsConfig` override, else `$TEMPLATE_RESOURCE_DIR`, else `resources/`.
+/// 
+/// Relative roots are relative to the working directory.
+pub fn resource_root() -> PathBuf {
+    let configured = RESOURCE_ROOT.read().unwrap_or_else(std::sync::PoisonError::into_inner).clone();
+    configured
+        .or_else(|| std::env::var_os(RESOURCE_DIR_ENV).filter(|dir| !dir.is_empty()).map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_RESOURCE_DIR))
+}
+
+/// Resolve a resource path against `resource_root`; absolute paths are returned unchanged.
+/// 
+/// # Arguments
+/// * `path` - Path such as `shaders/basic.vert` or `textures/crate.png`
+pub fn resource_path(path: impl AsRef<Path>) -> PathBuf {
+    resource_root().join(path)
+}
+
+// ============================================================
+// ========================== Tests ===========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DecodedImage;
+
+    #[test]
+    fn loaders_resolve_paths_against_configured_root() {
+        let root = std::env::temp_dir().join(format!("template_resources_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("textures")).unwrap();
+        image::RgbaImage::new(3, 2).save(root.join("textures/probe.png")).unwrap();
+        
+        GraphicsConfig::new().with_resource_root(&root).apply();
+        assert_eq!(resource_path("shaders/basic.vert"), root.join("shaders/basic.vert"));
+        assert_eq!(resource_path(root.join("textures/probe.png")), root.join("textures/probe.png"));
+        let decoded = DecodedImage::decode(Path::new("textures/probe.png"));
+        GraphicsConfig::new().apply();
+        std::fs::remove_dir_all(&root).unwrap();
+        
+        let decoded = decoded.unwrap();
+        assert_eq!((decoded.width, decoded.height), (3, 2));
+    }
+}
\ No newline at end of file
@@ -5,20 +5,61 @@
 // ============================================================
 
 use template_core::{TemplateResult, TemplateError};
-use crate::GlContext;
+use crate::{GlCapabilities, GlContext, ProgramPipeline, Texture, supports_tessellation};
 use glow::HasContext;
 use nalgebra::Matrix4;
 use std::collections::HashMap;
 use tracing;
 
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// `GL_COMPLETION_STATUS_KHR` from `KHR_parallel_shader_compile`.
+const COMPLETION_STATUS_KHR: u32 = 0x91B1;
+
+/// Built-in magenta shader used when a requested shader fails to compile.
+const FALLBACK_VERTEX_SOURCE: &str = include_str!("../../resources/shaders/fallback.vert");
+const FALLBACK_FRAGMENT_SOURCE: &str = include_str!("../../resources/shaders/fallback.frag");
+
 // ============================================================
 // ===================== Structs & Impls ======================
 // ============================================================
 
+/// Where a shader program's GLSL source comes from.
+enum ShaderOrigin {
+    /// Files relative to the resource root's `shaders/` (or the embedded store).
+    Files { vertex_path: String, fragment_path: String },
+    /// Source strings supplied directly.
+    Inline { vertex_source: String, fragment_source: String },
+    /// A single stage linked as a separable program for use in a `ProgramPipeline`.
+    Separable { stage: u32, source: String },
+    /// Vertex, tessellation control, tessellation evaluation and fragment files.
+    Tessellated { paths: [String; 4] },
+}
+
+/// Last value uploaded to a uniform, compared exactly to skip redundant uploads.
+#[derive(Clone, Copy, PartialEq)]
+enum CachedUniform {
+    Matrix4([f32; 16]),
+    Vec3([f32; 3]),
+    Vec4([f32; 4]),
+    Float(f32),
+    Int(i32),
+    Uint(u32),
+}
+
 /// Compiled shader program with uniform caching.
+/// 
+/// Locations are looked up once per name, and setters skip the GL call when the uniform
+/// already holds the same value, since uniforms keep their values in the program object.
 pub struct Shader {
     program: glow::Program,
     uniforms: HashMap<String, glow::UniformLocation>,
+    values: HashMap<glow::UniformLocation, CachedUniform>,
+    origin: ShaderOrigin,
+    preamble: Option<String>,
+    fallback: bool,
 }
 
 impl Shader {
@@ -26,83 +67,861 @@ impl Shader {
     /// 
     /// # Arguments
     /// * `gl` - OpenGL context
-    /// * `vertex_path` - Path to vertex shader file (relative to resources/shaders/)
-    /// * `fragment_path` - Path to fragment shader file (relative to resources/shaders/)
+    /// * `vertex_path` - Path to vertex shader file (relative to the resource root's `shaders/`)
+    /// * `fragment_path` - Path to fragment shader file (relative to the resource root's `shaders/`)
     pub fn new(gl: &GlContext, vertex_path: &str, fragment_path: &str) -> TemplateResult<Self> {
-        tracing::info!("Compiling shader program: {} + {}", vertex_path, fragment_path);
-        
-        let vertex_source = std::fs::read_to_string(format!("resources/shaders/{}", vertex_path))?;
-        let fragment_source = std::fs::read_to_string(format!("resources/shaders/{}", fragment_path))?;
+        Self::with_preamble(gl, vertex_path, fragment_path, None)
+    }
+
+    /// Create a shader program, replacing the `#version` line of both sources with a preamble.
+    /// 
+    /// Pass `GlApi::shader_preamble` to run the desktop shaders on OpenGL ES.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertex_path` - Path to vertex shader file (relative to the resource root's `shaders/`)
+    /// * `fragment_path` - Path to fragment shader file (relative to the resource root's `shaders/`)
+    /// * `preamble` - Header injected in place of the `#version` line, or `None` to keep the sources as-is
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` if a source file cannot be read, or `TemplateError::ShaderCompilation`
+    /// with the driver's log if compiling or linking fails
+    pub fn with_preamble(gl: &GlContext, vertex_path: &str, fragment_path: &str, preamble: Option<&str>) -> TemplateResult<Self> {
+        let origin = ShaderOrigin::Files {
+            vertex_path: vertex_path.to_string(),
+            fragment_path: fragment_path.to_string(),
+        };
+        Self::from_origin(gl, origin, preamble)
+    }
+
+    /// Create a shader program from GLSL source strings instead of files.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertex_source` - Vertex shader GLSL source
+    /// * `fragment_source` - Fragment shader GLSL source
+    /// * `preamble` - Header injected in place of the `#version` line, or `None` to keep the sources as-is
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` with the driver's log if compiling or linking fails
+    pub fn from_source(gl: &GlContext, vertex_source: &str, fragment_source: &str, preamble: Option<&str>) -> TemplateResult<Self> {
+        let origin = ShaderOrigin::Inline {
+            vertex_source: vertex_source.to_string(),
+            fragment_source: fragment_source.to_string(),
+        };
+        Self::from_origin(gl, origin, preamble)
+    }
+
+    /// Compile a vertex shader on its own as a separable program for a `ProgramPipeline`.
+    /// 
+    /// On desktop GL the source must redeclare `out gl_PerVertex { vec4 gl_Position; };`
+    /// and its outputs must match the fragment stage's inputs by location or name.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `source` - Vertex shader GLSL source
+    /// * `preamble` - Header injected in place of the `#version` line, or `None` to keep the source as-is
+    /// 
+    /// # Returns
+    /// The stage
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` if separate shader objects are unsupported
+    pub fn separable_vertex(gl: &GlContext, source: &str, preamble: Option<&str>) -> TemplateResult<Self> {
+        Self::separable(gl, glow::VERTEX_SHADER, source, preamble)
+    }
+
+    /// Compile a fragment shader on its own as a separable program for a `ProgramPipeline`.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `source` - Fragment shader GLSL source
+    /// * `preamble` - Header injected in place of the `#version` line, or `None` to keep the source as-is
+    /// 
+    /// # Returns
+    /// The stage
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` if separate shader objects are unsupported
+    pub fn separable_fragment(gl: &GlContext, source: &str, preamble: Option<&str>) -> TemplateResult<Self> {
+        Self::separable(gl, glow::FRAGMENT_SHADER, source, preamble)
+    }
+
+    /// Create a program with tessellation control and evaluation stages between the vertex
+    /// and fragment shaders.
+    /// 
+    /// Needs a desktop OpenGL 4.0 context, which the context creation picks whenever the
+    /// driver offers one. The sources are compiled as written, so each must declare
+    /// `#version 400 core` or later. Draw it with a `Mesh::new_patches` mesh.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertex_path` - Vertex shader file (relative to the resource root's `shaders/`)
+    /// * `control_path` - Tessellation control shader file
+    /// * `evaluation_path` - Tessellation evaluation shader file
+    /// * `fragment_path` - Fragment shader file
+    /// 
+    /// # Returns
+    /// The shader
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` on OpenGL ES and pre-4.0 contexts
+    pub fn with_tessellation(gl: &GlContext, vertex_path: &str, control_path: &str, evaluation_path: &str, fragment_path: &str) -> TemplateResult<Self> {
+        if !supports_tessellation(gl) {
+            let version = gl.version();
+            return Err(TemplateError::ShaderCompilation(format!(
+                "tessellation shaders need desktop OpenGL 4.0, but the context is {}OpenGL {}.{}",
+                if version.is_embedded { "ES " } else { "" },
+                version.major,
+                version.minor,
+            )));
+        }
+        let paths = [vertex_path, control_path, evaluation_path, fragment_path].map(str::to_string);
+        Self::from_origin(gl, ShaderOrigin::Tessellated { paths }, None)
+    }
+
+    fn separable(gl: &GlContext, stage: u32, source: &str, preamble: Option<&str>) -> TemplateResult<Self> {
+        if !ProgramPipeline::is_supported(gl) {
+            return Err(TemplateError::ShaderCompilation("separate shader objects are not supported".to_string()));
+        }
+        let origin = ShaderOrigin::Separable { stage, source: source.to_string() };
+        Self::from_origin(gl, origin, preamble)
+    }
+
+    fn from_origin(gl: &GlContext, origin: ShaderOrigin, preamble: Option<&str>) -> TemplateResult<Self> {
+        let program = Self::build_program(gl, &origin, preamble)?;
         
-        let vertex_shader = Self::compile_shader(gl, glow::VERTEX_SHADER, &vertex_source)?;
-        let fragment_shader = Self::compile_shader(gl, glow::FRAGMENT_SHADER, &fragment_source)?;
+        Ok(Self {
+            program,
+            uniforms: HashMap::new(),
+            values: HashMap::new(),
+            origin,
+            preamble: preamble.map(str::to_string),
+            fallback: false,
+        })
+    }
+
+    /// Create a shader program, substituting a built-in magenta shader if compilation fails.
+    /// 
+    /// The error is logged instead of returned so the application keeps running. The
+    /// fallback remembers the requested files, so a later successful `reload` (e.g. through
+    /// hot-reload) replaces it with the real program. Uniforms the fallback does not
+    /// declare are silently ignored.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertex_path` - Path to vertex shader file (relative to the resource root's `shaders/`)
+    /// * `fragment_path` - Path to fragment shader file (relative to the resource root's `shaders/`)
+    /// * `preamble` - Header injected in place of the `#version` line, or `None` to keep the sources as-is
+    /// 
+    /// # Returns
+    /// The shader and whether the fallback is in use
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` only if the built-in fallback itself fails to compile
+    pub fn new_or_fallback(gl: &GlContext, vertex_path: &str, fragment_path: &str, preamble: Option<&str>) -> TemplateResult<(Self, bool)> {
+        let error = match Self::with_preamble(gl, vertex_path, fragment_path, preamble) {
+            Ok(shader) => return Ok((shader, false)),
+            Err(error) => error,
+        };
         
-        let program = Self::link_program(gl, vertex_shader, fragment_shader)?;
+        tracing::error!("Shader {} + {} failed, using fallback: {}", vertex_path, fragment_path, error);
         
-        tracing::info!("Shader program compiled successfully");
+        let fallback = ShaderOrigin::Inline {
+            vertex_source: FALLBACK_VERTEX_SOURCE.to_string(),
+            fragment_source: FALLBACK_FRAGMENT_SOURCE.to_string(),
+        };
+        let program = Self::build_program(gl, &fallback, preamble)?;
         
-        Ok(Self {
+        let shader = Self {
             program,
             uniforms: HashMap::new(),
-        })
+            values: HashMap::new(),
+            origin: ShaderOrigin::Files {
+                vertex_path: vertex_path.to_string(),
+                fragment_path: fragment_path.to_string(),
+            },
+            preamble: preamble.map(str::to_string),
+            fallback: true,
+        };
+        Ok((shader, true))
+    }
+
+    /// Check whether this shader is the magenta fallback from `new_or_fallback`.
+    #[must_use]
+    pub fn is_fallback(&self) -> bool {
+        self.fallback
+    }
+
+    /// Recompile the program from its sources, replacing the current program on success.
+    /// 
+    /// On failure the current program stays in use and the compile error is returned.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context the shader was created with
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the sources cannot be read or
+    /// compiled; the current program is kept
+    pub fn reload(&mut self, gl: &GlContext) -> TemplateResult<()> {
+        let program = Self::build_program(gl, &self.origin, self.preamble.as_deref())?;
+        unsafe {
+            gl.delete_program(self.program);
+            crate::leaks::track_deleted();
+        }
+        self.program = program;
+        self.uniforms.clear();
+        self.values.clear();
+        self.fallback = false;
+        Ok(())
+    }
+
+    /// Check whether the program was built from the given shader file.
+    /// 
+    /// # Arguments
+    /// * `path` - Path relative to the resource root's `shaders/`
+    #[must_use]
+    pub fn uses_file(&self, path: &str) -> bool {
+        match &self.origin {
+            ShaderOrigin::Files { vertex_path, fragment_path } => vertex_path == path || fragment_path == path,
+            ShaderOrigin::Tessellated { paths } => paths.iter().any(|stage_path| stage_path == path),
+            ShaderOrigin::Inline { .. } | ShaderOrigin::Separable { .. } => false,
+        }
+    }
+
+    /// Get the pipeline stage bit (`VERTEX_SHADER_BIT` or `FRAGMENT_SHADER_BIT`) of a separable
+    /// stage, or `None` for a regular program.
+    #[must_use]
+    pub fn separable_stage(&self) -> Option<u32> {
+        match &self.origin {
+            ShaderOrigin::Separable { stage: glow::VERTEX_SHADER, .. } => Some(glow::VERTEX_SHADER_BIT),
+            ShaderOrigin::Separable { .. } => Some(glow::FRAGMENT_SHADER_BIT),
+            ShaderOrigin::Files { .. } | ShaderOrigin::Inline { .. } | ShaderOrigin::Tessellated { .. } => None,
+        }
+    }
+
+    /// Get the location the linker assigned to a vertex input.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `name` - Name of the `in` variable in the vertex shader
+    /// 
+    /// # Returns
+    /// The location, or `None` if the input does not exist or was optimized out
+    pub fn attribute_location(&self, gl: &GlContext, name: &str) -> Option<u32> {
+        unsafe { gl.get_attrib_location(self.program, name) }
+    }
+
+    /// Get the underlying OpenGL program handle.
+    pub(crate) fn program(&self) -> glow::Program {
+        self.program
+    }
+
+    /// Recompile the program from its sources in a newly created context.
+    /// 
+    /// The old program handle is abandoned rather than deleted, since it belongs to a
+    /// context that no longer exists.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context to compile the new program in
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the sources cannot be read or compiled
+    pub fn recreate(&mut self, gl: &GlContext) -> TemplateResult<()> {
+        self.program = Self::build_program(gl, &self.origin, self.preamble.as_deref())?;
+        self.uniforms.clear();
+        self.values.clear();
+        self.fallback = false;
+        Ok(())
+    }
+
+    /// Release the GPU program.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context the shader was created with
+    pub fn delete(self, gl: &GlContext) {
+        unsafe {
+            gl.delete_program(self.program);
+            crate::leaks::track_deleted();
+        }
+    }
+
+    fn build_program(gl: &GlContext, origin: &ShaderOrigin, preamble: Option<&str>) -> TemplateResult<glow::Program> {
+        match origin {
+            ShaderOrigin::Separable { stage, source } => return Self::build_separable(gl, *stage, source, preamble),
+            ShaderOrigin::Tessellated { paths } => return Self::build_tessellated(gl, paths),
+            ShaderOrigin::Files { .. } | ShaderOrigin::Inline { .. } => {}
+        }
+        
+        let (vertex_source, fragment_source) = Self::load_sources(origin, preamble)?;
+        let (program, vertex_shader, fragment_shader) = Self::start_program(gl, &vertex_source, &fragment_source)?;
+        Self::finish_program(gl, program, vertex_shader, fragment_shader)
     }
 
-    fn compile_shader(gl: &GlContext, shader_type: u32, source: &str) -> TemplateResult<glow::Shader> {
-        let shader = unsafe { gl.create_shader(shader_type) }
-            .map_err(|e| TemplateError::ShaderCompilation(e))?;
+    fn load_sources(origin: &ShaderOrigin, preamble: Option<&str>) -> TemplateResult<(String, String)> {
+        let (mut vertex_source, mut fragment_source) = match origin {
+            ShaderOrigin::Files { vertex_path, fragment_path } => {
+                tracing::info!("Compiling shader program: {} + {}", vertex_path, fragment_path);
+                (Self::load_source(vertex_path)?, Self::load_source(fragment_path)?)
+            }
+            ShaderOrigin::Inline { vertex_source, fragment_source } => {
+                tracing::info!("Compiling shader program from source");
+                (vertex_source.clone(), fragment_source.clone())
+            }
+            ShaderOrigin::Separable { .. } => unreachable!("separable stages are built by build_separable"),
+            ShaderOrigin::Tessellated { .. } => unreachable!("tessellated programs are built by build_tessellated"),
+        };
+        
+        if let Some(preamble) = preamble {
+            vertex_source = Self::inject_preamble(&vertex_source, preamble);
+            fragment_source = Self::inject_preamble(&fragment_source, preamble);
+        }
+        
+        Ok((vertex_source, fragment_source))
+    }
+
+    /// Read shader source from the embedded store (with `embedded-shaders`), falling back to
+    /// the `shaders` folder of the resource root.
+    fn load_source(path: &str) -> TemplateResult<String> {
+        #[cfg(feature = "embedded-shaders")]
+        if let Some(source) = crate::embedded::shader_source(path) {
+            return Ok(source.to_string());
+        }
         
+        Ok(std::fs::read_to_string(crate::resource_path("shaders").join(path))?)
+    }
+
+    fn inject_preamble(source: &str, preamble: &str) -> String {
+        let body = source
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("#version"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!("{preamble}{body}")
+    }
+
+    /// Issue compile and link commands without querying their status, so drivers with
+    /// `KHR_parallel_shader_compile` can work on them in the background.
+    fn start_program(gl: &GlContext, vertex_source: &str, fragment_source: &str) -> TemplateResult<(glow::Program, glow::Shader, glow::Shader)> {
+        unsafe {
+            let vertex_shader = Self::start_shader(gl, glow::VERTEX_SHADER, vertex_source)?;
+            let fragment_shader = match Self::start_shader(gl, glow::FRAGMENT_SHADER, fragment_source) {
+                Ok(shader) => shader,
+                Err(error) => {
+                    gl.delete_shader(vertex_shader);
+                    return Err(error);
+                }
+            };
+            let program = match gl.create_program() {
+                Ok(program) => program,
+                Err(error) => {
+                    gl.delete_shader(vertex_shader);
+                    gl.delete_shader(fragment_shader);
+                    return Err(TemplateError::ShaderCompilation(error));
+                }
+            };
+            crate::leaks::track_created();
+            
+            gl.attach_shader(program, vertex_shader);
+            gl.attach_shader(program, fragment_shader);
+            gl.link_program(program);
+            
+            Ok((program, vertex_shader, fragment_shader))
+        }
+    }
+
+    fn start_shader(gl: &GlContext, shader_type: u32, source: &str) -> TemplateResult<glow::Shader> {
         unsafe {
+            let shader = gl.create_shader(shader_type).map_err(TemplateError::ShaderCompilation)?;
             gl.shader_source(shader, source);
             gl.compile_shader(shader);
+            Ok(shader)
+        }
+    }
+
+    /// Compile and link a single stage with `PROGRAM_SEPARABLE` set.
+    fn build_separable(gl: &GlContext, stage: u32, source: &str, preamble: Option<&str>) -> TemplateResult<glow::Program> {
+        tracing::info!("Compiling separable {} stage", if stage == glow::VERTEX_SHADER { "vertex" } else { "fragment" });
+        let source = match preamble {
+            Some(preamble) => Self::inject_preamble(source, preamble),
+            None => source.to_string(),
+        };
+        
+        unsafe {
+            let shader = Self::start_shader(gl, stage, &source)?;
+            let program = gl.create_program().map_err(TemplateError::ShaderCompilation)?;
+            crate::leaks::track_created();
             
-            if !gl.get_shader_compile_status(shader) {
-                let error = gl.get_shader_info_log(shader);
+            crate::pipeline::set_program_separable(gl, program);
+            gl.attach_shader(program, shader);
+            gl.link_program(program);
+            
+            let error = if gl.get_shader_compile_status(shader) {
+                (!gl.get_program_link_status(program)).then(|| gl.get_program_info_log(program))
+            } else {
+                Some(gl.get_shader_info_log(shader))
+            };
+            gl.delete_shader(shader);
+            
+            if let Some(error) = error {
+                gl.delete_program(program);
+                crate::leaks::track_deleted();
                 return Err(TemplateError::ShaderCompilation(error));
             }
+            
+            Ok(program)
         }
-        
-        Ok(shader)
     }
 
-    fn link_program(gl: &GlContext, vertex_shader: glow::Shader, fragment_shader: glow::Shader) -> TemplateResult<glow::Program> {
-        let program = unsafe { gl.create_program() }
-            .map_err(|e| TemplateError::ShaderCompilation(e))?;
+    /// Compile and link the four stages of a tessellation program from files.
+    fn build_tessellated(gl: &GlContext, paths: &[String; 4]) -> TemplateResult<glow::Program> {
+        const STAGES: [u32; 4] = [glow::VERTEX_SHADER, glow::TESS_CONTROL_SHADER, glow::TESS_EVALUATION_SHADER, glow::FRAGMENT_SHADER];
+        tracing::info!("Compiling tessellation program: {}", paths.join(" + "));
+        
+        let sources = paths.iter().map(|path| Self::load_source(path)).collect::<TemplateResult<Vec<_>>>()?;
         
         unsafe {
-            gl.attach_shader(program, vertex_shader);
-            gl.attach_shader(program, fragment_shader);
+            let mut shaders = Vec::with_capacity(STAGES.len());
+            for (stage, source) in STAGES.into_iter().zip(&sources) {
+                match Self::start_shader(gl, stage, source) {
+                    Ok(shader) => shaders.push(shader),
+                    Err(error) => {
+                        for shader in shaders {
+                            gl.delete_shader(shader);
+                        }
+                        return Err(error);
+                    }
+                }
+            }
+            
+            let program = gl.create_program().map_err(TemplateError::ShaderCompilation)?;
+            crate::leaks::track_created();
+            for shader in &shaders {
+                gl.attach_shader(program, *shader);
+            }
             gl.link_program(program);
             
-            if !gl.get_program_link_status(program) {
-                let error = gl.get_program_info_log(program);
+            let error = shaders
+                .iter()
+                .find(|shader| !gl.get_shader_compile_status(**shader))
+                .map(|shader| gl.get_shader_info_log(*shader))
+                .or_else(|| (!gl.get_program_link_status(program)).then(|| gl.get_program_info_log(program)));
+            for shader in shaders {
+                gl.delete_shader(shader);
+            }
+            
+            if let Some(error) = error {
+                gl.delete_program(program);
+                crate::leaks::track_deleted();
                 return Err(TemplateError::ShaderCompilation(error));
             }
             
+            tracing::info!("Shader program compiled successfully");
+            Ok(program)
+        }
+    }
+
+    /// Wait for a started program and report the first compile or link error.
+    fn finish_program(
+        gl: &GlContext,
+        program: glow::Program,
+        vertex_shader: glow::Shader,
+        fragment_shader: glow::Shader,
+    ) -> TemplateResult<glow::Program> {
+        unsafe {
+            let error = [vertex_shader, fragment_shader]
+                .into_iter()
+                .find(|shader| !gl.get_shader_compile_status(*shader))
+                .map(|shader| gl.get_shader_info_log(shader))
+                .or_else(|| (!gl.get_program_link_status(program)).then(|| gl.get_program_info_log(program)));
+            
             gl.delete_shader(vertex_shader);
             gl.delete_shader(fragment_shader);
+            
+            if let Some(error) = error {
+                gl.delete_program(program);
+                crate::leaks::track_deleted();
+                return Err(TemplateError::ShaderCompilation(error));
+            }
         }
         
+        tracing::info!("Shader program compiled successfully");
         Ok(program)
     }
 
+    /// Start compiling a shader program without waiting for the driver.
+    /// 
+    /// With `GL_KHR_parallel_shader_compile` the driver compiles in the background; poll
+    /// `PendingShader::is_ready` and call `PendingShader::finish` once it returns true.
+    /// Without the extension compilation still happens, but `finish` blocks until it is done.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `vertex_path` - Path to vertex shader file (relative to the resource root's `shaders/`)
+    /// * `fragment_path` - Path to fragment shader file (relative to the resource root's `shaders/`)
+    /// * `preamble` - Header injected in place of the `#version` line, or `None` to keep the sources as-is
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` if a source file cannot be read, or `TemplateError::ShaderCompilation`
+    /// if the program objects cannot be created
+    pub fn begin(gl: &GlContext, vertex_path: &str, fragment_path: &str, preamble: Option<&str>) -> TemplateResult<PendingShader> {
+        let origin = ShaderOrigin::Files {
+            vertex_path: vertex_path.to_string(),
+            fragment_path: fragment_path.to_string(),
+        };
+        let (vertex_source, fragment_source) = Self::load_sources(&origin, preamble)?;
+        let (program, vertex_shader, fragment_shader) = Self::start_program(gl, &vertex_source, &fragment_source)?;
+        
+        Ok(PendingShader {
+            program,
+            vertex_shader,
+            fragment_shader,
+            origin,
+            preamble: preamble.map(str::to_string),
+        })
+    }
+
+    /// Compile several shader programs, letting the driver work on all of them at once.
+    /// 
+    /// Every program is started before any is checked, which avoids serial stalls when
+    /// `GL_KHR_parallel_shader_compile` is available and costs nothing when it is not.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `programs` - `(vertex path, fragment path)` pairs relative to the resource root's `shaders/`
+    /// * `preamble` - Header injected in place of the `#version` line, or `None` to keep the sources as-is
+    /// 
+    /// # Returns
+    /// The compiled shaders in the same order
+    /// 
+    /// # Errors
+    /// The first `TemplateError::Io` or `TemplateError::ShaderCompilation` among the programs.
+    /// Every program created so far is deleted before returning it.
+    pub fn load_all(gl: &GlContext, programs: &[(&str, &str)], preamble: Option<&str>) -> TemplateResult<Vec<Self>> {
+        let mut pending = Vec::with_capacity(programs.len());
+        for (vertex_path, fragment_path) in programs {
+            match Self::begin(gl, vertex_path, fragment_path, preamble) {
+                Ok(shader) => pending.push(shader),
+                Err(error) => {
+                    for shader in pending {
+                        shader.delete(gl);
+                    }
+                    return Err(error);
+                }
+            }
+        }
+        
+        let mut shaders = Vec::with_capacity(pending.len());
+        let mut pending = pending.into_iter();
+        while let Some(shader) = pending.next() {
+            match shader.finish(gl) {
+                Ok(shader) => shaders.push(shader),
+                Err(error) => {
+                    for shader in pending {
+                        shader.delete(gl);
+                    }
+                    for shader in shaders {
+                        shader.delete(gl);
+                    }
+                    return Err(error);
+                }
+            }
+        }
+        Ok(shaders)
+    }
+
     /// Bind this shader program for rendering.
+    /// 
+    /// The program stays bound until another program is bound or `Shader::unbind` is called.
     pub fn bind(&self, gl: &GlContext) {
+        let _span = tracing::trace_span!("shader_bind").entered();
+        crate::assert_gl_thread();
         tracing::trace!("Binding shader program");
         unsafe { gl.use_program(Some(self.program)); }
     }
 
+    /// Unbind whatever program is current, leaving no program in use.
+    /// 
+    /// Call this before handing control to GL code that assumes a clean state.
+    pub fn unbind(gl: &GlContext) {
+        tracing::trace!("Unbinding shader program");
+        unsafe { gl.use_program(None); }
+    }
+
     /// Set a 4x4 matrix uniform.
     pub fn set_matrix4(&mut self, gl: &GlContext, name: &str, matrix: &Matrix4<f32>) {
         tracing::trace!("Setting matrix uniform: {}", name);
-        let location = self.get_uniform_location(gl, name);
+        let mut value = [0.0; 16];
+        value.copy_from_slice(matrix.as_slice());
+        if let Some(location) = self.changed_location(gl, name, CachedUniform::Matrix4(value)) {
+            unsafe {
+                gl.uniform_matrix_4_f32_slice(Some(&location), false, &value);
+            }
+        }
+    }
+
+    /// Set a vec3 uniform.
+    pub fn set_vec3(&mut self, gl: &GlContext, name: &str, value: [f32; 3]) {
+        tracing::trace!("Setting vec3 uniform: {}", name);
+        if let Some(location) = self.changed_location(gl, name, CachedUniform::Vec3(value)) {
+            unsafe {
+                gl.uniform_3_f32_slice(Some(&location), &value);
+            }
+        }
+    }
+
+    /// Set the leading elements of a vec3 array uniform, e.g. `uniform vec3 positions[8]`.
+    /// 
+    /// Arrays are always uploaded; only single values are compared with the last upload.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `name` - Array name without an index
+    /// * `values` - Values for elements 0 onwards; must not exceed the declared length
+    pub fn set_vec3_array(&mut self, gl: &GlContext, name: &str, values: &[[f32; 3]]) {
+        tracing::trace!("Setting vec3 array uniform: {} ({} elements)", name, values.len());
+        if values.is_empty() {
+            return;
+        }
+        if let Some(location) = self.get_uniform_location(gl, name) {
+            // The location is element 0's, whose cached single value no longer applies
+            self.values.remove(&location);
+            unsafe {
+                gl.uniform_3_f32_slice(Some(&location), bytemuck::cast_slice(values));
+            }
+        }
+    }
+
+    /// Set a vec4 uniform.
+    pub fn set_vec4(&mut self, gl: &GlContext, name: &str, value: [f32; 4]) {
+        tracing::trace!("Setting vec4 uniform: {}", name);
+        if let Some(location) = self.changed_location(gl, name, CachedUniform::Vec4(value)) {
+            unsafe {
+                gl.uniform_4_f32_slice(Some(&location), &value);
+            }
+        }
+    }
+
+    /// Set a float uniform.
+    pub fn set_float(&mut self, gl: &GlContext, name: &str, value: f32) {
+        tracing::trace!("Setting float uniform: {}", name);
+        if let Some(location) = self.changed_location(gl, name, CachedUniform::Float(value)) {
+            unsafe {
+                gl.uniform_1_f32(Some(&location), value);
+            }
+        }
+    }
+
+    /// Set an integer uniform (also used for sampler units).
+    pub fn set_int(&mut self, gl: &GlContext, name: &str, value: i32) {
+        tracing::trace!("Setting int uniform: {}", name);
+        if let Some(location) = self.changed_location(gl, name, CachedUniform::Int(value)) {
+            unsafe {
+                gl.uniform_1_i32(Some(&location), value);
+            }
+        }
+    }
+
+    /// Set an unsigned integer uniform.
+    pub fn set_uint(&mut self, gl: &GlContext, name: &str, value: u32) {
+        tracing::trace!("Setting uint uniform: {}", name);
+        if let Some(location) = self.changed_location(gl, name, CachedUniform::Uint(value)) {
+            unsafe {
+                gl.uniform_1_u32(Some(&location), value);
+            }
+        }
+    }
+
+    /// Forget the last uploaded uniform values so the next setters upload unconditionally.
+    /// 
+    /// Call this after changing this program's uniforms with raw GL calls.
+    pub fn invalidate_uniform_values(&mut self) {
+        self.values.clear();
+    }
+
+    /// Bind several textures at once and point their sampler uniforms at the matching units.
+    /// 
+    /// The shader must be bound first.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `textures` - `(sampler uniform name, texture, texture unit)` triples
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if a unit exceeds `MAX_TEXTURE_IMAGE_UNITS`
+    pub fn bind_textures(&mut self, gl: &GlContext, textures: &[(&str, &Texture, u32)]) -> TemplateResult<()> {
+        let max_units = GlCapabilities::query(gl).max_texture_image_units;
+        
+        for (name, texture, unit) in textures {
+            if *unit >= max_units {
+                return Err(TemplateError::Texture(format!(
+                    "texture unit {unit} for sampler '{name}' exceeds MAX_TEXTURE_IMAGE_UNITS ({max_units})"
+                )));
+            }
+            texture.bind(gl, *unit);
+            self.set_int(gl, name, (*unit).cast_signed());
+        }
+        
         unsafe {
-            gl.uniform_matrix_4_f32_slice(Some(&location), false, matrix.as_slice());
+            gl.active_texture(glow::TEXTURE0);
         }
+        Ok(())
     }
 
-    fn get_uniform_location(&mut self, gl: &GlContext, name: &str) -> glow::UniformLocation {
-        *self.uniforms.entry(name.to_string()).or_insert_with(|| {
-            unsafe { gl.get_uniform_location(self.program, name).unwrap() }
-        })
+    /// Read back the current value of a 4x4 matrix uniform.
+    /// 
+    /// Meant for inspection tools; reading uniforms stalls until pending draws are done.
+    /// 
+    /// # Returns
+    /// The value, or `None` if the program has no active uniform with that name
+    pub fn get_uniform_matrix4(&mut self, gl: &GlContext, name: &str) -> Option<Matrix4<f32>> {
+        let location = self.lookup_uniform(gl, name)?;
+        let mut value = [0.0; 16];
+        unsafe {
+            gl.get_uniform_f32(self.program, &location, &mut value);
+        }
+        Some(Matrix4::from_column_slice(&value))
+    }
+
+    /// Check whether the program has an active uniform with this name.
+    /// 
+    /// Unlike the setters this never panics, so it can validate names read from files.
+    pub fn has_uniform(&mut self, gl: &GlContext, name: &str) -> bool {
+        self.lookup_uniform(gl, name).is_some()
+    }
+
+    /// Read back the current value of a vec3 uniform.
+    /// 
+    /// # Returns
+    /// The value, or `None` if the program has no active uniform with that name
+    pub fn get_uniform_vec3(&mut self, gl: &GlContext, name: &str) -> Option<[f32; 3]> {
+        let location = self.lookup_uniform(gl, name)?;
+        let mut value = [0.0; 3];
+        unsafe {
+            gl.get_uniform_f32(self.program, &location, &mut value);
+        }
+        Some(value)
+    }
+
+    /// Read back the current value of a vec4 uniform.
+    /// 
+    /// # Returns
+    /// The value, or `None` if the program has no active uniform with that name
+    pub fn get_uniform_vec4(&mut self, gl: &GlContext, name: &str) -> Option<[f32; 4]> {
+        let location = self.lookup_uniform(gl, name)?;
+        let mut value = [0.0; 4];
+        unsafe {
+            gl.get_uniform_f32(self.program, &location, &mut value);
+        }
+        Some(value)
+    }
+
+    /// Read back the current value of a float uniform.
+    /// 
+    /// # Returns
+    /// The value, or `None` if the program has no active uniform with that name
+    pub fn get_uniform_float(&mut self, gl: &GlContext, name: &str) -> Option<f32> {
+        let location = self.lookup_uniform(gl, name)?;
+        let mut value = [0.0];
+        unsafe {
+            gl.get_uniform_f32(self.program, &location, &mut value);
+        }
+        Some(value[0])
     }
+
+    /// Read back the current value of an integer (or sampler) uniform.
+    /// 
+    /// # Returns
+    /// The value, or `None` if the program has no active uniform with that name
+    pub fn get_uniform_int(&mut self, gl: &GlContext, name: &str) -> Option<i32> {
+        let location = self.lookup_uniform(gl, name)?;
+        let mut value = [0];
+        unsafe {
+            gl.get_uniform_i32(self.program, &location, &mut value);
+        }
+        Some(value[0])
+    }
+
+    fn get_uniform_location(&mut self, gl: &GlContext, name: &str) -> Option<glow::UniformLocation> {
+        match self.lookup_uniform(gl, name) {
+            Some(location) => Some(location),
+            // The fallback only declares the transform uniforms
+            None if self.fallback => None,
+            None => panic!("uniform '{name}' not found in shader program"),
+        }
+    }
+
+    /// Get the location to upload a uniform value to, or `None` if it already holds that value.
+    fn changed_location(&mut self, gl: &GlContext, name: &str, value: CachedUniform) -> Option<glow::UniformLocation> {
+        let location = self.get_uniform_location(gl, name)?;
+        if self.values.insert(location, value) == Some(value) {
+            return None;
+        }
+        Some(location)
+    }
+
+    /// Get a uniform location from the cache, querying and caching it on first use.
+    fn lookup_uniform(&mut self, gl: &GlContext, name: &str) -> Option<glow::UniformLocation> {
+        if let Some(location) = self.uniforms.get(name) {
+            return Some(*location);
+        }
+        
+        let location = unsafe { gl.get_uniform_location(self.program, name) }?;
+        self.uniforms.insert(name.to_string(), location);
+        Some(location)
+    }
+}
+
+/// Shader program whose compilation was started with `Shader::begin`.
+pub struct PendingShader {
+    program: glow::Program,
+    vertex_shader: glow::Shader,
+    fragment_shader: glow::Shader,
+    origin: ShaderOrigin,
+    preamble: Option<String>,
 }
+
+impl PendingShader {
+    /// Check without blocking whether the driver has finished compiling and linking.
+    /// 
+    /// Always true when `GL_KHR_parallel_shader_compile` is not supported.
+    pub fn is_ready(&self, gl: &GlContext) -> bool {
+        if !gl.supported_extensions().contains("GL_KHR_parallel_shader_compile") {
+            return true;
+        }
+        unsafe { gl.get_program_parameter_i32(self.program, COMPLETION_STATUS_KHR) != 0 }
+    }
+
+    /// Wait for compilation to finish and return the usable shader.
+    /// 
+    /// # Returns
+    /// The shader
+    /// 
+    /// # Errors
+    /// `TemplateError::ShaderCompilation` with the driver's log
+    pub fn finish(self, gl: &GlContext) -> TemplateResult<Shader> {
+        let program = Shader::finish_program(gl, self.program, self.vertex_shader, self.fragment_shader)?;
+        
+        Ok(Shader {
+            program,
+            uniforms: HashMap::new(),
+            values: HashMap::new(),
+            origin: self.origin,
+            preamble: self.preamble,
+            fallback: false,
+        })
+    }
+
+    /// Abandon the compilation and release its program and shader objects.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context the shader was started with
+    pub fn delete(self, gl: &GlContext) {
+        unsafe {
+            gl.delete_shader(self.vertex_shader);
+            gl.delete_shader(self.fragment_shader);
+            gl.delete_program(self.program);
+            crate::leaks::track_deleted();
+        }
+    }
+}
\ No newline at end of file
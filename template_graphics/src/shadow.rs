@@ -0,0 +1,129 @@
+//! Depth-only shadow map rendered from a light's point of view.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::{GlApi, GlContext, SceneItem, Shader, Texture};
+use crate::context::gl_size;
+use glow::HasContext;
+use nalgebra::Matrix4;
+use tracing;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Square depth texture plus the framebuffer and shader used to fill it.
+pub struct ShadowMap {
+    fbo: glow::Framebuffer,
+    depth: Texture,
+    shader: Shader,
+    size: u32,
+}
+
+impl ShadowMap {
+    /// Create a shadow map with a square depth texture.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `api` - Graphics API, used to pick the shader preamble
+    /// * `size` - Width and height of the depth texture in pixels
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the depth shader cannot be loaded,
+    /// `TemplateError::Texture` if the depth texture cannot be created, or `TemplateError::OpenGL` if
+    /// the framebuffer is incomplete
+    pub fn new(gl: &GlContext, api: GlApi, size: u32) -> TemplateResult<Self> {
+        tracing::debug!("Creating {}x{} shadow map", size, size);
+        
+        let shader = Shader::with_preamble(gl, "depth.vert", "depth.frag", Some(api.shader_preamble()))?;
+        let depth = Texture::depth_target(gl, size, size)?;
+        
+        unsafe {
+            let fbo = gl.create_framebuffer().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+            gl.framebuffer_texture_2d(glow::FRAMEBUFFER, glow::DEPTH_ATTACHMENT, glow::TEXTURE_2D, Some(depth.handle()), 0);
+            // No color attachment: tell GL not to expect one
+            gl.draw_buffers(&[glow::NONE]);
+            gl.read_buffer(glow::NONE);
+            
+            let status = gl.check_framebuffer_status(glow::FRAMEBUFFER);
+            gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+            
+            if status != glow::FRAMEBUFFER_COMPLETE {
+                gl.delete_framebuffer(fbo);
+                crate::leaks::track_deleted();
+                depth.delete(gl);
+                shader.delete(gl);
+                return Err(TemplateError::OpenGL(format!("shadow map framebuffer incomplete (status 0x{status:X})")));
+            }
+            
+            Ok(Self { fbo, depth, shader, size })
+        }
+    }
+
+    /// Render the depth of every scene item as seen from the light.
+    /// 
+    /// Leaves the shadow framebuffer bound with its viewport; the caller binds the next
+    /// target and restores the window viewport.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `items` - Items that cast shadows
+    /// * `light_view_proj` - Light projection matrix multiplied by the light view matrix
+    pub fn render_depth(&mut self, gl: &GlContext, items: &[SceneItem], light_view_proj: &Matrix4<f32>) {
+        let _span = tracing::debug_span!("shadow_depth", items = items.len()).entered();
+        
+        unsafe {
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.fbo));
+            gl.viewport(0, 0, gl_size(self.size), gl_size(self.size));
+            gl.color_mask(false, false, false, false);
+            gl.clear(glow::DEPTH_BUFFER_BIT);
+        }
+        
+        self.shader.bind(gl);
+        self.shader.set_matrix4(gl, "lightSpace", light_view_proj);
+        for item in items {
+            self.shader.set_matrix4(gl, "model", &item.model);
+            item.mesh.draw(gl);
+        }
+        
+        unsafe {
+            gl.color_mask(true, true, true, true);
+        }
+    }
+
+    /// Bind the depth texture to a texture unit for sampling in the main pass.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `unit` - Texture unit index (0 for `TEXTURE0`)
+    pub fn bind_depth_texture(&self, gl: &GlContext, unit: u32) {
+        self.depth.bind(gl, unit);
+    }
+
+    /// Get the depth texture.
+    #[must_use]
+    pub fn depth_texture(&self) -> &Texture {
+        &self.depth
+    }
+
+    /// Get the width and height of the depth texture in pixels.
+    #[must_use]
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Release the framebuffer, depth texture and shader.
+    pub fn delete(self, gl: &GlContext) {
+        unsafe {
+            gl.delete_framebuffer(self.fbo);
+            crate::leaks::track_deleted();
+        }
+        self.depth.delete(gl);
+        self.shader.delete(gl);
+    }
+}
\ No newline at end of file
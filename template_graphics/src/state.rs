@@ -0,0 +1,132 @@
+//! Fixed-function OpenGL state the renderer keeps applied.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use crate::GlContext;
+use glow::HasContext;
+
+// ============================================================
+// ====================== Types & Enums ======================
+// ============================================================
+
+/// Winding order of triangles that face the camera.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrontFace {
+    /// Counter-clockwise triangles are front faces (OpenGL default).
+    #[default]
+    Ccw,
+    /// Clockwise triangles are front faces.
+    Cw,
+}
+
+impl FrontFace {
+    /// Get the matching OpenGL constant for `front_face`.
+    #[must_use]
+    pub fn gl_mode(self) -> u32 {
+        match self {
+            FrontFace::Ccw => glow::CCW,
+            FrontFace::Cw => glow::CW,
+        }
+    }
+}
+
+/// How a transparent fragment is combined with the color already in the framebuffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// Straight alpha: `src * src_alpha + dst * (1 - src_alpha)`.
+    #[default]
+    Alpha,
+    /// Colors already multiplied by alpha: `src + dst * (1 - src_alpha)`.
+    /// 
+    /// Use for premultiplied images; `Alpha` darkens their soft edges into halos.
+    PremultipliedAlpha,
+    /// Light accumulation: `src * src_alpha + dst`.
+    Additive,
+}
+
+impl BlendMode {
+    /// Get the source and destination factors for `blend_func`.
+    #[must_use]
+    pub fn factors(self) -> (u32, u32) {
+        match self {
+            BlendMode::Alpha => (glow::SRC_ALPHA, glow::ONE_MINUS_SRC_ALPHA),
+            BlendMode::PremultipliedAlpha => (glow::ONE, glow::ONE_MINUS_SRC_ALPHA),
+            BlendMode::Additive => (glow::SRC_ALPHA, glow::ONE),
+        }
+    }
+
+    /// Set the blend function of the current context. Blending must be enabled separately.
+    pub fn apply(self, gl: &GlContext) {
+        let (source, destination) = self.factors();
+        unsafe {
+            gl.blend_func(source, destination);
+        }
+    }
+}
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Desired global GL state (capabilities, depth comparison and clear values).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderState {
+    /// Enable depth testing.
+    pub depth_test: bool,
+    /// Face to cull (`glow::BACK`, `glow::FRONT`, ...), or `None` to disable culling.
+    pub cull_face: Option<u32>,
+    /// Winding order that counts as front-facing for culling.
+    pub front_face: FrontFace,
+    /// RGBA color used by `Renderer::clear`.
+    pub clear_color: [f32; 4],
+    /// Depth value used by `Renderer::clear` (1.0 normally, 0.0 for reverse-Z).
+    pub clear_depth: f32,
+    /// Depth comparison (`glow::LESS` normally, `glow::GREATER` for reverse-Z).
+    pub depth_func: u32,
+}
+
+impl RenderState {
+    /// Apply this state to the current context.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context to configure
+    pub fn apply(&self, gl: &GlContext) {
+        unsafe {
+            if self.depth_test {
+                gl.enable(glow::DEPTH_TEST);
+            } else {
+                gl.disable(glow::DEPTH_TEST);
+            }
+            
+            match self.cull_face {
+                Some(face) => {
+                    gl.enable(glow::CULL_FACE);
+                    gl.cull_face(face);
+                }
+                None => gl.disable(glow::CULL_FACE),
+            }
+            gl.front_face(self.front_face.gl_mode());
+            
+            gl.depth_func(self.depth_func);
+            
+            let [r, g, b, a] = self.clear_color;
+            gl.clear_color(r, g, b, a);
+            gl.clear_depth_f32(self.clear_depth);
+        }
+    }
+}
+
+impl Default for RenderState {
+    fn default() -> Self {
+        Self {
+            depth_test: true,
+            cull_face: Some(glow::BACK),
+            front_face: FrontFace::Ccw,
+            clear_color: [0.2, 0.3, 0.3, 1.0], // Dark teal background
+            clear_depth: 1.0,
+            depth_func: glow::LESS,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,364 @@
+//! Bitmap-font text rendering for debug overlays.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::{GlApi, GlContext, Shader, Texture};
+use crate::context::gl_count;
+use glow::HasContext;
+use nalgebra::Matrix4;
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Glyph size in font pixels.
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+/// Atlas cell size; the spare column and row double as letter and line spacing.
+const CELL_WIDTH: usize = 6;
+const CELL_HEIGHT: usize = 8;
+/// Glyphs per atlas row.
+const ATLAS_COLUMNS: usize = 16;
+const ATLAS_WIDTH: usize = ATLAS_COLUMNS * CELL_WIDTH;
+const ATLAS_HEIGHT: usize = GLYPHS.len().div_ceil(ATLAS_COLUMNS) * CELL_HEIGHT;
+/// First character in the atlas; glyphs cover printable ASCII up to `~`.
+const FIRST_CHAR: u8 = b' ';
+/// Floats per text vertex: screen position (2), atlas UV (2) and RGBA color (4).
+const VERTEX_FLOATS: usize = 8;
+
+/// 5x7 glyphs for printable ASCII, one byte per row from top to bottom.
+/// Bit 4 is the leftmost pixel.
+const GLYPHS: [[u8; GLYPH_HEIGHT]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04], // '!'
+    [0x0A, 0x0A, 0x0A, 0x00, 0x00, 0x00, 0x00], // '"'
+    [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A], // '#'
+    [0x04, 0x0F, 0x14, 0x0E, 0x05, 0x1E, 0x04], // '$'
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03], // '%'
+    [0x0C, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0D], // '&'
+    [0x04, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00], // '\''
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02], // '('
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08], // ')'
+    [0x00, 0x04, 0x15, 0x0E, 0x15, 0x04, 0x00], // '*'
+    [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00], // '+'
+    [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08], // ','
+    [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00], // '-'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C], // '.'
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00], // '/'
+    [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E], // '0'
+    [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E], // '1'
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F], // '2'
+    [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E], // '3'
+    [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02], // '4'
+    [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E], // '5'
+    [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E], // '6'
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08], // '7'
+    [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E], // '8'
+    [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C], // '9'
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00], // ':'
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x04, 0x08], // ';'
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02], // '<'
+    [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00], // '='
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08], // '>'
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04], // '?'
+    [0x0E, 0x11, 0x01, 0x0D, 0x15, 0x15, 0x0E], // '@'
+    [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11], // 'A'
+    [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E], // 'B'
+    [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E], // 'C'
+    [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C], // 'D'
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F], // 'E'
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10], // 'F'
+    [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F], // 'G'
+    [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11], // 'H'
+    [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // 'I'
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C], // 'J'
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11], // 'K'
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F], // 'L'
+    [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11], // 'M'
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11], // 'N'
+    [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // 'O'
+    [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10], // 'P'
+    [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D], // 'Q'
+    [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11], // 'R'
+    [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E], // 'S'
+    [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // 'T'
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // 'U'
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04], // 'V'
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A], // 'W'
+    [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11], // 'X'
+    [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04], // 'Y'
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F], // 'Z'
+    [0x0E, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0E], // '['
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00], // '\\'
+    [0x0E, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0E], // ']'
+    [0x04, 0x0A, 0x11, 0x00, 0x00, 0x00, 0x00], // '^'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F], // '_'
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00], // '`'
+    [0x00, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F], // 'a'
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1E], // 'b'
+    [0x00, 0x00, 0x0E, 0x10, 0x10, 0x11, 0x0E], // 'c'
+    [0x01, 0x01, 0x0D, 0x13, 0x11, 0x11, 0x0F], // 'd'
+    [0x00, 0x00, 0x0E, 0x11, 0x1F, 0x10, 0x0E], // 'e'
+    [0x06, 0x09, 0x08, 0x1C, 0x08, 0x08, 0x08], // 'f'
+    [0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x0E], // 'g'
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11], // 'h'
+    [0x04, 0x00, 0x0C, 0x04, 0x04, 0x04, 0x0E], // 'i'
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0C], // 'j'
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12], // 'k'
+    [0x0C, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // 'l'
+    [0x00, 0x00, 0x1A, 0x15, 0x15, 0x11, 0x11], // 'm'
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11], // 'n'
+    [0x00, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E], // 'o'
+    [0x00, 0x00, 0x1E, 0x11, 0x1E, 0x10, 0x10], // 'p'
+    [0x00, 0x00, 0x0D, 0x13, 0x0F, 0x01, 0x01], // 'q'
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10], // 'r'
+    [0x00, 0x00, 0x0E, 0x10, 0x0E, 0x01, 0x1E], // 's'
+    [0x08, 0x08, 0x1C, 0x08, 0x08, 0x09, 0x06], // 't'
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D], // 'u'
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0A, 0x04], // 'v'
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0A], // 'w'
+    [0x00, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11], // 'x'
+    [0x00, 0x00, 0x11, 0x11, 0x0F, 0x01, 0x0E], // 'y'
+    [0x00, 0x00, 0x1F, 0x02, 0x04, 0x08, 0x1F], // 'z'
+    [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02], // '{'
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // '|'
+    [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08], // '}'
+    [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00], // '~'
+];
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Draws ASCII text in screen space with a built-in 5x7 pixel font.
+/// 
+/// The font atlas and vertex buffer are created once and reused. Quads are collected with
+/// `add` and drawn by `flush` in a single upload and draw call, so many strings (even in
+/// different colors) cost the same as one. `draw` is `add` followed by `flush`.
+pub struct TextRenderer {
+    shader: Shader,
+    atlas: Texture,
+    vao: glow::VertexArray,
+    vbo: glow::Buffer,
+    buffer_size: usize,
+    vertices: Vec<f32>,
+}
+
+impl TextRenderer {
+    /// Build the font atlas, compile the text shader and create the quad buffer.
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` or `TemplateError::ShaderCompilation` if the text shader cannot be loaded,
+    /// `TemplateError::Texture` if the atlas cannot be created, or `TemplateError::OpenGL` if the
+    /// quad buffer cannot be created
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(gl: &GlContext, api: GlApi) -> TemplateResult<Self> {
+        let shader = Shader::with_preamble(gl, "text.vert", "text.frag", Some(api.shader_preamble()))?;
+        let atlas = Texture::from_rgba(gl, ATLAS_WIDTH as u32, ATLAS_HEIGHT as u32, &atlas_pixels())?;
+        atlas.set_filter(gl, glow::NEAREST);
+        
+        unsafe {
+            let vao = gl.create_vertex_array().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            let vbo = gl.create_buffer().map_err(TemplateError::OpenGL)?;
+            crate::leaks::track_created();
+            
+            gl.bind_vertex_array(Some(vao));
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(vbo));
+            
+            let stride = gl_count(VERTEX_FLOATS * std::mem::size_of::<f32>());
+            gl.enable_vertex_attrib_array(0);
+            gl.vertex_attrib_pointer_f32(0, 2, glow::FLOAT, false, stride, 0);
+            gl.enable_vertex_attrib_array(1);
+            gl.vertex_attrib_pointer_f32(1, 2, glow::FLOAT, false, stride, 8);
+            gl.enable_vertex_attrib_array(2);
+            gl.vertex_attrib_pointer_f32(2, 4, glow::FLOAT, false, stride, 16);
+            
+            gl.bind_vertex_array(None);
+            
+            Ok(Self { shader, atlas, vao, vbo, buffer_size: 0, vertices: Vec::new() })
+        }
+    }
+
+    /// Draw a string with its top-left corner at a window position.
+    /// 
+    /// Text queued with `add` is drawn in the same call.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `text` - Text to draw
+    /// * `position` - Top-left corner in window pixels (origin top-left, y down)
+    /// * `scale` - Screen pixels per font pixel (1.0 gives 8 pixel tall lines)
+    /// * `color` - RGBA text color
+    /// * `viewport` - Viewport size in pixels
+    pub fn draw(&mut self, gl: &GlContext, text: &str, position: (f32, f32), scale: f32, color: [f32; 4], viewport: (u32, u32)) {
+        self.add(text, position, scale, color);
+        self.flush(gl, viewport);
+    }
+
+    /// Queue a string for the next `flush`.
+    /// 
+    /// `\n` starts a new line; characters outside printable ASCII are drawn as `?`.
+    /// 
+    /// # Arguments
+    /// * `text` - Text to draw
+    /// * `position` - Top-left corner in window pixels (origin top-left, y down)
+    /// * `scale` - Screen pixels per font pixel (1.0 gives 8 pixel tall lines)
+    /// * `color` - RGBA text color
+    pub fn add(&mut self, text: &str, position: (f32, f32), scale: f32, color: [f32; 4]) {
+        self.build_quads(text, position, scale, color);
+    }
+
+    /// Discard queued text without drawing it.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+    }
+
+    /// Draw all queued text with one upload and one draw call, then empty the queue.
+    /// 
+    /// Depth testing and face culling are left disabled afterwards.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `viewport` - Viewport size in pixels
+    #[allow(clippy::cast_precision_loss)]
+    pub fn flush(&mut self, gl: &GlContext, viewport: (u32, u32)) {
+        if self.vertices.is_empty() {
+            return;
+        }
+        
+        let (width, height) = viewport;
+        let projection = Matrix4::new_orthographic(0.0, width as f32, height as f32, 0.0, -1.0, 1.0);
+        
+        self.shader.bind(gl);
+        self.shader.set_matrix4(gl, "projection", &projection);
+        self.atlas.bind(gl, 0);
+        self.shader.set_int(gl, "fontAtlas", 0);
+        
+        unsafe {
+            gl.disable(glow::DEPTH_TEST);
+            gl.disable(glow::CULL_FACE);
+            gl.enable(glow::BLEND);
+            gl.blend_func(glow::SRC_ALPHA, glow::ONE_MINUS_SRC_ALPHA);
+            
+            let data: &[u8] = bytemuck::cast_slice(&self.vertices);
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo));
+            if data.len() > self.buffer_size {
+                gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, data, glow::DYNAMIC_DRAW);
+                self.buffer_size = data.len();
+            } else {
+                gl.buffer_sub_data_u8_slice(glow::ARRAY_BUFFER, 0, data);
+            }
+            
+            gl.bind_vertex_array(Some(self.vao));
+            gl.draw_arrays(glow::TRIANGLES, 0, gl_count(self.vertices.len() / VERTEX_FLOATS));
+            gl.bind_vertex_array(None);
+            
+            gl.disable(glow::BLEND);
+        }
+        self.vertices.clear();
+    }
+
+    /// Get the size in pixels a string would cover when drawn at `scale`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn measure(text: &str, scale: f32) -> (f32, f32) {
+        let columns = text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        let rows = text.lines().count();
+        (
+            (columns * CELL_WIDTH) as f32 * scale,
+            (rows * CELL_HEIGHT) as f32 * scale,
+        )
+    }
+
+    /// Release the shader, atlas and quad buffer.
+    pub fn delete(self, gl: &GlContext) {
+        unsafe {
+            gl.delete_vertex_array(self.vao);
+            crate::leaks::track_deleted();
+            gl.delete_buffer(self.vbo);
+            crate::leaks::track_deleted();
+        }
+        self.atlas.delete(gl);
+        self.shader.delete(gl);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn build_quads(&mut self, text: &str, (origin_x, origin_y): (f32, f32), scale: f32, color: [f32; 4]) {
+        let [r, g, b, a] = color;
+        let glyph_width = GLYPH_WIDTH as f32 * scale;
+        let glyph_height = GLYPH_HEIGHT as f32 * scale;
+        
+        for (row, line) in text.lines().enumerate() {
+            let top = origin_y + (row * CELL_HEIGHT) as f32 * scale;
+            
+            for (column, character) in line.chars().enumerate() {
+                if character == ' ' {
+                    continue;
+                }
+                
+                let left = origin_x + (column * CELL_WIDTH) as f32 * scale;
+                let (u0, v0, u1, v1) = glyph_uv(character);
+                let (right, bottom) = (left + glyph_width, top + glyph_height);
+                
+                self.vertices.extend_from_slice(&[
+                    left, top, u0, v0, r, g, b, a,
+                    left, bottom, u0, v1, r, g, b, a,
+                    right, bottom, u1, v1, r, g, b, a,
+                    right, bottom, u1, v1, r, g, b, a,
+                    right, top, u1, v0, r, g, b, a,
+                    left, top, u0, v0, r, g, b, a,
+                ]);
+            }
+        }
+    }
+}
+
+// ============================================================
+// ===================== Helper Functions =====================
+// ============================================================
+
+/// Rasterize every glyph into white RGBA pixels, transparent where the glyph is empty.
+/// 
+/// Rows are stored top to bottom, so row `r` of the image sits at `v = r / ATLAS_HEIGHT`.
+fn atlas_pixels() -> Vec<u8> {
+    let mut pixels = vec![0u8; ATLAS_WIDTH * ATLAS_HEIGHT * 4];
+    
+    for (index, glyph) in GLYPHS.iter().enumerate() {
+        let cell_x = (index % ATLAS_COLUMNS) * CELL_WIDTH;
+        let cell_y = (index / ATLAS_COLUMNS) * CELL_HEIGHT;
+        
+        for (row, bits) in glyph.iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (0x10 >> column) != 0 {
+                    let offset = ((cell_y + row) * ATLAS_WIDTH + cell_x + column) * 4;
+                    pixels[offset..offset + 4].copy_from_slice(&[255, 255, 255, 255]);
+                }
+            }
+        }
+    }
+    
+    pixels
+}
+
+/// Get the atlas UV rectangle `(u0, v0, u1, v1)` of a character, top-left to bottom-right.
+#[allow(clippy::cast_precision_loss)]
+fn glyph_uv(character: char) -> (f32, f32, f32, f32) {
+    let index = u8::try_from(character)
+        .ok()
+        .filter(|byte| (FIRST_CHAR..=b'~').contains(byte))
+        .unwrap_or(b'?') - FIRST_CHAR;
+    
+    let cell_x = usize::from(index) % ATLAS_COLUMNS * CELL_WIDTH;
+    let cell_y = usize::from(index) / ATLAS_COLUMNS * CELL_HEIGHT;
+    
+    (
+        cell_x as f32 / ATLAS_WIDTH as f32,
+        cell_y as f32 / ATLAS_HEIGHT as f32,
+        (cell_x + GLYPH_WIDTH) as f32 / ATLAS_WIDTH as f32,
+        (cell_y + GLYPH_HEIGHT) as f32 / ATLAS_HEIGHT as f32,
+    )
+}
\ No newline at end of file
@@ -0,0 +1,401 @@
+//! 2D texture creation and binding.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::{BlendMode, CompressedImage, GlContext};
+use crate::context::gl_size;
+use glow::HasContext;
+use std::path::{Path, PathBuf};
+use std::thread::JoinHandle;
+use tracing;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// RGBA pixels decoded from an image file, ready to be uploaded with `Texture::upload`.
+pub struct DecodedImage {
+    pub width: u32,
+    pub height: u32,
+    /// Pixel data (4 bytes per pixel, rows bottom to top)
+    pub pixels: Vec<u8>,
+    /// Whether the color channels are already multiplied by alpha.
+    pub premultiplied: bool,
+}
+
+impl DecodedImage {
+    /// Decode a PNG or JPEG file into RGBA pixels on the calling thread.
+    /// 
+    /// # Arguments
+    /// * `path` - Path to the image file, relative to the resource root unless absolute
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if the file cannot be read or decoded
+    pub fn decode(path: &Path) -> TemplateResult<Self> {
+        let path = &crate::resource_path(path);
+        let _span = tracing::debug_span!("decode_image", path = %path.display()).entered();
+        
+        let image = image::open(path)
+            .map_err(|e| TemplateError::Texture(format!("failed to decode {}: {}", path.display(), e)))?
+            // GL expects the first row at the bottom
+            .flipv()
+            .into_rgba8();
+        
+        Ok(Self {
+            width: image.width(),
+            height: image.height(),
+            pixels: image.into_raw(),
+            premultiplied: false,
+        })
+    }
+
+    /// Decode an image file whose colors are already multiplied by alpha.
+    /// 
+    /// Such images show dark halos with `BlendMode::Alpha`; draw textures made from
+    /// them with `BlendMode::PremultipliedAlpha`.
+    /// 
+    /// # Arguments
+    /// * `path` - Path to the image file
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if the file cannot be read or decoded
+    pub fn decode_premultiplied(path: &Path) -> TemplateResult<Self> {
+        let mut image = Self::decode(path)?;
+        image.premultiplied = true;
+        Ok(image)
+    }
+
+    /// Multiply the color channels by alpha, unless already done.
+    /// 
+    /// Premultiplied textures filter and blend without dark fringes around transparent edges.
+    pub fn premultiply(&mut self) {
+        if self.premultiplied {
+            return;
+        }
+        for pixel in self.pixels.chunks_exact_mut(4) {
+            let alpha = u16::from(pixel[3]);
+            for channel in &mut pixel[..3] {
+                // Rounded division by 255
+                *channel = u8::try_from((u16::from(*channel) * alpha + 127) / 255).unwrap_or(u8::MAX);
+            }
+        }
+        self.premultiplied = true;
+    }
+}
+
+/// A 2D RGBA texture stored on the GPU.
+pub struct Texture {
+    handle: glow::Texture,
+    width: u32,
+    height: u32,
+    premultiplied: bool,
+}
+
+impl Texture {
+    /// Create a texture from tightly packed 8-bit RGBA pixels.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `width` - Width in pixels
+    /// * `height` - Height in pixels
+    /// * `pixels` - Pixel data (4 bytes per pixel, rows bottom to top)
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if `pixels` does not hold `width * height` pixels or the texture
+    /// cannot be created
+    pub fn from_rgba(gl: &GlContext, width: u32, height: u32, pixels: &[u8]) -> TemplateResult<Self> {
+        let expected = width as usize * height as usize * 4;
+        if pixels.len() != expected {
+            return Err(TemplateError::Texture(format!(
+                "expected {} bytes for a {}x{} RGBA texture, got {}",
+                expected, width, height, pixels.len()
+            )));
+        }
+        
+        tracing::debug!("Creating {}x{} texture", width, height);
+        
+        unsafe {
+            let handle = gl.create_texture().map_err(TemplateError::Texture)?;
+            crate::leaks::track_created();
+            gl.bind_texture(glow::TEXTURE_2D, Some(handle));
+            
+            gl.tex_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                glow::RGBA8.cast_signed(),
+                gl_size(width),
+                gl_size(height),
+                0,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelUnpackData::Slice(Some(pixels)),
+            );
+            gl.generate_mipmap(glow::TEXTURE_2D);
+            
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, glow::LINEAR_MIPMAP_LINEAR.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, glow::LINEAR.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_S, glow::REPEAT.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_T, glow::REPEAT.cast_signed());
+            
+            gl.bind_texture(glow::TEXTURE_2D, None);
+            
+            Ok(Self { handle, width, height, premultiplied: false })
+        }
+    }
+
+    /// Decode an image file on a worker thread.
+    /// 
+    /// Decoding touches no GL state, so it can run anywhere. The GL upload cannot: join the
+    /// handle (or poll `is_finished`) and pass the result to `Texture::upload` on the thread
+    /// that owns the current context.
+    /// 
+    /// # Arguments
+    /// * `path` - Path to the image file
+    /// 
+    /// # Returns
+    /// A handle resolving to the decoded pixels
+    pub fn load_async(path: impl Into<PathBuf>) -> JoinHandle<TemplateResult<DecodedImage>> {
+        let path = path.into();
+        std::thread::spawn(move || DecodedImage::decode(&path))
+    }
+
+    /// Upload decoded pixels to a new texture.
+    /// 
+    /// Must be called on the thread where `gl` is current. The texture is marked
+    /// premultiplied if the image is.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `image` - Pixels produced by `Texture::load_async` or `DecodedImage::decode`
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if the texture cannot be created
+    pub fn upload(gl: &GlContext, image: &DecodedImage) -> TemplateResult<Self> {
+        let mut texture = Self::from_rgba(gl, image.width, image.height, &image.pixels)?;
+        texture.premultiplied = image.premultiplied;
+        Ok(texture)
+    }
+
+    /// Upload a block-compressed image and its mip chain to a new texture.
+    /// 
+    /// The driver keeps the blocks compressed in VRAM. Filtering is trilinear when the file
+    /// has mip levels and bilinear otherwise; compressed mips cannot be generated on the GPU.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `image` - Image from `CompressedImage::load`
+    /// 
+    /// # Returns
+    /// The texture
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if the context lacks the format's extension
+    pub fn upload_compressed(gl: &GlContext, image: &CompressedImage) -> TemplateResult<Self> {
+        if !image.is_supported(gl) {
+            return Err(TemplateError::Texture(format!(
+                "{:?} compressed textures are not supported by this context",
+                image.format
+            )));
+        }
+        
+        tracing::debug!("Creating {}x{} {:?} texture with {} levels", image.width, image.height, image.format, image.levels.len());
+        
+        unsafe {
+            let handle = gl.create_texture().map_err(TemplateError::Texture)?;
+            crate::leaks::track_created();
+            gl.bind_texture(glow::TEXTURE_2D, Some(handle));
+            
+            for (level, data) in (0_i32..).zip(&image.levels) {
+                gl.compressed_tex_image_2d(
+                    glow::TEXTURE_2D,
+                    level,
+                    image.format.gl_internal_format().cast_signed(),
+                    gl_size(crate::compressed::mip_extent(image.width, level.unsigned_abs())),
+                    gl_size(crate::compressed::mip_extent(image.height, level.unsigned_abs())),
+                    0,
+                    i32::try_from(data.len()).unwrap_or(i32::MAX),
+                    data,
+                );
+            }
+            
+            let max_level = i32::try_from(image.levels.len().saturating_sub(1)).unwrap_or(i32::MAX);
+            let min_filter = if max_level > 0 { glow::LINEAR_MIPMAP_LINEAR } else { glow::LINEAR };
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAX_LEVEL, max_level);
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, min_filter.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, glow::LINEAR.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_S, glow::REPEAT.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_T, glow::REPEAT.cast_signed());
+            
+            gl.bind_texture(glow::TEXTURE_2D, None);
+            
+            Ok(Self { handle, width: image.width, height: image.height, premultiplied: false })
+        }
+    }
+
+    /// Create an empty RGBA texture to render into (linear filtering, clamped edges, no mipmaps).
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `width` - Width in pixels
+    /// * `height` - Height in pixels
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if the texture cannot be created
+    pub fn render_target(gl: &GlContext, width: u32, height: u32) -> TemplateResult<Self> {
+        Self::render_target_with_format(gl, width, height, glow::RGBA8)
+    }
+
+    /// Create an empty render target texture with a specific 8-bit RGBA internal format.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `width` - Width in pixels
+    /// * `height` - Height in pixels
+    /// * `internal_format` - `glow::RGBA8` or `glow::SRGB8_ALPHA8`
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if the texture cannot be created
+    pub fn render_target_with_format(gl: &GlContext, width: u32, height: u32, internal_format: u32) -> TemplateResult<Self> {
+        unsafe {
+            let handle = gl.create_texture().map_err(TemplateError::Texture)?;
+            crate::leaks::track_created();
+            gl.bind_texture(glow::TEXTURE_2D, Some(handle));
+            
+            gl.tex_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                internal_format.cast_signed(),
+                gl_size(width),
+                gl_size(height),
+                0,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelUnpackData::Slice(None),
+            );
+            
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, glow::LINEAR.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, glow::LINEAR.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE.cast_signed());
+            
+            gl.bind_texture(glow::TEXTURE_2D, None);
+            
+            Ok(Self { handle, width, height, premultiplied: false })
+        }
+    }
+
+    /// Create an empty depth texture to render into (nearest filtering, clamped edges).
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `width` - Width in pixels
+    /// * `height` - Height in pixels
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if the texture cannot be created
+    pub fn depth_target(gl: &GlContext, width: u32, height: u32) -> TemplateResult<Self> {
+        unsafe {
+            let handle = gl.create_texture().map_err(TemplateError::Texture)?;
+            crate::leaks::track_created();
+            gl.bind_texture(glow::TEXTURE_2D, Some(handle));
+            
+            gl.tex_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                glow::DEPTH_COMPONENT24.cast_signed(),
+                gl_size(width),
+                gl_size(height),
+                0,
+                glow::DEPTH_COMPONENT,
+                glow::UNSIGNED_INT,
+                glow::PixelUnpackData::Slice(None),
+            );
+            
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, glow::NEAREST.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, glow::NEAREST.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE.cast_signed());
+            
+            gl.bind_texture(glow::TEXTURE_2D, None);
+            
+            Ok(Self { handle, width, height, premultiplied: false })
+        }
+    }
+
+    /// Bind the texture to a texture unit.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `unit` - Texture unit index (0 for `TEXTURE0`)
+    pub fn bind(&self, gl: &GlContext, unit: u32) {
+        crate::assert_gl_thread();
+        unsafe {
+            gl.active_texture(glow::TEXTURE0 + unit);
+            gl.bind_texture(glow::TEXTURE_2D, Some(self.handle));
+        }
+    }
+
+    /// Set the minification and magnification filter, e.g. `glow::NEAREST` for pixel art.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `filter` - `glow::NEAREST` or `glow::LINEAR`
+    pub fn set_filter(&self, gl: &GlContext, filter: u32) {
+        unsafe {
+            gl.bind_texture(glow::TEXTURE_2D, Some(self.handle));
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, filter.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, filter.cast_signed());
+            gl.bind_texture(glow::TEXTURE_2D, None);
+        }
+    }
+
+    /// Get the width in pixels.
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the height in pixels.
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Check whether the texture's colors are multiplied by alpha.
+    #[must_use]
+    pub fn is_premultiplied(&self) -> bool {
+        self.premultiplied
+    }
+
+    /// Mark whether the texture's colors are multiplied by alpha, e.g. after rendering into it.
+    pub fn set_premultiplied(&mut self, premultiplied: bool) {
+        self.premultiplied = premultiplied;
+    }
+
+    /// Get the blend mode that composites this texture correctly.
+    #[must_use]
+    pub fn blend_mode(&self) -> BlendMode {
+        if self.premultiplied {
+            BlendMode::PremultipliedAlpha
+        } else {
+            BlendMode::Alpha
+        }
+    }
+
+    /// Get the underlying OpenGL texture handle.
+    #[must_use]
+    pub fn handle(&self) -> glow::Texture {
+        self.handle
+    }
+
+    /// Release the GPU texture.
+    pub fn delete(self, gl: &GlContext) {
+        unsafe {
+            gl.delete_texture(self.handle);
+            crate::leaks::track_deleted();
+        }
+    }
+}
\ No newline at end of file
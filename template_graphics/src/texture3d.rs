@@ -0,0 +1,189 @@
+//! 3D textures, used for color-grading lookup tables.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::GlContext;
+use crate::context::gl_size;
+use glow::HasContext;
+use std::path::Path;
+use tracing;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// A cubic RGBA 3D texture stored on the GPU.
+pub struct Texture3D {
+    handle: glow::Texture,
+    size: u32,
+}
+
+impl Texture3D {
+    /// Load a color lookup table stored as a horizontal strip image.
+    /// 
+    /// The strip is `size * size` pixels wide and `size` pixels high (e.g. 256x16, 1024x32
+    /// or 4096x64). Each `size`-wide slice holds one blue level, with red increasing to the
+    /// right and green increasing downwards.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `path` - Path to the PNG or JPEG strip, relative to the resource root unless absolute
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if the image cannot be decoded, is not a `size * size` by `size`
+    /// strip, or the texture cannot be created
+    pub fn load_lut(gl: &GlContext, path: &Path) -> TemplateResult<Self> {
+        let path = &crate::resource_path(path);
+        let image = image::open(path)
+            .map_err(|e| TemplateError::Texture(format!("failed to decode {}: {}", path.display(), e)))?
+            .into_rgba8();
+        
+        let (size, pixels) = strip_to_voxels(image.width(), image.height(), image.as_raw())
+            .map_err(|reason| TemplateError::Texture(format!("LUT strip {} {}", path.display(), reason)))?;
+        tracing::debug!("Loading {}^3 LUT from {}", size, path.display());
+        
+        Self::from_rgba(gl, size, &pixels)
+    }
+
+    /// Create a cubic texture from tightly packed 8-bit RGBA voxels (x fastest, then y, then z).
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `size` - Width, height and depth in voxels
+    /// * `voxels` - Voxel data (4 bytes per voxel)
+    /// 
+    /// # Errors
+    /// `TemplateError::Texture` if `voxels` does not hold `size` cubed voxels or the texture
+    /// cannot be created
+    pub fn from_rgba(gl: &GlContext, size: u32, voxels: &[u8]) -> TemplateResult<Self> {
+        let expected = (size as usize).pow(3) * 4;
+        if voxels.len() != expected {
+            return Err(TemplateError::Texture(format!(
+                "expected {} bytes for a {}^3 RGBA texture, got {}",
+                expected, size, voxels.len()
+            )));
+        }
+        
+        unsafe {
+            let handle = gl.create_texture().map_err(TemplateError::Texture)?;
+            crate::leaks::track_created();
+            gl.bind_texture(glow::TEXTURE_3D, Some(handle));
+            
+            gl.tex_image_3d(
+                glow::TEXTURE_3D,
+                0,
+                glow::RGBA8.cast_signed(),
+                gl_size(size),
+                gl_size(size),
+                gl_size(size),
+                0,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelUnpackData::Slice(Some(voxels)),
+            );
+            
+            gl.tex_parameter_i32(glow::TEXTURE_3D, glow::TEXTURE_MIN_FILTER, glow::LINEAR.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_3D, glow::TEXTURE_MAG_FILTER, glow::LINEAR.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_3D, glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_3D, glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE.cast_signed());
+            gl.tex_parameter_i32(glow::TEXTURE_3D, glow::TEXTURE_WRAP_R, glow::CLAMP_TO_EDGE.cast_signed());
+            
+            gl.bind_texture(glow::TEXTURE_3D, None);
+            
+            Ok(Self { handle, size })
+        }
+    }
+
+    /// Bind the texture to a texture unit.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `unit` - Texture unit index (0 for `TEXTURE0`)
+    pub fn bind(&self, gl: &GlContext, unit: u32) {
+        unsafe {
+            gl.active_texture(glow::TEXTURE0 + unit);
+            gl.bind_texture(glow::TEXTURE_3D, Some(self.handle));
+        }
+    }
+
+    /// Get the edge length in voxels.
+    #[must_use]
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Release the GPU texture.
+    pub fn delete(self, gl: &GlContext) {
+        unsafe {
+            gl.delete_texture(self.handle);
+            crate::leaks::track_deleted();
+        }
+    }
+}
+
+// ============================================================
+// ===================== Helper Functions =====================
+// ============================================================
+
+/// Reorder a `size * size` by `size` LUT strip so each blue slice becomes one depth layer.
+/// 
+/// # Arguments
+/// * `width` - Strip width in pixels
+/// * `height` - Strip height in pixels, which is the LUT size
+/// * `strip` - Tightly packed RGBA rows of the strip, top row first
+/// 
+/// # Returns
+/// The LUT size and its voxels for `Texture3D::from_rgba`, or why the strip does not fit
+fn strip_to_voxels(width: u32, height: u32, strip: &[u8]) -> Result<(u32, Vec<u8>), String> {
+    let size = height;
+    let expected_width = size.checked_mul(size)
+        .ok_or_else(|| format!("is {size} pixels high, too large for a LUT"))?;
+    if width != expected_width {
+        return Err(format!("is {width}x{height}, expected {expected_width}x{size}"));
+    }
+    let expected_len = u64::from(size).pow(3) * 4;
+    if u64::try_from(strip.len()).ok() != Some(expected_len) {
+        return Err(format!("holds {} bytes, expected {} for {}x{} RGBA", strip.len(), expected_len, width, height));
+    }
+    
+    let edge = size as usize;
+    let mut voxels = Vec::with_capacity(strip.len());
+    for blue in 0..edge {
+        for green in 0..edge {
+            let row_start = (green * edge * edge + blue * edge) * 4;
+            voxels.extend_from_slice(&strip[row_start..row_start + edge * 4]);
+        }
+    }
+    Ok((size, voxels))
+}
+
+// ============================================================
+// ========================== Tests ===========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_slices_become_depth_layers() {
+        // 4x2 strip of a 2^3 LUT; each pixel's red byte is its index in the strip
+        let strip: Vec<u8> = (0..8).flat_map(|index| [index, 0, 0, 255]).collect();
+        let (size, voxels) = strip_to_voxels(4, 2, &strip).unwrap();
+        
+        assert_eq!(size, 2);
+        // Blue slice 0 is the left half of both rows, slice 1 the right half
+        let order: Vec<u8> = voxels.chunks_exact(4).map(|voxel| voxel[0]).collect();
+        assert_eq!(order, [0, 1, 4, 5, 2, 3, 6, 7]);
+    }
+
+    #[test]
+    fn strips_of_the_wrong_shape_are_rejected() {
+        assert_eq!(strip_to_voxels(8, 2, &[0; 64]).unwrap_err(), "is 8x2, expected 4x2");
+        assert_eq!(strip_to_voxels(4, 2, &[0; 16]).unwrap_err(), "holds 16 bytes, expected 32 for 4x2 RGBA");
+        assert_eq!(strip_to_voxels(0, 70_000, &[]).unwrap_err(), "is 70000 pixels high, too large for a LUT");
+    }
+}
@@ -0,0 +1,111 @@
+//! Translation, rotation and scale of a scene object, with an optional rotation pivot.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use nalgebra::{Matrix4, Point3, Translation3, UnitQuaternion, Vector3};
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Object placement that builds a model matrix.
+/// 
+/// Rotation and scale happen around `pivot` (in object space) instead of the object origin,
+/// so an off-center model can spin and grow around its own center.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    /// World-space offset applied last.
+    pub translation: Vector3<f32>,
+    /// Orientation around the pivot.
+    pub rotation: UnitQuaternion<f32>,
+    /// Per-axis scale around the pivot, applied before the rotation.
+    pub scale: Vector3<f32>,
+    /// Point the rotation is centered on, in object space.
+    pub pivot: Point3<f32>,
+}
+
+impl Transform {
+    /// Create an identity transform rotating around the object origin.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            translation: Vector3::zeros(),
+            rotation: UnitQuaternion::identity(),
+            scale: Vector3::repeat(1.0),
+            pivot: Point3::origin(),
+        }
+    }
+
+    /// Set the point the rotation is centered on.
+    /// 
+    /// # Arguments
+    /// * `pivot` - Rotation center in object space (e.g. `mesh.bounds().center()`)
+    #[must_use]
+    pub fn with_pivot(mut self, pivot: Point3<f32>) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    /// Build the model matrix `T(translation) * T(pivot) * R * S * T(-pivot)`.
+    #[must_use]
+    pub fn matrix(&self) -> Matrix4<f32> {
+        let to_pivot = Translation3::from(self.pivot.coords).to_homogeneous();
+        let from_pivot = Translation3::from(-self.pivot.coords).to_homogeneous();
+        Translation3::from(self.translation).to_homogeneous()
+            * to_pivot
+            * self.rotation.to_homogeneous()
+            * Matrix4::new_nonuniform_scaling(&self.scale)
+            * from_pivot
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================
+// ========================== Tests ===========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pivot_stays_fixed_under_rotation() {
+        let pivot = Point3::new(2.0, 1.0, -3.0);
+        let mut transform = Transform::new().with_pivot(pivot);
+        transform.rotation = UnitQuaternion::from_euler_angles(0.3, 1.2, -0.7);
+        
+        let moved = transform.matrix().transform_point(&pivot);
+        assert!((moved - pivot).norm() < 1e-5);
+        
+        // Other points do move, and translation shifts the pivot as a whole
+        let corner = Point3::new(0.0, 0.0, 0.0);
+        assert!((transform.matrix().transform_point(&corner) - corner).norm() > 1e-2);
+        transform.translation = Vector3::new(5.0, 0.0, 0.0);
+        let shifted = transform.matrix().transform_point(&pivot);
+        assert!((shifted - (pivot + transform.translation)).norm() < 1e-5);
+    }
+
+    #[test]
+    fn pivot_stays_fixed_under_scale_and_rotation() {
+        let pivot = Point3::new(2.0, 1.0, -3.0);
+        let mut transform = Transform::new().with_pivot(pivot);
+        transform.rotation = UnitQuaternion::from_euler_angles(0.3, 1.2, -0.7);
+        transform.scale = Vector3::new(2.0, 0.5, 3.0);
+        
+        let moved = transform.matrix().transform_point(&pivot);
+        assert!((moved - pivot).norm() < 1e-5);
+        
+        // A point one unit from the pivot ends up scaled, then rotated, about the pivot
+        let offset = Vector3::new(1.0, 1.0, 1.0);
+        let expected = pivot + transform.rotation * offset.component_mul(&transform.scale);
+        let actual = transform.matrix().transform_point(&(pivot + offset));
+        assert!((actual - expected).norm() < 1e-5);
+    }
+}
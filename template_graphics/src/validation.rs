@@ -0,0 +1,229 @@
+//! Detection and repair of broken geometry before it reaches the GPU.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use nalgebra::Vector3;
+use std::fmt;
+
+// ============================================================
+// ========================== Types ===========================
+// ============================================================
+
+/// A problem found in mesh data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeshIssue {
+    /// A vertex position contains NaN or infinity.
+    NonFinitePosition { vertex: usize },
+    /// A triangle references a vertex that does not exist.
+    IndexOutOfBounds { triangle: usize, index: u32 },
+    /// A triangle has (nearly) zero area and would rasterize to nothing.
+    DegenerateTriangle { triangle: usize },
+    /// The index count is not a multiple of three; the trailing indices are unused.
+    IncompleteTriangle { indices: usize },
+}
+
+impl fmt::Display for MeshIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFinitePosition { vertex } => write!(f, "vertex {vertex} has a non-finite position"),
+            Self::IndexOutOfBounds { triangle, index } => write!(f, "triangle {triangle} uses out-of-range index {index}"),
+            Self::DegenerateTriangle { triangle } => write!(f, "triangle {triangle} has zero area"),
+            Self::IncompleteTriangle { indices } => write!(f, "{indices} trailing indices do not form a triangle"),
+        }
+    }
+}
+
+/// What mesh builders do with indexed triangles that fail `validate_mesh`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidTriangles {
+    /// Log the issues and keep the triangles; out-of-range indices are still an error.
+    #[default]
+    Report,
+    /// Log the issues and remove triangles that are out of range, degenerate or touch a
+    /// non-finite vertex.
+    Drop,
+}
+
+impl InvalidTriangles {
+    /// Validate indexed triangles and handle the invalid ones as this policy says.
+    /// 
+    /// # Arguments
+    /// * `positions` - Vertex positions
+    /// * `indices` - Three vertex indices per triangle
+    /// 
+    /// # Errors
+    /// `TemplateError::InvalidMesh` if an index is out of range and the policy is `Report`
+    pub fn apply(self, positions: &[[f32; 3]], indices: &[u32]) -> TemplateResult<Vec<u32>> {
+        if self == Self::Drop {
+            return Ok(drop_invalid_triangles(positions, indices));
+        }
+        
+        if let Err(issues) = validate_mesh(positions, indices) {
+            log_issues(&issues);
+            if let Some(MeshIssue::IndexOutOfBounds { index, .. }) = issues.iter().find(|issue| matches!(issue, MeshIssue::IndexOutOfBounds { .. })) {
+                return Err(TemplateError::InvalidMesh(format!("index {index} out of range for {} vertices", positions.len())));
+            }
+        }
+        Ok(indices.to_vec())
+    }
+}
+
+// ============================================================
+// ==================== Global Functions ======================
+// ============================================================
+
+/// Check indexed triangle data for NaN positions, out-of-range indices and zero-area triangles.
+/// 
+/// # Arguments
+/// * `positions` - Vertex positions
+/// * `indices` - Three vertex indices per triangle
+/// 
+/// # Errors
+/// Every issue found, if the data is not clean
+pub fn validate_mesh(positions: &[[f32; 3]], indices: &[u32]) -> Result<(), Vec<MeshIssue>> {
+    let mut issues: Vec<MeshIssue> = positions
+        .iter()
+        .enumerate()
+        .filter(|(_, position)| !position.iter().all(|value| value.is_finite()))
+        .map(|(vertex, _)| MeshIssue::NonFinitePosition { vertex })
+        .collect();
+    
+    let triangles = indices.chunks_exact(3);
+    if !triangles.remainder().is_empty() {
+        issues.push(MeshIssue::IncompleteTriangle { indices: triangles.remainder().len() });
+    }
+    
+    for (triangle, corners) in triangles.enumerate() {
+        if let Some(issue) = check_triangle(positions, triangle, corners) {
+            issues.push(issue);
+        }
+    }
+    
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues)
+    }
+}
+
+/// Remove every triangle that is out of range, degenerate or touches a non-finite vertex.
+/// 
+/// Each issue is logged as a warning.
+/// 
+/// # Arguments
+/// * `positions` - Vertex positions
+/// * `indices` - Three vertex indices per triangle
+/// 
+/// # Returns
+/// The indices of the remaining triangles
+#[must_use]
+pub fn drop_invalid_triangles(positions: &[[f32; 3]], indices: &[u32]) -> Vec<u32> {
+    if let Err(issues) = validate_mesh(positions, indices) {
+        log_issues(&issues);
+    }
+    
+    indices
+        .chunks_exact(3)
+        .enumerate()
+        .filter(|(triangle, corners)| check_triangle(positions, *triangle, corners).is_none())
+        .flat_map(|(_, corners)| corners.iter().copied())
+        .collect()
+}
+
+/// Log mesh issues as warnings, summarizing long lists.
+pub fn log_issues(issues: &[MeshIssue]) {
+    const MAX_LOGGED: usize = 10;
+    
+    for issue in issues.iter().take(MAX_LOGGED) {
+        tracing::warn!("Mesh issue: {}", issue);
+    }
+    if issues.len() > MAX_LOGGED {
+        tracing::warn!("... and {} more mesh issues", issues.len() - MAX_LOGGED);
+    }
+}
+
+// ============================================================
+// ===================== Helper Functions =====================
+// ============================================================
+
+/// Find the first problem with one triangle, if any.
+fn check_triangle(positions: &[[f32; 3]], triangle: usize, corners: &[u32]) -> Option<MeshIssue> {
+    let mut points = [Vector3::zeros(); 3];
+    
+    for (point, &index) in points.iter_mut().zip(corners) {
+        let Some(position) = positions.get(index as usize) else {
+            return Some(MeshIssue::IndexOutOfBounds { triangle, index });
+        };
+        if !position.iter().all(|value| value.is_finite()) {
+            // Already reported once per vertex; treat the triangle as degenerate
+            return Some(MeshIssue::DegenerateTriangle { triangle });
+        }
+        *point = Vector3::from(*position);
+    }
+    
+    let doubled_area = (points[1] - points[0]).cross(&(points[2] - points[0])).norm();
+    (doubled_area <= f32::EPSILON).then_some(MeshIssue::DegenerateTriangle { triangle })
+}
+
+// ============================================================
+// ========================== Tests ===========================
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A unit square split into two triangles.
+    const SQUARE: [[f32; 3]; 4] = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]];
+
+    #[test]
+    fn clean_meshes_pass() {
+        assert_eq!(validate_mesh(&SQUARE, &[0, 1, 2, 0, 2, 3]), Ok(()));
+    }
+
+    #[test]
+    fn nan_positions_are_reported() {
+        let mut positions = SQUARE;
+        positions[3][1] = f32::NAN;
+        
+        let issues = validate_mesh(&positions, &[0, 1, 2, 0, 2, 3]).unwrap_err();
+        assert_eq!(issues, vec![MeshIssue::NonFinitePosition { vertex: 3 }, MeshIssue::DegenerateTriangle { triangle: 1 }]);
+    }
+
+    #[test]
+    fn zero_area_triangles_are_reported() {
+        // Collinear corners and a repeated corner
+        let issues = validate_mesh(&SQUARE, &[0, 1, 1, 0, 2, 3, 1, 1, 1]).unwrap_err();
+        assert_eq!(issues, vec![MeshIssue::DegenerateTriangle { triangle: 0 }, MeshIssue::DegenerateTriangle { triangle: 2 }]);
+    }
+
+    #[test]
+    fn out_of_range_and_incomplete_indices_are_reported() {
+        let issues = validate_mesh(&SQUARE, &[0, 1, 4, 0, 2, 3, 1]).unwrap_err();
+        assert_eq!(issues, vec![MeshIssue::IncompleteTriangle { indices: 1 }, MeshIssue::IndexOutOfBounds { triangle: 0, index: 4 }]);
+    }
+
+    #[test]
+    fn invalid_triangles_are_dropped() {
+        let mut positions = SQUARE.to_vec();
+        positions.push([f32::INFINITY, 0.0, 0.0]);
+        
+        // Valid, out of range, degenerate, touching the infinite vertex, valid, then a trailing index
+        let indices = [0, 1, 2, 0, 2, 9, 0, 0, 1, 0, 1, 4, 0, 2, 3, 2];
+        assert_eq!(drop_invalid_triangles(&positions, &indices), vec![0, 1, 2, 0, 2, 3]);
+    }
+
+    #[test]
+    fn invalid_triangles_are_only_dropped_on_request() {
+        let indices = [0, 1, 2, 0, 0, 1, 0, 2, 3];
+        assert_eq!(InvalidTriangles::default().apply(&SQUARE, &indices).unwrap(), indices.to_vec());
+        assert_eq!(InvalidTriangles::Drop.apply(&SQUARE, &indices).unwrap(), vec![0, 1, 2, 0, 2, 3]);
+        
+        let error = InvalidTriangles::Report.apply(&SQUARE, &[0, 1, 4]).unwrap_err();
+        assert!(matches!(error, TemplateError::InvalidMesh(_)), "{error:?}");
+        assert_eq!(InvalidTriangles::Drop.apply(&SQUARE, &[0, 1, 4]).unwrap(), Vec::<u32>::new());
+    }
+}
\ No newline at end of file
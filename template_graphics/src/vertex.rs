@@ -0,0 +1,215 @@
+//! Vertex attribute layouts describing how interleaved buffers map to shader inputs.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use crate::{GlContext, Shader};
+use crate::context::gl_count;
+use glow::HasContext;
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Vertex input names used by the bundled shaders for `VertexLayout::position_color` data.
+pub const POSITION_COLOR_NAMES: [&str; 2] = ["aPos", "aColor"];
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// A single shader input read from an interleaved vertex buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VertexAttribute {
+    /// Shader attribute location.
+    pub location: u32,
+    /// Number of components (1 to 4).
+    pub components: i32,
+    /// OpenGL component type (e.g. `glow::FLOAT`, `glow::UNSIGNED_BYTE`).
+    pub data_type: u32,
+    /// Map integer components to the 0..1 range in the shader.
+    pub normalized: bool,
+}
+
+impl VertexAttribute {
+    /// Create an attribute made of 32-bit floats.
+    #[must_use]
+    pub fn float(location: u32, components: i32) -> Self {
+        Self { location, components, data_type: glow::FLOAT, normalized: false }
+    }
+
+    /// Create an attribute made of unsigned bytes read as normalized floats (0..255 becomes 0..1).
+    #[must_use]
+    pub fn normalized_u8(location: u32, components: i32) -> Self {
+        Self { location, components, data_type: glow::UNSIGNED_BYTE, normalized: true }
+    }
+
+    /// Get the size of this attribute in bytes.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        let component_size = match self.data_type {
+            glow::UNSIGNED_BYTE | glow::BYTE => 1,
+            glow::UNSIGNED_SHORT | glow::SHORT | glow::HALF_FLOAT => 2,
+            _ => 4,
+        };
+        component_size * usize::try_from(self.components).unwrap_or(0)
+    }
+}
+
+/// Ordered list of attributes packed back to back in each vertex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VertexLayout {
+    attributes: Vec<VertexAttribute>,
+}
+
+impl VertexLayout {
+    /// Create a layout from attributes in the order they appear in each vertex.
+    #[must_use]
+    pub fn new(attributes: Vec<VertexAttribute>) -> Self {
+        Self { attributes }
+    }
+
+    /// Position (3 floats) followed by an RGB or RGBA float color, as used by `Mesh::new` and `Mesh::new_rgba`.
+    #[must_use]
+    pub fn position_color(color_size: i32) -> Self {
+        Self::new(vec![VertexAttribute::float(0, 3), VertexAttribute::float(1, color_size)])
+    }
+
+    /// Position (3 floats) followed by an RGBA color packed into 4 normalized bytes.
+    #[must_use]
+    pub fn position_color_u8() -> Self {
+        Self::new(vec![VertexAttribute::float(0, 3), VertexAttribute::normalized_u8(1, 4)])
+    }
+
+    /// Position (3 floats), normal (3), texture coordinate (2) and tangent (4), matching `TangentVertex`.
+    #[must_use]
+    pub fn position_normal_uv_tangent() -> Self {
+        Self::new(vec![
+            VertexAttribute::float(0, 3),
+            VertexAttribute::float(1, 3),
+            VertexAttribute::float(2, 2),
+            VertexAttribute::float(3, 4),
+        ])
+    }
+
+    /// Get the attributes in vertex order.
+    #[must_use]
+    pub fn attributes(&self) -> &[VertexAttribute] {
+        &self.attributes
+    }
+
+    /// Get the size of one vertex in bytes.
+    pub fn stride(&self) -> usize {
+        self.attributes.iter().map(VertexAttribute::size).sum()
+    }
+
+    /// Copy this layout with each attribute moved to the location a shader gives its name.
+    /// 
+    /// Use this for shaders without `layout (location = N)` qualifiers, whose locations
+    /// are picked by the linker.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    /// * `shader` - Linked shader to query
+    /// * `names` - Vertex input name of each attribute, in vertex order
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the name count differs or an input is missing from the shader
+    pub fn resolve_locations(&self, gl: &GlContext, shader: &Shader, names: &[&str]) -> TemplateResult<Self> {
+        if names.len() != self.attributes.len() {
+            return Err(TemplateError::OpenGL(format!(
+                "{} attribute names given for a layout with {} attributes",
+                names.len(), self.attributes.len()
+            )));
+        }
+        
+        let attributes = self
+            .attributes
+            .iter()
+            .zip(names)
+            .map(|(attribute, name)| {
+                let location = shader.attribute_location(gl, name).ok_or_else(|| {
+                    TemplateError::OpenGL(format!("shader has no active vertex input '{name}'"))
+                })?;
+                Ok(VertexAttribute { location, ..*attribute })
+            })
+            .collect::<TemplateResult<Vec<_>>>()?;
+        Ok(Self::new(attributes))
+    }
+
+    /// Disable the attribute arrays of this layout on the currently bound VAO.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    pub fn disable(&self, gl: &GlContext) {
+        for attribute in &self.attributes {
+            unsafe {
+                gl.disable_vertex_attrib_array(attribute.location);
+            }
+        }
+    }
+
+    /// Configure the attribute pointers of the currently bound VAO and array buffer.
+    /// 
+    /// # Arguments
+    /// * `gl` - OpenGL context
+    pub fn apply(&self, gl: &GlContext) {
+        let stride = gl_count(self.stride());
+        let mut offset = 0;
+        
+        for attribute in &self.attributes {
+            unsafe {
+                gl.enable_vertex_attrib_array(attribute.location);
+                gl.vertex_attrib_pointer_f32(
+                    attribute.location,
+                    attribute.components,
+                    attribute.data_type,
+                    attribute.normalized,
+                    stride,
+                    offset,
+                );
+            }
+            offset += gl_count(attribute.size());
+        }
+    }
+}
+
+/// Vertex with a float position and a packed 8-bit RGBA color (16 bytes instead of 28).
+/// 
+/// Upload with `Mesh::new_packed_rgba` and draw with `basic_rgba.vert` + `basic_rgba.frag`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PackedColorVertex {
+    pub position: [f32; 3],
+    pub color: [u8; 4],
+}
+
+impl PackedColorVertex {
+    /// Create a vertex from a position and RGBA bytes.
+    #[must_use]
+    pub fn new(position: [f32; 3], color: [u8; 4]) -> Self {
+        Self { position, color }
+    }
+}
+
+// SAFETY: `PackedColorVertex` is `repr(C)`, has no padding (12 + 4 bytes) and only plain-data fields.
+unsafe impl bytemuck::Zeroable for PackedColorVertex {}
+unsafe impl bytemuck::Pod for PackedColorVertex {}
+
+/// Vertex for normal-mapped surfaces, drawn with `normal_mapped.vert` + `normal_mapped.frag`.
+/// 
+/// The tangent's `w` is the bitangent sign (-1 where the texture is mirrored).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TangentVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub uv: [f32; 2],
+    pub tangent: [f32; 4],
+}
+
+// SAFETY: `TangentVertex` is `repr(C)`, has no padding (12 f32 fields) and only plain-data fields.
+unsafe impl bytemuck::Zeroable for TangentVertex {}
+unsafe impl bytemuck::Pod for TangentVertex {}
\ No newline at end of file
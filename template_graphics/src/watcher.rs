@@ -0,0 +1,61 @@
+//! Shader hot-reload file watching (requires the `hot-reload` feature).
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_core::{TemplateResult, TemplateError};
+use notify::{RecursiveMode, Watcher};
+use std::{collections::HashSet, sync::mpsc};
+use tracing;
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// Watches the `shaders` folder of the resource root and queues the names of modified shader files.
+pub struct ShaderWatcher {
+    _watcher: notify::RecommendedWatcher,
+    receiver: mpsc::Receiver<String>,
+}
+
+impl ShaderWatcher {
+    /// Start watching the shader directory.
+    /// 
+    /// # Errors
+    /// `TemplateError::Io` if the platform watcher cannot be created or the directory cannot be watched
+    pub fn new() -> TemplateResult<Self> {
+        let (sender, receiver) = mpsc::channel();
+        
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+            match result {
+                Ok(event) if event.kind.is_modify() || event.kind.is_create() => {
+                    for path in event.paths {
+                        if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+                            let _ = sender.send(name.to_string());
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Shader watcher error: {}", e),
+            }
+        })
+        .map_err(|e| TemplateError::Io(std::io::Error::other(e.to_string())))?;
+        
+        let directory = crate::resource_path("shaders");
+        watcher
+            .watch(&directory, RecursiveMode::Recursive)
+            .map_err(|e| TemplateError::Io(std::io::Error::other(e.to_string())))?;
+        
+        tracing::info!("Watching {} for changes", directory.display());
+        Ok(Self { _watcher: watcher, receiver })
+    }
+
+    /// Drain the queue of changed shader file names (relative to the resource root's `shaders/`).
+    /// 
+    /// Editors often emit several events per save, so names are deduplicated.
+    #[must_use]
+    pub fn changed_files(&self) -> HashSet<String> {
+        self.receiver.try_iter().collect()
+    }
+}
\ No newline at end of file
@@ -8,78 +8,187 @@ use template_core::{TemplateResult, TemplateError};
 use glutin::{display::GetGlDisplay, prelude::*, surface::GlSurface, context::NotCurrentGlContext};
 use raw_window_handle::HasWindowHandle;
 use std::num::NonZeroU32;
-use tracing::info;
-use winit::event_loop::EventLoop;
+use tracing::{info, warn};
+use crate::{GlApi, SwapMode};
+use crate::context::gl_size;
+
+// ============================================================
+// ======================== Constants =========================
+// ============================================================
+
+/// Desktop OpenGL versions requested in order until the driver accepts one.
+/// 
+/// After the last entry a context without a requested version is tried.
+const DESKTOP_VERSION_LADDER: [(u8, u8); 3] = [(4, 6), (4, 3), (3, 3)];
+
+/// OpenGL ES versions requested in order; 3.0 is the minimum the shaders need.
+const GLES_VERSION_LADDER: [(u8, u8); 3] = [(3, 2), (3, 1), (3, 0)];
+
+// ============================================================
+// ========================= Traits ===========================
+// ============================================================
+
+/// Event loop handle windows can be created from: an `EventLoop` before `run_app`, or the
+/// `ActiveEventLoop` passed to handlers while it runs.
+/// 
+/// glutin-winit's own event loop trait is sealed, so this forwards to it per type.
+pub trait WindowEventLoop {
+    /// Create a window and pick a GL config for it.
+    /// 
+    /// # Errors
+    /// The display builder's error if the window cannot be created or no GL config is offered
+    fn build_display(
+        &self,
+        builder: glutin_winit::DisplayBuilder,
+        template: glutin::config::ConfigTemplateBuilder,
+    ) -> Result<(Option<winit::window::Window>, glutin::config::Config), Box<dyn std::error::Error>>;
+}
+
+impl<T> WindowEventLoop for winit::event_loop::EventLoop<T> {
+    fn build_display(
+        &self,
+        builder: glutin_winit::DisplayBuilder,
+        template: glutin::config::ConfigTemplateBuilder,
+    ) -> Result<(Option<winit::window::Window>, glutin::config::Config), Box<dyn std::error::Error>> {
+        builder.build(self, template, pick_config)
+    }
+}
+
+impl WindowEventLoop for winit::event_loop::ActiveEventLoop {
+    fn build_display(
+        &self,
+        builder: glutin_winit::DisplayBuilder,
+        template: glutin::config::ConfigTemplateBuilder,
+    ) -> Result<(Option<winit::window::Window>, glutin::config::Config), Box<dyn std::error::Error>> {
+        builder.build(self, template, pick_config)
+    }
+}
 
 // ============================================================
 // ===================== Structs & Impls ======================
 // ============================================================
 
 /// Window wrapper with OpenGL context and surface.
+/// 
+/// Fields drop in declaration order: the context and surface are destroyed before the
+/// display connection and the native window they were created for.
 pub struct Window {
-    handle: winit::window::Window,
     context: glutin::context::PossiblyCurrentContext,
-    display: glutin::display::Display,
     surface: glutin::surface::Surface<glutin::surface::WindowSurface>,
+    config: glutin::config::Config,
+    display: glutin::display::Display,
+    handle: winit::window::Window,
+    api: GlApi,
+    swap_mode: SwapMode,
 }
 
 impl Window {
     /// Create a new window with the specified dimensions and title.
-    pub fn new(width: u32, height: u32, title: &str, event_loop: &EventLoop<()>) -> TemplateResult<Self> {
+    /// 
+    /// # Errors
+    /// `TemplateError::WindowCreation` if the window, context or surface cannot be created
+    pub fn new(
+        width: u32,
+        height: u32,
+        title: &str,
+        api: GlApi,
+        swap_mode: SwapMode,
+        visible: bool,
+        event_loop: &impl WindowEventLoop,
+    ) -> TemplateResult<Self> {
         info!("Creating window {}x{}", width, height);
         
-        let (window, gl_config) = Self::create_window_and_config(width, height, title, event_loop)?;
+        let (window, gl_config) = Self::create_window_and_config(width, height, title, api, visible, event_loop)?;
         let display = gl_config.display();
-        let context = Self::create_context(&window, &gl_config, &display)?;
+        let context = Self::create_context(&window, &gl_config, &display, api)?;
         let surface = Self::create_surface(&window, &gl_config, &display, width, height)?;
         let context = Self::make_context_current(context, &surface)?;
         
-        Self::configure_surface(&surface, &context)?;
+        Self::configure_surface(&display, &surface, &context, swap_mode)?;
         
         info!("Window created successfully");
-        Ok(Self { handle: window, context, display, surface })
+        Ok(Self { handle: window, context, display, surface, config: gl_config, api, swap_mode })
     }
 
     fn create_window_and_config(
         width: u32, 
         height: u32, 
         title: &str, 
-        event_loop: &EventLoop<()>
+        api: GlApi,
+        visible: bool,
+        event_loop: &impl WindowEventLoop
     ) -> TemplateResult<(winit::window::Window, glutin::config::Config)> {
         let window_attributes = winit::window::Window::default_attributes()
             .with_title(title)
-            .with_inner_size(winit::dpi::LogicalSize::new(width, height));
+            .with_inner_size(winit::dpi::LogicalSize::new(width, height))
+            .with_visible(visible);
         
-        let template = glutin::config::ConfigTemplateBuilder::new()
+        let mut template = glutin::config::ConfigTemplateBuilder::new()
             .with_alpha_size(8);
         
+        if api == GlApi::Gles {
+            template = template.with_api(glutin::config::Api::GLES3);
+        }
+        
         let display_builder = glutin_winit::DisplayBuilder::new()
             .with_window_attributes(Some(window_attributes));
         
-        let (window, gl_config) = display_builder
-            .build(event_loop, template, |mut configs| {
-                configs.next().unwrap()
-            })
+        let (window, gl_config) = event_loop
+            .build_display(display_builder, template)
             .map_err(|e| TemplateError::WindowCreation(e.to_string()))?;
         
         Ok((window.unwrap(), gl_config))
     }
 
+    /// Create a context, stepping down the version ladder of `api` until the driver accepts one.
     fn create_context(
         window: &winit::window::Window,
         gl_config: &glutin::config::Config,
-        display: &glutin::display::Display
+        display: &glutin::display::Display,
+        api: GlApi
     ) -> TemplateResult<glutin::context::NotCurrentContext> {
         let window_handle = window.window_handle()
             .map_err(|e| TemplateError::WindowCreation(e.to_string()))?;
         
-        let context_attributes = glutin::context::ContextAttributesBuilder::new()
-            .build(Some(window_handle.as_raw()));
+        let versions: Vec<Option<glutin::context::Version>> = match api {
+            GlApi::OpenGl => DESKTOP_VERSION_LADDER
+                .iter()
+                .map(|&(major, minor)| Some(glutin::context::Version::new(major, minor)))
+                .chain(std::iter::once(None))
+                .collect(),
+            GlApi::Gles => GLES_VERSION_LADDER
+                .iter()
+                .map(|&(major, minor)| Some(glutin::context::Version::new(major, minor)))
+                .collect(),
+        };
         
-        unsafe {
-            display.create_context(gl_config, &context_attributes)
-                .map_err(|e| TemplateError::WindowCreation(e.to_string()))
+        let mut last_error = String::from("no context versions to try");
+        for version in versions {
+            let context_api = match api {
+                GlApi::OpenGl => glutin::context::ContextApi::OpenGl(version),
+                GlApi::Gles => glutin::context::ContextApi::Gles(version),
+            };
+            let context_attributes = glutin::context::ContextAttributesBuilder::new()
+                .with_context_api(context_api)
+                .build(Some(window_handle.as_raw()));
+            
+            match unsafe { display.create_context(gl_config, &context_attributes) } {
+                Ok(context) => {
+                    if let Some(version) = version {
+                        info!("Created {:?} {}.{} context", api, version.major, version.minor);
+                    } else {
+                        info!("Created {:?} context with the driver's default version", api);
+                    }
+                    return Ok(context);
+                }
+                Err(e) => {
+                    warn!("{:?} context request {:?} failed: {}", api, version, e);
+                    last_error = e.to_string();
+                }
+            }
         }
+        
+        Err(TemplateError::WindowCreation(last_error))
     }
 
     fn create_surface(
@@ -110,21 +219,196 @@ impl Window {
     }
 
     fn configure_surface(
+        display: &glutin::display::Display,
         surface: &glutin::surface::Surface<glutin::surface::WindowSurface>,
-        context: &glutin::context::PossiblyCurrentContext
+        context: &glutin::context::PossiblyCurrentContext,
+        swap_mode: SwapMode
     ) -> TemplateResult<()> {
-        surface.set_swap_interval(context, glutin::surface::SwapInterval::Wait(NonZeroU32::MIN))
+        let interval = match swap_mode {
+            SwapMode::Vsync => glutin::surface::SwapInterval::Wait(NonZeroU32::MIN),
+            SwapMode::Adaptive => {
+                if Self::set_adaptive_swap_interval(display, surface) {
+                    return Ok(());
+                }
+                warn!("Adaptive vsync needs the swap_control_tear extension, falling back to vsync");
+                glutin::surface::SwapInterval::Wait(NonZeroU32::MIN)
+            }
+            SwapMode::Off => glutin::surface::SwapInterval::DontWait,
+        };
+        
+        surface.set_swap_interval(context, interval)
             .map_err(|e| TemplateError::WindowCreation(e.to_string()))
     }
 
+    /// Set a swap interval of -1 through `GLX_EXT_swap_control_tear` or `WGL_EXT_swap_control_tear`.
+    /// 
+    /// glutin's `SwapInterval` cannot express negative intervals, so the extension entry
+    /// point is called on the raw display. Returns `false` when the display lacks the
+    /// extension (always the case for EGL and CGL) and the interval was left unchanged.
+    /// The context must be current.
+    fn set_adaptive_swap_interval(
+        display: &glutin::display::Display,
+        surface: &glutin::surface::Surface<glutin::surface::WindowSurface>,
+    ) -> bool {
+        match (display, surface) {
+            #[cfg(all(unix, not(target_vendor = "apple"), not(target_os = "android"), not(target_family = "wasm")))]
+            (glutin::display::Display::Glx(display), glutin::surface::Surface::Glx(surface)) => {
+                use glutin::display::{AsRawDisplay, GetDisplayExtensions, RawDisplay};
+                use glutin::surface::{AsRawSurface, RawSurface};
+                
+                if !display.extensions().contains("GLX_EXT_swap_control_tear") {
+                    return false;
+                }
+                let (RawDisplay::Glx(raw_display), RawSurface::Glx(drawable)) = (display.raw_display(), surface.raw_surface()) else {
+                    return false;
+                };
+                // XIDs are `unsigned long`, which is narrower than u64 on 32-bit targets
+                #[allow(irrefutable_let_patterns)]
+                let Ok(drawable) = std::ffi::c_ulong::try_from(drawable) else {
+                    return false;
+                };
+                let swap_interval = display.get_proc_address(c"glXSwapIntervalEXT");
+                if swap_interval.is_null() {
+                    return false;
+                }
+                
+                // SAFETY: the pointer is glXSwapIntervalEXT, which the extension string advertises
+                unsafe {
+                    let swap_interval: unsafe extern "C" fn(*const std::ffi::c_void, std::ffi::c_ulong, std::ffi::c_int) = std::mem::transmute(swap_interval);
+                    swap_interval(raw_display, drawable, -1);
+                }
+                true
+            }
+            #[cfg(windows)]
+            (glutin::display::Display::Wgl(display), glutin::surface::Surface::Wgl(_)) => {
+                use glutin::display::GetDisplayExtensions;
+                
+                if !display.extensions().contains("WGL_EXT_swap_control_tear") {
+                    return false;
+                }
+                let swap_interval = display.get_proc_address(c"wglSwapIntervalEXT");
+                if swap_interval.is_null() {
+                    return false;
+                }
+                
+                // SAFETY: the pointer is wglSwapIntervalEXT, which the extension string advertises
+                unsafe {
+                    let swap_interval: unsafe extern "system" fn(std::ffi::c_int) -> std::ffi::c_int = std::mem::transmute(swap_interval);
+                    swap_interval(-1) != 0
+                }
+            }
+            _ => false,
+        }
+    }
+
     /// Swap the front and back buffers.
+    /// 
+    /// This does not schedule another frame; call `request_redraw` for that.
+    /// Returns `TemplateError::ContextLost` when the driver reports the context as lost.
     pub fn swap_buffers(&self) -> TemplateResult<()> {
         self.surface.swap_buffers(&self.context)
-            .map_err(|e| TemplateError::OpenGL(e.to_string()))?;
-        self.handle.request_redraw();
+            .map_err(|e| map_swap_error(&e))
+    }
+
+    /// Swap the buffers, telling the compositor only the given rectangles changed.
+    /// 
+    /// Uses `EGL_KHR_swap_buffers_with_damage` on EGL displays, which lets the
+    /// compositor skip recompositing and scanning out unchanged parts of a mostly static
+    /// frame. Elsewhere this is a normal `swap_buffers`. The whole back buffer must still
+    /// hold the complete frame; damage only limits what gets presented. An empty slice
+    /// marks the entire window as damaged.
+    /// 
+    /// # Arguments
+    /// * `rects` - Changed regions as `(x, y, width, height)` in window pixels, y down
+    /// 
+    /// # Errors
+    /// `TemplateError::ContextLost` if the context was lost, or `TemplateError::OpenGL` if
+    /// the buffers cannot be swapped
+    pub fn swap_buffers_with_damage(&self, rects: &[(i32, i32, i32, i32)]) -> TemplateResult<()> {
+        #[cfg(all(any(windows, unix), not(target_vendor = "apple"), not(target_family = "wasm")))]
+        #[allow(irrefutable_let_patterns)]
+        if let (glutin::surface::Surface::Egl(surface), glutin::context::PossiblyCurrentContext::Egl(context)) = (&self.surface, &self.context) {
+            // EGL damage rectangles have their origin at the bottom left
+            let window_height = gl_size(self.handle.inner_size().height);
+            let damage: Vec<glutin::surface::Rect> = rects.iter()
+                .map(|&(x, y, width, height)| glutin::surface::Rect::new(x, window_height - y - height, width, height))
+                .collect();
+            
+            return match surface.swap_buffers_with_damage(context, &damage) {
+                Ok(()) => Ok(()),
+                Err(e) if matches!(e.error_kind(), glutin::error::ErrorKind::NotSupported(_)) => self.swap_buffers(),
+                Err(e) => Err(map_swap_error(&e)),
+            };
+        }
+        #[cfg(not(all(any(windows, unix), not(target_vendor = "apple"), not(target_family = "wasm"))))]
+        let _ = rects;
+        
+        self.swap_buffers()
+    }
+
+    /// Replace the OpenGL context with a freshly created one bound to the same surface.
+    /// 
+    /// All GL objects from the old context (buffers, programs, textures) are invalid afterwards.
+    /// 
+    /// # Errors
+    /// `TemplateError::WindowCreation` if the new context cannot be created or made current
+    pub fn recreate_context(&mut self) -> TemplateResult<()> {
+        info!("Recreating OpenGL context");
+        
+        let context = Self::create_context(&self.handle, &self.config, &self.display, self.api)?;
+        self.context = Self::make_context_current(context, &self.surface)?;
+        Self::configure_surface(&self.display, &self.surface, &self.context, self.swap_mode)?;
+        
+        info!("OpenGL context recreated successfully");
         Ok(())
     }
 
+    /// Ask winit to deliver a `RedrawRequested` event for this window.
+    pub fn request_redraw(&self) {
+        self.handle.request_redraw();
+    }
+
+    /// Make this window's OpenGL context current on the calling thread.
+    /// 
+    /// Required before issuing GL calls when more than one window is alive.
+    /// 
+    /// # Errors
+    /// `TemplateError::OpenGL` if the context cannot be made current
+    pub fn make_current(&self) -> TemplateResult<()> {
+        self.context.make_current(&self.surface)
+            .map_err(|e| TemplateError::OpenGL(e.to_string()))
+    }
+
+    /// Get the id used by winit to route events to this window.
+    #[must_use]
+    pub fn id(&self) -> winit::window::WindowId {
+        self.handle.id()
+    }
+
+    /// Create a context that shares buffers, textures and programs with this window's context.
+    /// 
+    /// The new context is not current anywhere and can be moved to another thread.
+    /// 
+    /// # Arguments
+    /// * `version` - Version to request, normally that of the existing context
+    pub(crate) fn create_shared_context(&self, version: (u8, u8)) -> TemplateResult<glutin::context::NotCurrentContext> {
+        let window_handle = self.handle.window_handle()
+            .map_err(|e| TemplateError::WindowCreation(e.to_string()))?;
+        
+        let version = Some(glutin::context::Version::new(version.0, version.1));
+        let context_api = match self.api {
+            GlApi::OpenGl => glutin::context::ContextApi::OpenGl(version),
+            GlApi::Gles => glutin::context::ContextApi::Gles(version),
+        };
+        let context_attributes = glutin::context::ContextAttributesBuilder::new()
+            .with_context_api(context_api)
+            .with_sharing(&self.context)
+            .build(Some(window_handle.as_raw()));
+        
+        unsafe { self.display.create_context(&self.config, &context_attributes) }
+            .map_err(|e| TemplateError::WindowCreation(format!("shared context creation failed: {e}")))
+    }
+
     /// Get the address of an OpenGL function.
     pub fn get_proc_address(&self, addr: &std::ffi::CStr) -> *const std::ffi::c_void {
         self.display.get_proc_address(addr)
@@ -134,4 +418,21 @@ impl Window {
     pub fn handle(&self) -> &winit::window::Window {
         &self.handle
     }
+}
+
+// ============================================================
+// ===================== Helper Functions =====================
+// ============================================================
+
+/// Convert a failed swap into a template error, keeping context loss distinguishable.
+fn map_swap_error(e: &glutin::error::Error) -> TemplateError {
+    match e.error_kind() {
+        glutin::error::ErrorKind::ContextLost => TemplateError::ContextLost(e.to_string()),
+        _ => TemplateError::OpenGL(e.to_string()),
+    }
+}
+
+/// Take the first GL config the display offers.
+fn pick_config(mut configs: Box<dyn Iterator<Item = glutin::config::Config> + '_>) -> glutin::config::Config {
+    configs.next().expect("display offered no GL configs")
 }
\ No newline at end of file
@@ -0,0 +1,67 @@
+//! Background preparation of GPU resources.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+// ============================================================
+// ===================== Structs & Impls ======================
+// ============================================================
+
+/// CPU work running on a worker thread whose result is uploaded on the GL thread.
+/// 
+/// The closure must not touch OpenGL; it produces plain data (vertices, decoded pixels)
+/// that the GL thread later passes to `Mesh::from_data`, `Texture::upload` and the like.
+pub struct WorkerTask<T> {
+    receiver: Receiver<T>,
+    done: bool,
+}
+
+impl<T: Send + 'static> WorkerTask<T> {
+    /// Start running `prepare` on a new thread.
+    pub fn spawn(prepare: impl FnOnce() -> T + Send + 'static) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            // The receiver may have been dropped if the result is no longer wanted
+            let _ = sender.send(prepare());
+        });
+        Self { receiver, done: false }
+    }
+
+    /// Take the result if the work has finished, without blocking.
+    /// 
+    /// # Returns
+    /// The result exactly once, `None` while still running, after it was taken, or if the worker panicked
+    pub fn poll(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        
+        match self.receiver.try_recv() {
+            Ok(result) => {
+                self.done = true;
+                Some(result)
+            }
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                tracing::error!("Worker task ended without producing a result");
+                self.done = true;
+                None
+            }
+        }
+    }
+
+    /// Block until the work has finished and take the result.
+    /// 
+    /// # Returns
+    /// The result, or `None` if it was already taken or the worker panicked
+    #[must_use]
+    pub fn wait(self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        self.receiver.recv().ok()
+    }
+}
\ No newline at end of file
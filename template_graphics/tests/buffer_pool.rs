@@ -0,0 +1,41 @@
+//! `BufferPool` handing a retired mesh's objects to the next mesh of the same size.
+
+mod common;
+
+use template_graphics::{BufferPool, Mesh};
+
+#[test]
+#[ignore = "needs a display and OpenGL driver"]
+fn recycled_objects_are_reused() {
+    let event_loop = common::event_loop();
+    let renderer = common::test_renderer(&event_loop);
+    let gl = &renderer.gl;
+    let mut pool = BufferPool::new();
+    
+    let triangle = [
+        -0.5, -0.5, 0.0, 1.0, 0.0, 0.0,
+         0.5, -0.5, 0.0, 0.0, 1.0, 0.0,
+         0.0,  0.5, 0.0, 0.0, 0.0, 1.0,
+    ];
+    let first = Mesh::new_pooled(gl, &mut pool, &triangle).expect("failed to create pooled mesh");
+    let buffer = first.vertex_buffer().handle();
+    assert_eq!(pool.reuse_count(), 0);
+    
+    // Retiring the mesh hands back its buffer and VAO
+    first.recycle(gl, &mut pool);
+    assert_eq!(pool.free_count(), 2);
+    
+    let second = Mesh::new_pooled(gl, &mut pool, &triangle).expect("failed to create pooled mesh");
+    assert_eq!(pool.reuse_count(), 2);
+    assert_eq!(pool.free_count(), 0);
+    assert_eq!(second.vertex_buffer().handle(), buffer);
+    assert_eq!(second.read_vertices(gl).expect("failed to read vertices"), triangle);
+    
+    // A static mesh only gives back its VAO; its buffer is deleted
+    let fixed = Mesh::new(gl, &triangle);
+    fixed.recycle(gl, &mut pool);
+    assert_eq!(pool.free_count(), 1);
+    
+    second.delete(gl);
+    pool.delete(gl);
+}
\ No newline at end of file
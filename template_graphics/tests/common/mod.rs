@@ -0,0 +1,31 @@
+//! Hidden-window renderer shared by the integration tests that need an OpenGL driver.
+//! 
+//! Each test file is its own process, and winit allows only one event loop per process,
+//! so every file creates one renderer for a single test.
+
+// ============================================================
+// ========================= Imports ==========================
+// ============================================================
+
+use template_graphics::Renderer;
+use winit::event_loop::EventLoop;
+
+// ============================================================
+// ==================== Global Functions ======================
+// ============================================================
+
+/// Create an event loop, allowing the test harness's worker thread to own it.
+pub fn event_loop() -> EventLoop<()> {
+    let mut builder = EventLoop::builder();
+    #[cfg(all(unix, not(target_vendor = "apple"), not(target_os = "android")))]
+    winit::platform::x11::EventLoopBuilderExtX11::with_any_thread(&mut builder, true);
+    #[cfg(windows)]
+    winit::platform::windows::EventLoopBuilderExtWindows::with_any_thread(&mut builder, true);
+    builder.build().expect("failed to create event loop")
+}
+
+/// Create a small renderer, running from the workspace root so its resources load.
+pub fn test_renderer(event_loop: &EventLoop<()>) -> Renderer {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).expect("failed to enter the workspace root");
+    Renderer::new(64, 64, "test", event_loop).expect("failed to create renderer")
+}
\ No newline at end of file
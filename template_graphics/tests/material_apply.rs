@@ -0,0 +1,52 @@
+//! `Material::apply` uploading every stored uniform.
+
+mod common;
+
+use nalgebra::{Matrix4, Vector3};
+use template_graphics::{Material, Shader, UniformValue};
+
+const VERTEX_SOURCE: &str = "#version 330 core
+layout (location = 0) in vec3 aPos;
+uniform mat4 model;
+uniform vec3 offset;
+uniform float scale;
+void main() {
+    gl_Position = model * vec4(aPos * scale + offset, 1.0);
+}";
+
+const FRAGMENT_SOURCE: &str = "#version 330 core
+uniform vec4 tint;
+uniform int mode;
+out vec4 FragColor;
+void main() {
+    FragColor = mode == 1 ? tint : vec4(1.0);
+}";
+
+#[test]
+#[ignore = "needs a display and OpenGL driver"]
+fn apply_sets_each_stored_uniform() {
+    let event_loop = common::event_loop();
+    let renderer = common::test_renderer(&event_loop);
+    let gl = &renderer.gl;
+    let preamble = Some(renderer.api().shader_preamble());
+    let mut shader = Shader::from_source(gl, VERTEX_SOURCE, FRAGMENT_SOURCE, preamble).expect("failed to build shader");
+    
+    let model = Matrix4::new_translation(&Vector3::new(1.0, 2.0, 3.0));
+    let mut material = Material::new();
+    material
+        .set("model", UniformValue::Matrix4(model))
+        .set("offset", UniformValue::Vec3([0.5, -0.5, 0.25]))
+        .set("tint", UniformValue::Vec4([0.1, 0.2, 0.3, 0.4]))
+        .set("scale", UniformValue::Float(2.5))
+        .set("mode", UniformValue::Int(1));
+    
+    shader.bind(gl);
+    material.apply(gl, &mut shader).expect("failed to apply material");
+    
+    assert_eq!(shader.get_uniform_matrix4(gl, "model"), Some(model));
+    assert_eq!(shader.get_uniform_vec3(gl, "offset"), Some([0.5, -0.5, 0.25]));
+    assert_eq!(shader.get_uniform_vec4(gl, "tint"), Some([0.1, 0.2, 0.3, 0.4]));
+    assert_eq!(shader.get_uniform_float(gl, "scale"), Some(2.5));
+    assert_eq!(shader.get_uniform_int(gl, "mode"), Some(1));
+    shader.delete(gl);
+}
\ No newline at end of file
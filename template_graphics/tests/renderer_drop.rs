@@ -0,0 +1,45 @@
+//! Tearing down a `Renderer` after the objects built from it were deleted.
+
+mod common;
+
+use glow::HasContext;
+use nalgebra::Matrix4;
+use template_graphics::{Camera, CameraProjection, Mesh, Shader};
+
+#[test]
+#[ignore = "needs a display and OpenGL driver"]
+fn renderer_releases_its_objects_on_drop() {
+    let event_loop = common::event_loop();
+    let renderer = common::test_renderer(&event_loop);
+    
+    let triangle = [
+        -0.5, -0.5, 0.0, 1.0, 0.0, 0.0,
+         0.5, -0.5, 0.0, 0.0, 1.0, 0.0,
+         0.0,  0.5, 0.0, 0.0, 0.0, 1.0,
+    ];
+    let mesh = Mesh::new(&renderer.gl, &triangle);
+    // Fill the renderer's cached helpers so its Drop has GL objects to release
+    renderer.clear();
+    let projection = CameraProjection::default().perspective(64, 64);
+    renderer.draw_grid(&Camera::default(), &projection, 10.0, 1.0).expect("failed to draw grid");
+    renderer.draw_text("drop", 0.0, 0.0, 1.0, [1.0; 4]).expect("failed to draw text");
+    let mut shader = Shader::with_preamble(&renderer.gl, "basic.vert", "basic.frag", Some(renderer.api().shader_preamble())).expect("failed to build shader");
+    shader.bind(&renderer.gl);
+    for name in ["view", "projection"] {
+        shader.set_matrix4(&renderer.gl, name, &Matrix4::identity());
+    }
+    // The second call reuses the cached edge buffer
+    for _ in 0..2 {
+        renderer.draw_solid_wireframe(&mesh, &mut shader, &Matrix4::identity(), [1.0; 3]).expect("failed to draw wireframe");
+    }
+    
+    // Objects built from the context go first, while it is still alive
+    mesh.delete(&renderer.gl);
+    shader.delete(&renderer.gl);
+    assert_eq!(unsafe { renderer.gl.get_error() }, glow::NO_ERROR);
+    drop(renderer);
+    
+    // The renderer's Drop released its helpers, timer queries and caches
+    #[cfg(all(feature = "leak-check", debug_assertions))]
+    assert_eq!(template_graphics::live_object_count(), 0);
+}
@@ -0,0 +1,44 @@
+//! Two VAOs reading one vertex buffer through `Mesh::share_vertices`.
+
+mod common;
+
+use glow::HasContext;
+use std::sync::Arc;
+use template_graphics::{Mesh, Shader, VertexAttribute, VertexLayout};
+
+#[test]
+#[ignore = "needs a display and OpenGL driver"]
+fn shared_mesh_reads_the_source_buffer() {
+    let event_loop = common::event_loop();
+    let renderer = common::test_renderer(&event_loop);
+    let gl = &renderer.gl;
+    
+    let triangle = [
+        -0.5, -0.5, 0.0, 1.0, 0.0, 0.0,
+         0.5, -0.5, 0.0, 0.0, 1.0, 0.0,
+         0.0,  0.5, 0.0, 0.0, 0.0, 1.0,
+    ];
+    let source = Mesh::new(gl, &triangle);
+    // Same stride, color fed to another location, as a second pass's shader might expect
+    let layout = VertexLayout::new(vec![VertexAttribute::float(0, 3), VertexAttribute::float(2, 3)]);
+    let shared = Mesh::share_vertices(gl, &source, &layout).expect("failed to share vertices");
+    
+    assert!(Arc::ptr_eq(source.vertex_buffer(), shared.vertex_buffer()));
+    assert_eq!(Arc::strong_count(source.vertex_buffer()), 2);
+    assert_ne!(source.layout(), shared.layout());
+    assert_eq!(shared.read_vertices(gl).expect("failed to read shared vertices"), triangle);
+    
+    let shader = Shader::with_preamble(gl, "basic.vert", "basic.frag", Some(renderer.api().shader_preamble())).expect("failed to build shader");
+    shader.bind(gl);
+    while unsafe { gl.get_error() } != glow::NO_ERROR {}
+    source.draw(gl);
+    shared.draw(gl);
+    assert_eq!(unsafe { gl.get_error() }, glow::NO_ERROR);
+    shader.delete(gl);
+    
+    // The buffer outlives the mesh that created it
+    source.delete(gl);
+    assert_eq!(Arc::strong_count(shared.vertex_buffer()), 1);
+    assert_eq!(shared.read_vertices(gl).expect("failed to read shared vertices"), triangle);
+    shared.delete(gl);
+}
\ No newline at end of file
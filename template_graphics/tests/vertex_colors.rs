@@ -0,0 +1,72 @@
+//! Float and normalized `u8` vertex colors rendering the same image.
+
+mod common;
+
+use glow::HasContext;
+use nalgebra::Matrix4;
+use template_graphics::{compare_images, Framebuffer, Mesh, PackedColorVertex, Renderer, Shader};
+
+/// Counter-clockwise quad covering the viewport, with a different color at each corner.
+const QUAD: [([f32; 3], [u8; 4]); 6] = [
+    ([-1.0, -1.0, 0.0], [255, 0, 0, 255]),
+    ([ 1.0, -1.0, 0.0], [0, 255, 0, 255]),
+    ([ 1.0,  1.0, 0.0], [0, 0, 255, 255]),
+    ([-1.0, -1.0, 0.0], [255, 0, 0, 255]),
+    ([ 1.0,  1.0, 0.0], [0, 0, 255, 255]),
+    ([-1.0,  1.0, 0.0], [51, 102, 153, 255]),
+];
+
+/// Clear the bound framebuffer, draw `mesh` and read the result back.
+fn render(renderer: &Renderer, shader: &Shader, mesh: &Mesh) -> image::RgbaImage {
+    unsafe {
+        renderer.gl.clear_color(0.0, 0.0, 0.0, 1.0);
+        renderer.gl.clear(glow::COLOR_BUFFER_BIT);
+    }
+    shader.bind(&renderer.gl);
+    mesh.draw(&renderer.gl);
+    renderer.read_pixels()
+}
+
+#[test]
+#[ignore = "needs a display and OpenGL driver"]
+fn packed_colors_match_float_colors() {
+    let event_loop = common::event_loop();
+    let renderer = common::test_renderer(&event_loop);
+    let gl = &renderer.gl;
+    let (width, height) = renderer.size();
+    let target = Framebuffer::new(gl, width, height).expect("failed to create framebuffer");
+    target.bind(gl);
+    unsafe {
+        gl.disable(glow::DEPTH_TEST);
+    }
+
+    let mut shader = Shader::with_preamble(gl, "basic_rgba.vert", "basic_rgba.frag", Some(renderer.api().shader_preamble())).expect("failed to build shader");
+    shader.bind(gl);
+    for name in ["model", "view", "projection"] {
+        shader.set_matrix4(gl, name, &Matrix4::identity());
+    }
+
+    let float_vertices: Vec<f32> = QUAD.iter()
+        .flat_map(|(position, color)| position.iter().copied().chain(color.iter().map(|&c| f32::from(c) / 255.0)))
+        .collect();
+    let packed_vertices: Vec<PackedColorVertex> = QUAD.iter()
+        .map(|&(position, color)| PackedColorVertex::new(position, color))
+        .collect();
+    let float_mesh = Mesh::new_rgba(gl, &float_vertices).expect("failed to create float mesh");
+    let packed_mesh = Mesh::new_packed_rgba(gl, &packed_vertices).expect("failed to create packed mesh");
+
+    let float_image = render(&renderer, &shader, &float_mesh);
+    let packed_image = render(&renderer, &shader, &packed_mesh);
+
+    // Both colors reach the shader as the same floats; allow for interpolation rounding
+    let comparison = compare_images(&packed_image, &float_image, 2).expect("images differ in size");
+    assert!(comparison.matches(), "{comparison:?}");
+    // The quad actually covered the target
+    assert_ne!(float_image.get_pixel(1, height - 2).0, [0, 0, 0, 255]);
+
+    float_mesh.delete(gl);
+    packed_mesh.delete(gl);
+    shader.delete(gl);
+    Framebuffer::unbind(gl);
+    target.delete(gl);
+}